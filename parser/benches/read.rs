@@ -0,0 +1,63 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+use parser::read_buffer;
+use parser::view::ClassFileView;
+
+const CLASSES: [(&str, &[u8]); 4] = [
+    ("Constants", include_bytes!("../tests/classes/Constants.class")),
+    ("Complex", include_bytes!("../tests/classes/Complex.class")),
+    ("Lambda", include_bytes!("../tests/classes/Lambda.class")),
+    ("Switch", include_bytes!("../tests/classes/Switch.class")),
+];
+
+/// 建立索引时的典型用法: 读取类名以及每个方法的名称、描述符与字节码长度
+fn index_eager(bytes: &[u8]) -> usize {
+    let class_file = read_buffer(bytes).unwrap();
+    let mut size = class_file.name.len();
+    for method in &class_file.methods {
+        size += method.name.len() + method.type_descriptor.len();
+        size += method.code.as_ref().map_or(0, |code| code.code.len());
+    }
+    size
+}
+
+fn index_view(bytes: &[u8]) -> usize {
+    let view = ClassFileView::parse(bytes).unwrap();
+    let mut size = view.name().unwrap().len();
+    for method in view.methods() {
+        size += method.name().unwrap().len() + method.descriptor().unwrap().len();
+        size += method.code().unwrap().map_or(0, |code| code.code.len());
+    }
+    size
+}
+
+fn bench_read(c: &mut Criterion) {
+    let mut group = c.benchmark_group("read");
+    for (name, bytes) in CLASSES {
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+        group.bench_with_input(BenchmarkId::new("eager", name), bytes, |b, bytes| {
+            b.iter(|| read_buffer(black_box(bytes)).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("view", name), bytes, |b, bytes| {
+            b.iter(|| ClassFileView::parse(black_box(bytes)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn bench_index(c: &mut Criterion) {
+    let mut group = c.benchmark_group("index");
+    for (name, bytes) in CLASSES {
+        group.throughput(Throughput::Bytes(bytes.len() as u64));
+        group.bench_with_input(BenchmarkId::new("eager", name), bytes, |b, bytes| {
+            b.iter(|| index_eager(black_box(bytes)))
+        });
+        group.bench_with_input(BenchmarkId::new("view", name), bytes, |b, bytes| {
+            b.iter(|| index_view(black_box(bytes)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_read, bench_index);
+criterion_main!(benches);
@@ -0,0 +1,299 @@
+use std::fmt;
+use std::fmt::Formatter;
+
+use itertools::Itertools;
+
+use crate::constant_pool::constant_pool::{ConstantPool, ConstantPoolEntry};
+use crate::error::{ClassFileParserError, ClassFileParserResult};
+use crate::utils::buffer::Buffer;
+use crate::utils::types::Type;
+
+/// 一个注解, 例如 `@Entity(name = "users")`
+/// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.16
+#[derive(Debug, Clone, PartialEq)]
+pub struct Annotation {
+    /// 注解类型的内部名称, 例如 `javax/persistence/Entity`
+    pub type_name: String,
+    /// 显式给出的元素, 使用默认值的元素不会出现在 class 文件中
+    pub elements: Vec<ElementValuePair>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementValuePair {
+    pub name: String,
+    pub value: ElementValue,
+}
+
+/// 注解元素的值
+/// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.16.1
+#[derive(Debug, Clone, PartialEq)]
+pub enum ElementValue {
+    Const(ConstValue),
+    /// 枚举常量, 例如 `ElementType.TYPE`
+    Enum { type_name: String, const_name: String },
+    /// 类字面量的返回值描述符, 例如 `Ljava/lang/String;`, `void.class` 是 `V`
+    Class(String),
+    Annotation(Annotation),
+    Array(Vec<ElementValue>),
+}
+
+/// 基本类型以及字符串常量
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstValue {
+    Byte(i8),
+    /// UTF-16 编码单元, 可能是单独的代理项
+    Char(u16),
+    Double(f64),
+    Float(f32),
+    Int(i32),
+    Long(i64),
+    Short(i16),
+    Boolean(bool),
+    String(String),
+}
+
+/// 同一个位置上运行时可见与不可见的注解,
+/// 分别来自 Runtime[In]VisibleAnnotations 或者 Runtime[In]VisibleParameterAnnotations 属性
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Annotations {
+    /// `@Retention(RUNTIME)` 的注解
+    pub visible: Vec<Annotation>,
+    /// `@Retention(CLASS)` 的注解
+    pub invisible: Vec<Annotation>,
+}
+
+impl Annotation {
+    /// 读取 RuntimeVisibleAnnotations 或 RuntimeInvisibleAnnotations 属性的内容
+    pub(crate) fn read_all(bytes: &[u8], constants: &ConstantPool) -> ClassFileParserResult<Vec<Annotation>> {
+        let mut buf = Buffer::new(bytes);
+        Self::read_list(&mut buf, constants)
+    }
+
+    /// 读取 Runtime[In]VisibleParameterAnnotations 属性的内容, 每个参数一组注解。
+    /// 注意参数的个数可能少于方法描述符中的参数个数, 例如编译器为内部类构造器生成的参数没有注解
+    pub(crate) fn read_parameters(
+        bytes: &[u8],
+        constants: &ConstantPool,
+    ) -> ClassFileParserResult<Vec<Vec<Annotation>>> {
+        let mut buf = Buffer::new(bytes);
+        let num_parameters = buf.read_u8()?;
+        (0..num_parameters)
+            .map(|_| Self::read_list(&mut buf, constants))
+            .collect()
+    }
+
+    fn read_list(buf: &mut Buffer, constants: &ConstantPool) -> ClassFileParserResult<Vec<Annotation>> {
+        let num_annotations = buf.read_u16()?;
+        (0..num_annotations).map(|_| Self::read(buf, constants)).collect()
+    }
+
+    pub(crate) fn read(buf: &mut Buffer, constants: &ConstantPool) -> ClassFileParserResult<Annotation> {
+        Self::read_nested(buf, constants, 0)
+    }
+
+    /// depth 是外层 element_value 的层数, 嵌套的注解与数组是递归读取的
+    fn read_nested(buf: &mut Buffer, constants: &ConstantPool, depth: usize) -> ClassFileParserResult<Annotation> {
+        let type_name = object_type_name(&utf8_of(constants, buf.read_u16()?)?)?;
+        let num_element_value_pairs = buf.read_u16()?;
+        let elements = (0..num_element_value_pairs)
+            .map(|_| {
+                let name = utf8_of(constants, buf.read_u16()?)?;
+                let value = ElementValue::read(buf, constants, depth + 1)?;
+                Ok(ElementValuePair { name, value })
+            })
+            .collect::<ClassFileParserResult<Vec<ElementValuePair>>>()?;
+        Ok(Annotation { type_name, elements })
+    }
+
+    /// 查找显式给出的元素
+    pub fn element(&self, name: &str) -> Option<&ElementValue> {
+        self.elements
+            .iter()
+            .find(|element| element.name == name)
+            .map(|element| &element.value)
+    }
+}
+
+impl ElementValue {
+    /// 读取注解类型中方法的 AnnotationDefault 属性
+    pub(crate) fn read_default(bytes: &[u8], constants: &ConstantPool) -> ClassFileParserResult<ElementValue> {
+        let mut buf = Buffer::new(bytes);
+        Self::read(&mut buf, constants, 1)
+    }
+
+    fn read(buf: &mut Buffer, constants: &ConstantPool, depth: usize) -> ClassFileParserResult<ElementValue> {
+        if depth > MAX_NESTING_DEPTH {
+            return Err(ClassFileParserError::InvalidClassData {
+                name: format!("element_value is nested deeper than {MAX_NESTING_DEPTH} levels"),
+                is_invalidate_constant_pool_idx: false,
+            });
+        }
+        let tag = buf.read_u8()?;
+        let value = match tag {
+            b'B' | b'C' | b'I' | b'S' | b'Z' => {
+                let index = buf.read_u16()?;
+                let value = match constants.get_entry(index)? {
+                    ConstantPoolEntry::Integer(value) => *value,
+                    entry => return Err(unexpected_constant("Integer", index, entry)),
+                };
+                // 与字节码一样, 这些类型都使用 Integer 常量保存, 截断即可得到原来的值
+                ElementValue::Const(match tag {
+                    b'B' => ConstValue::Byte(value as i8),
+                    b'C' => ConstValue::Char(value as u16),
+                    b'S' => ConstValue::Short(value as i16),
+                    b'Z' => ConstValue::Boolean(value != 0),
+                    _ => ConstValue::Int(value),
+                })
+            }
+            b'D' => {
+                let index = buf.read_u16()?;
+                match constants.get_entry(index)? {
+                    ConstantPoolEntry::Double(value) => ElementValue::Const(ConstValue::Double(*value)),
+                    entry => return Err(unexpected_constant("Double", index, entry)),
+                }
+            }
+            b'F' => {
+                let index = buf.read_u16()?;
+                match constants.get_entry(index)? {
+                    ConstantPoolEntry::Float(value) => ElementValue::Const(ConstValue::Float(*value)),
+                    entry => return Err(unexpected_constant("Float", index, entry)),
+                }
+            }
+            b'J' => {
+                let index = buf.read_u16()?;
+                match constants.get_entry(index)? {
+                    ConstantPoolEntry::Long(value) => ElementValue::Const(ConstValue::Long(*value)),
+                    entry => return Err(unexpected_constant("Long", index, entry)),
+                }
+            }
+            b's' => ElementValue::Const(ConstValue::String(utf8_of(constants, buf.read_u16()?)?)),
+            b'e' => {
+                let type_name = object_type_name(&utf8_of(constants, buf.read_u16()?)?)?;
+                let const_name = utf8_of(constants, buf.read_u16()?)?;
+                ElementValue::Enum { type_name, const_name }
+            }
+            b'c' => ElementValue::Class(utf8_of(constants, buf.read_u16()?)?),
+            b'@' => ElementValue::Annotation(Annotation::read_nested(buf, constants, depth)?),
+            b'[' => {
+                let num_values = buf.read_u16()?;
+                let values = (0..num_values)
+                    .map(|_| Self::read(buf, constants, depth + 1))
+                    .collect::<ClassFileParserResult<Vec<ElementValue>>>()?;
+                ElementValue::Array(values)
+            }
+            _ => {
+                return Err(ClassFileParserError::InvalidClassData {
+                    name: format!("invalid element_value tag {:?}", tag as char),
+                    is_invalidate_constant_pool_idx: false,
+                })
+            }
+        };
+        Ok(value)
+    }
+}
+
+impl Annotations {
+    pub fn is_empty(&self) -> bool {
+        self.visible.is_empty() && self.invisible.is_empty()
+    }
+
+    /// 先是运行时可见的注解, 然后是不可见的注解
+    pub fn iter(&self) -> impl Iterator<Item = &Annotation> {
+        self.visible.iter().chain(self.invisible.iter())
+    }
+
+    /// 根据注解类型的内部名称查找注解, 例如 `org/junit/Test`
+    pub fn find(&self, type_name: &str) -> Option<&Annotation> {
+        self.iter().find(|annotation| annotation.type_name == type_name)
+    }
+
+    pub fn contains(&self, type_name: &str) -> bool {
+        self.find(type_name).is_some()
+    }
+}
+
+/// 嵌套的注解与数组在读取时会递归, 限制嵌套的层数, 避免恶意构造的 class 文件耗尽栈空间
+const MAX_NESTING_DEPTH: usize = 64;
+
+fn utf8_of(constants: &ConstantPool, index: u16) -> ClassFileParserResult<String> {
+    match constants.get_entry(index)? {
+        ConstantPoolEntry::Utf8(text) => Ok(text.clone()),
+        entry => Err(unexpected_constant("Utf8", index, entry)),
+    }
+}
+
+fn unexpected_constant(expected: &str, index: u16, entry: &ConstantPoolEntry) -> ClassFileParserError {
+    ClassFileParserError::InvalidClassData {
+        name: format!("expected a {expected} constant at index {index} in annotation but found {entry:?}"),
+        is_invalidate_constant_pool_idx: true,
+    }
+}
+
+/// 注解与枚举的类型以字段描述符的形式保存, 例如 `Ljava/lang/annotation/ElementType;`
+fn object_type_name(descriptor: &str) -> ClassFileParserResult<String> {
+    match Type::parse(descriptor)? {
+        Type::Object(name) => Ok(name),
+        _ => Err(ClassFileParserError::InvalidFiledTypeDescriptor(descriptor.to_string())),
+    }
+}
+
+/// Java 源码中的类型名称, 例如 `java.lang.String[]`
+fn java_type(field_type: &Type) -> String {
+    match field_type {
+        Type::Base(base) => base.java_name().to_string(),
+        Type::Object(name) => name.replace('/', "."),
+        Type::Array(component_type) => format!("{}[]", java_type(component_type)),
+    }
+}
+
+/// 按照 Java 源码的形式输出, 例如 `@javax.persistence.Entity(name="users")`
+impl fmt::Display for Annotation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "@{}", self.type_name.replace('/', "."))?;
+        if !self.elements.is_empty() {
+            write!(f, "({})", self.elements.iter().join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ElementValuePair {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}={}", self.name, self.value)
+    }
+}
+
+impl fmt::Display for ElementValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ElementValue::Const(value) => write!(f, "{value}"),
+            ElementValue::Enum { type_name, const_name } => write!(f, "{}.{const_name}", type_name.replace('/', ".")),
+            ElementValue::Class(descriptor) => match Type::parse(descriptor) {
+                Ok(class_type) => write!(f, "{}.class", java_type(&class_type)),
+                Err(_) if descriptor == "V" => f.write_str("void.class"),
+                Err(_) => write!(f, "{descriptor}.class"),
+            },
+            ElementValue::Annotation(annotation) => write!(f, "{annotation}"),
+            ElementValue::Array(values) => write!(f, "{{{}}}", values.iter().join(", ")),
+        }
+    }
+}
+
+impl fmt::Display for ConstValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ConstValue::Byte(value) => write!(f, "{value}"),
+            ConstValue::Char(value) => match char::from_u32(u32::from(*value)) {
+                Some(c) => write!(f, "{c:?}"),
+                None => write!(f, "'\\u{value:04x}'"),
+            },
+            ConstValue::Double(value) => write!(f, "{value:?}"),
+            ConstValue::Float(value) => write!(f, "{value:?}f"),
+            ConstValue::Int(value) => write!(f, "{value}"),
+            ConstValue::Long(value) => write!(f, "{value}L"),
+            ConstValue::Short(value) => write!(f, "{value}"),
+            ConstValue::Boolean(value) => write!(f, "{value}"),
+            ConstValue::String(value) => write!(f, "{value:?}"),
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod annotations;
+pub mod type_annotations;
+#[cfg(test)]
+mod test;
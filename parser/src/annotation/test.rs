@@ -0,0 +1,206 @@
+use crate::annotation::annotations::{Annotation, Annotations, ConstValue, ElementValue, ElementValuePair};
+use crate::annotation::type_annotations::{LocalVariableTarget, TypeAnnotation, TypeAnnotationTarget, TypePathEntry};
+use crate::constant_pool::constant_pool::{ConstantPool, ConstantPoolEntry};
+use crate::error::ClassFileParserError;
+use crate::method::local_variable_table::{LocalVariable, LocalVariableTable};
+use crate::utils::pc::ProgramCounter;
+use crate::utils::types::Type;
+
+fn annotation_constants() -> ConstantPool {
+    let mut constants = ConstantPool::default();
+    constants.add_entry(ConstantPoolEntry::Utf8("Ljavax/persistence/Entity;".to_string())); // 1
+    constants.add_entry(ConstantPoolEntry::Utf8("name".to_string())); // 2
+    constants.add_entry(ConstantPoolEntry::Utf8("users".to_string())); // 3
+    constants.add_entry(ConstantPoolEntry::Utf8("flags".to_string())); // 4
+    constants.add_entry(ConstantPoolEntry::Integer(65)); // 5
+    constants.add_entry(ConstantPoolEntry::Utf8("Ljava/lang/annotation/ElementType;".to_string())); // 6
+    constants.add_entry(ConstantPoolEntry::Utf8("TYPE".to_string())); // 7
+    constants.add_entry(ConstantPoolEntry::Utf8("V".to_string())); // 8
+    constants
+}
+
+#[test]
+fn can_read_annotations() {
+    let constants = annotation_constants();
+    // @Entity(name = "users", flags = {'A', 65, ElementType.TYPE, void.class})
+    let bytes = [
+        0, 1, 0, 1, 0, 2, //
+        0, 2, b's', 0, 3, //
+        0, 4, b'[', 0, 4, b'C', 0, 5, b'I', 0, 5, b'e', 0, 6, 0, 7, b'c', 0, 8,
+    ];
+    let annotations = Annotation::read_all(&bytes, &constants).unwrap();
+    assert_eq!(
+        vec![Annotation {
+            type_name: "javax/persistence/Entity".to_string(),
+            elements: vec![
+                ElementValuePair {
+                    name: "name".to_string(),
+                    value: ElementValue::Const(ConstValue::String("users".to_string())),
+                },
+                ElementValuePair {
+                    name: "flags".to_string(),
+                    value: ElementValue::Array(vec![
+                        ElementValue::Const(ConstValue::Char(65)),
+                        ElementValue::Const(ConstValue::Int(65)),
+                        ElementValue::Enum {
+                            type_name: "java/lang/annotation/ElementType".to_string(),
+                            const_name: "TYPE".to_string(),
+                        },
+                        ElementValue::Class("V".to_string()),
+                    ]),
+                },
+            ],
+        }],
+        annotations
+    );
+    assert_eq!(
+        "@javax.persistence.Entity(name=\"users\", flags={'A', 65, java.lang.annotation.ElementType.TYPE, void.class})",
+        annotations[0].to_string()
+    );
+    assert_eq!(
+        Some(&ElementValue::Const(ConstValue::String("users".to_string()))),
+        annotations[0].element("name")
+    );
+
+    let annotations = Annotations {
+        visible: vec![],
+        invisible: annotations,
+    };
+    assert!(annotations.contains("javax/persistence/Entity"));
+    assert!(!annotations.contains("org/junit/Test"));
+}
+
+#[test]
+fn can_read_parameter_annotations() {
+    let constants = annotation_constants();
+    // 第一个参数没有注解, 第二个参数有一个 @Entity
+    let bytes = [2, 0, 0, 0, 1, 0, 1, 0, 0];
+    let parameters = Annotation::read_parameters(&bytes, &constants).unwrap();
+    assert_eq!(2, parameters.len());
+    assert!(parameters[0].is_empty());
+    assert_eq!("javax/persistence/Entity", parameters[1][0].type_name);
+}
+
+#[test]
+fn cannot_read_invalid_annotations() {
+    let constants = annotation_constants();
+    // 未知的 tag
+    let result = ElementValue::read_default(&[b'x', 0, 3], &constants);
+    assert!(matches!(result, Err(ClassFileParserError::InvalidClassData { .. })));
+    // 字符串元素指向了 Integer 常量
+    let result = ElementValue::read_default(&[b's', 0, 5], &constants);
+    assert!(matches!(
+        result,
+        Err(ClassFileParserError::InvalidClassData {
+            is_invalidate_constant_pool_idx: true,
+            ..
+        })
+    ));
+    assert_eq!(Err(ClassFileParserError::UnexpectedEndOfData), Annotation::read_all(&[0, 1, 0, 1], &constants));
+}
+
+#[test]
+fn cannot_read_deeply_nested_element_values() {
+    let constants = annotation_constants();
+    let nested_arrays = |depth: usize| {
+        let mut bytes = [b'[', 0, 1].repeat(depth - 1);
+        bytes.extend_from_slice(&[b'[', 0, 0]);
+        bytes
+    };
+    assert!(ElementValue::read_default(&nested_arrays(64), &constants).is_ok());
+    let too_deep = Err(ClassFileParserError::InvalidClassData {
+        name: "element_value is nested deeper than 64 levels".to_string(),
+        is_invalidate_constant_pool_idx: false,
+    });
+    assert_eq!(too_deep, ElementValue::read_default(&nested_arrays(65), &constants));
+
+    // @Entity(name = @Entity(name = @Entity(...)))
+    let mut bytes = vec![0, 1];
+    for _ in 0..100 {
+        bytes.extend_from_slice(&[0, 1, 0, 1, 0, 2, b'@']);
+    }
+    bytes.extend_from_slice(&[0, 1, 0, 0]);
+    assert_eq!(too_deep.map(|_| Vec::new()), Annotation::read_all(&bytes, &constants));
+}
+
+#[test]
+fn can_read_type_annotations() {
+    let constants = annotation_constants();
+    let bytes = [
+        0, 3, //
+        // Map<String, ? extends @Entity Number> 类型的字段
+        0x13, 2, 3, 1, 2, 0, 0, 1, 0, 0, //
+        // 局部变量, 作用域分为两段
+        0x40, 0, 2, 0, 2, 0, 6, 0, 1, 0, 10, 0, 4, 0, 1, 0, 0, 1, 0, 0, //
+        // 显式给出类型实参的泛型方法调用
+        0x49, 0, 7, 1, 0, 0, 1, 0, 0,
+    ];
+    let type_annotations = TypeAnnotation::read_all(&bytes, &constants).unwrap();
+    let entity = Annotation {
+        type_name: "javax/persistence/Entity".to_string(),
+        elements: vec![],
+    };
+    assert_eq!(
+        vec![
+            TypeAnnotation {
+                target: TypeAnnotationTarget::Field,
+                type_path: vec![TypePathEntry::TypeArgument(1), TypePathEntry::WildcardBound],
+                annotation: entity.clone(),
+            },
+            TypeAnnotation {
+                target: TypeAnnotationTarget::LocalVariable(vec![
+                    LocalVariableTarget {
+                        range: ProgramCounter(2)..ProgramCounter(8),
+                        slot: 1,
+                    },
+                    LocalVariableTarget {
+                        range: ProgramCounter(10)..ProgramCounter(14),
+                        slot: 1,
+                    },
+                ]),
+                type_path: vec![],
+                annotation: entity.clone(),
+            },
+            TypeAnnotation {
+                target: TypeAnnotationTarget::MethodInvocationTypeArgument {
+                    program_counter: ProgramCounter(7),
+                    type_argument_index: 1,
+                },
+                type_path: vec![],
+                annotation: entity,
+            },
+        ],
+        type_annotations
+    );
+    assert_eq!(
+        "@javax.persistence.Entity FIELD, location=[TYPE_ARGUMENT(1), WILDCARD]",
+        type_annotations[0].to_string()
+    );
+    assert_eq!(Some(ProgramCounter(7)), type_annotations[2].target.program_counter());
+    assert_eq!(None, type_annotations[1].target.program_counter());
+
+    let local_variable = |start: u16, end: u16, name: &str| LocalVariable {
+        range: ProgramCounter(start)..ProgramCounter(end),
+        slot: 1,
+        name: name.to_string(),
+        type_descriptor: Type::Object("java/lang/String".to_string()),
+        signature: None,
+    };
+    let table = LocalVariableTable::new(vec![local_variable(2, 8, "a"), local_variable(10, 14, "b")]);
+    let names: Vec<&str> = type_annotations[1].local_variables(&table).iter().map(|v| v.name.as_str()).collect();
+    assert_eq!(vec!["a", "b"], names);
+    assert!(type_annotations[0].local_variables(&table).is_empty());
+}
+
+#[test]
+fn cannot_read_invalid_type_annotations() {
+    let constants = annotation_constants();
+    let result = TypeAnnotation::read_all(&[0, 1, 0x20, 0, 0, 1, 0, 0], &constants);
+    assert!(matches!(result, Err(ClassFileParserError::InvalidClassData { .. })));
+    // type_path_kind 只能是 0 到 3
+    let result = TypeAnnotation::read_all(&[0, 1, 0x13, 1, 4, 0, 0, 1, 0, 0], &constants);
+    assert!(matches!(result, Err(ClassFileParserError::InvalidClassData { .. })));
+    // 局部变量的作用域超出了地址范围
+    let result = TypeAnnotation::read_all(&[0, 1, 0x40, 0, 1, 0xff, 0xff, 0, 1, 0, 0, 0, 0, 1, 0, 0], &constants);
+    assert!(matches!(result, Err(ClassFileParserError::InvalidClassData { .. })));
+}
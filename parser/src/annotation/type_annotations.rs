@@ -0,0 +1,398 @@
+use std::fmt;
+use std::fmt::Formatter;
+use std::ops::Range;
+
+use itertools::Itertools;
+
+use crate::annotation::annotations::Annotation;
+use crate::constant_pool::constant_pool::ConstantPool;
+use crate::error::{ClassFileParserError, ClassFileParserResult};
+use crate::method::exception_table::{ExceptionTable, ExceptionTableEntry};
+use crate::method::local_variable_table::{LocalVariable, LocalVariableTable};
+use crate::utils::buffer::Buffer;
+use crate::utils::pc::ProgramCounter;
+
+/// 标注在类型上的注解(JSR 308), 例如 `List<@Nullable String>`
+/// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.20
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeAnnotation {
+    /// 被注解的类型出现在什么位置
+    pub target: TypeAnnotationTarget,
+    /// 被注解的是位置上类型的哪一部分, 为空时就是整个类型
+    pub type_path: Vec<TypePathEntry>,
+    pub annotation: Annotation,
+}
+
+/// target_type 与 target_info, 下标都是相对于注解所在的类、字段、方法或者 Code 属性的
+/// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.20.1
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeAnnotationTarget {
+    /// 0x00, 类的第几个类型形参
+    ClassTypeParameter(u8),
+    /// 0x01, 方法的第几个类型形参
+    MethodTypeParameter(u8),
+    /// 0x10, 实现的第几个接口, [TypeAnnotationTarget::SUPERCLASS] 表示父类
+    Supertype(u16),
+    /// 0x11, 类的类型形参的上界
+    ClassTypeParameterBound { type_parameter_index: u8, bound_index: u8 },
+    /// 0x12, 方法的类型形参的上界
+    MethodTypeParameterBound { type_parameter_index: u8, bound_index: u8 },
+    /// 0x13, 字段或者 record 组件的类型
+    Field,
+    /// 0x14, 方法的返回值类型, 或者构造器创建的对象的类型
+    MethodReturn,
+    /// 0x15, 方法的接收者, 即显式声明的 this 参数
+    MethodReceiver,
+    /// 0x16, 方法的第几个参数
+    FormalParameter(u8),
+    /// 0x17, throws 子句中的第几个异常
+    Throws(u16),
+    /// 0x40, 局部变量, 作用域不连续时会有多段
+    LocalVariable(Vec<LocalVariableTarget>),
+    /// 0x41, try-with-resources 中的资源变量
+    ResourceVariable(Vec<LocalVariableTarget>),
+    /// 0x42, catch 的异常参数, 下标指向异常表
+    ExceptionParameter(u16),
+    /// 0x43, instanceof 表达式
+    Instanceof(ProgramCounter),
+    /// 0x44, new 表达式
+    New(ProgramCounter),
+    /// 0x45, `Foo::new` 形式的方法引用
+    ConstructorReference(ProgramCounter),
+    /// 0x46, `Foo::bar` 形式的方法引用
+    MethodReference(ProgramCounter),
+    /// 0x47, 类型转换表达式, 交叉类型中的第几个类型
+    Cast { program_counter: ProgramCounter, type_argument_index: u8 },
+    /// 0x48, 调用泛型构造器时显式给出的类型实参
+    ConstructorInvocationTypeArgument { program_counter: ProgramCounter, type_argument_index: u8 },
+    /// 0x49, 调用泛型方法时显式给出的类型实参
+    MethodInvocationTypeArgument { program_counter: ProgramCounter, type_argument_index: u8 },
+    /// 0x4A, 构造器引用中显式给出的类型实参
+    ConstructorReferenceTypeArgument { program_counter: ProgramCounter, type_argument_index: u8 },
+    /// 0x4B, 方法引用中显式给出的类型实参
+    MethodReferenceTypeArgument { program_counter: ProgramCounter, type_argument_index: u8 },
+}
+
+/// localvar_target 中的一项
+#[derive(Debug, Clone, PartialEq)]
+pub struct LocalVariableTarget {
+    /// 局部变量有值的地址范围
+    pub range: Range<ProgramCounter>,
+    pub slot: u16,
+}
+
+/// type_path 中的一步
+/// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.20.2
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypePathEntry {
+    /// 进入数组的元素类型
+    Array,
+    /// 进入嵌套类型, 例如 `Map.@Nullable Entry`
+    Nested,
+    /// 进入通配符的边界, 例如 `? extends @Nullable Number`
+    WildcardBound,
+    /// 进入参数化类型的第几个类型实参
+    TypeArgument(u8),
+}
+
+/// 运行时可见与不可见的类型注解,
+/// 分别来自 RuntimeVisibleTypeAnnotations 与 RuntimeInvisibleTypeAnnotations 属性
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TypeAnnotations {
+    pub visible: Vec<TypeAnnotation>,
+    pub invisible: Vec<TypeAnnotation>,
+}
+
+impl TypeAnnotation {
+    /// 读取 RuntimeVisibleTypeAnnotations 或 RuntimeInvisibleTypeAnnotations 属性的内容
+    pub(crate) fn read_all(bytes: &[u8], constants: &ConstantPool) -> ClassFileParserResult<Vec<TypeAnnotation>> {
+        let mut buf = Buffer::new(bytes);
+        let num_annotations = buf.read_u16()?;
+        (0..num_annotations).map(|_| Self::read(&mut buf, constants)).collect()
+    }
+
+    fn read(buf: &mut Buffer, constants: &ConstantPool) -> ClassFileParserResult<TypeAnnotation> {
+        let target = TypeAnnotationTarget::read(buf)?;
+        let path_length = buf.read_u8()?;
+        let type_path = (0..path_length)
+            .map(|_| TypePathEntry::read(buf))
+            .collect::<ClassFileParserResult<Vec<TypePathEntry>>>()?;
+        let annotation = Annotation::read(buf, constants)?;
+        Ok(TypeAnnotation {
+            target,
+            type_path,
+            annotation,
+        })
+    }
+
+    /// 注解的是否是位置上的整个类型, 而不是数组元素、类型实参等其中的一部分
+    pub fn is_on_whole_type(&self) -> bool {
+        self.type_path.is_empty()
+    }
+
+    /// 被注解的局部变量, 需要使用 `javac -g` 编译才有局部变量表
+    pub fn local_variables<'a>(&self, local_variable_table: &'a LocalVariableTable) -> Vec<&'a LocalVariable> {
+        match &self.target {
+            TypeAnnotationTarget::LocalVariable(targets) | TypeAnnotationTarget::ResourceVariable(targets) => targets
+                .iter()
+                .filter_map(|target| local_variable_table.lookup(target.slot, target.range.start))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// 被注解的 catch 子句对应的异常表项
+    pub fn exception_table_entry<'a>(&self, exception_table: &'a ExceptionTable) -> Option<&'a ExceptionTableEntry> {
+        match self.target {
+            TypeAnnotationTarget::ExceptionParameter(index) => exception_table.entries().get(index as usize),
+            _ => None,
+        }
+    }
+}
+
+impl TypeAnnotationTarget {
+    /// Supertype 的下标为 65535 时表示父类
+    pub const SUPERCLASS: u16 = u16::MAX;
+
+    fn read(buf: &mut Buffer) -> ClassFileParserResult<TypeAnnotationTarget> {
+        let target_type = buf.read_u8()?;
+        let target = match target_type {
+            0x00 => TypeAnnotationTarget::ClassTypeParameter(buf.read_u8()?),
+            0x01 => TypeAnnotationTarget::MethodTypeParameter(buf.read_u8()?),
+            0x10 => TypeAnnotationTarget::Supertype(buf.read_u16()?),
+            0x11 => TypeAnnotationTarget::ClassTypeParameterBound {
+                type_parameter_index: buf.read_u8()?,
+                bound_index: buf.read_u8()?,
+            },
+            0x12 => TypeAnnotationTarget::MethodTypeParameterBound {
+                type_parameter_index: buf.read_u8()?,
+                bound_index: buf.read_u8()?,
+            },
+            0x13 => TypeAnnotationTarget::Field,
+            0x14 => TypeAnnotationTarget::MethodReturn,
+            0x15 => TypeAnnotationTarget::MethodReceiver,
+            0x16 => TypeAnnotationTarget::FormalParameter(buf.read_u8()?),
+            0x17 => TypeAnnotationTarget::Throws(buf.read_u16()?),
+            0x40 => TypeAnnotationTarget::LocalVariable(LocalVariableTarget::read_all(buf)?),
+            0x41 => TypeAnnotationTarget::ResourceVariable(LocalVariableTarget::read_all(buf)?),
+            0x42 => TypeAnnotationTarget::ExceptionParameter(buf.read_u16()?),
+            0x43 => TypeAnnotationTarget::Instanceof(ProgramCounter(buf.read_u16()?)),
+            0x44 => TypeAnnotationTarget::New(ProgramCounter(buf.read_u16()?)),
+            0x45 => TypeAnnotationTarget::ConstructorReference(ProgramCounter(buf.read_u16()?)),
+            0x46 => TypeAnnotationTarget::MethodReference(ProgramCounter(buf.read_u16()?)),
+            0x47..=0x4b => {
+                let program_counter = ProgramCounter(buf.read_u16()?);
+                let type_argument_index = buf.read_u8()?;
+                match target_type {
+                    0x47 => TypeAnnotationTarget::Cast {
+                        program_counter,
+                        type_argument_index,
+                    },
+                    0x48 => TypeAnnotationTarget::ConstructorInvocationTypeArgument {
+                        program_counter,
+                        type_argument_index,
+                    },
+                    0x49 => TypeAnnotationTarget::MethodInvocationTypeArgument {
+                        program_counter,
+                        type_argument_index,
+                    },
+                    0x4a => TypeAnnotationTarget::ConstructorReferenceTypeArgument {
+                        program_counter,
+                        type_argument_index,
+                    },
+                    _ => TypeAnnotationTarget::MethodReferenceTypeArgument {
+                        program_counter,
+                        type_argument_index,
+                    },
+                }
+            }
+            _ => {
+                return Err(ClassFileParserError::InvalidClassData {
+                    name: format!("invalid type annotation target_type {target_type:#04x}"),
+                    is_invalidate_constant_pool_idx: false,
+                })
+            }
+        };
+        Ok(target)
+    }
+
+    /// 表达式中的类型注解对应的指令地址
+    pub fn program_counter(&self) -> Option<ProgramCounter> {
+        match self {
+            TypeAnnotationTarget::Instanceof(program_counter)
+            | TypeAnnotationTarget::New(program_counter)
+            | TypeAnnotationTarget::ConstructorReference(program_counter)
+            | TypeAnnotationTarget::MethodReference(program_counter)
+            | TypeAnnotationTarget::Cast { program_counter, .. }
+            | TypeAnnotationTarget::ConstructorInvocationTypeArgument { program_counter, .. }
+            | TypeAnnotationTarget::MethodInvocationTypeArgument { program_counter, .. }
+            | TypeAnnotationTarget::ConstructorReferenceTypeArgument { program_counter, .. }
+            | TypeAnnotationTarget::MethodReferenceTypeArgument { program_counter, .. } => Some(*program_counter),
+            _ => None,
+        }
+    }
+}
+
+impl LocalVariableTarget {
+    fn read_all(buf: &mut Buffer) -> ClassFileParserResult<Vec<LocalVariableTarget>> {
+        let table_length = buf.read_u16()?;
+        (0..table_length)
+            .map(|_| {
+                let start_pc = buf.read_u16()?;
+                let length = buf.read_u16()?;
+                let slot = buf.read_u16()?;
+                let end_pc = start_pc
+                    .checked_add(length)
+                    .ok_or_else(|| ClassFileParserError::InvalidClassData {
+                        name: format!("invalid local variable target range {start_pc} + {length}"),
+                        is_invalidate_constant_pool_idx: false,
+                    })?;
+                Ok(LocalVariableTarget {
+                    range: ProgramCounter(start_pc)..ProgramCounter(end_pc),
+                    slot,
+                })
+            })
+            .collect()
+    }
+}
+
+impl TypePathEntry {
+    fn read(buf: &mut Buffer) -> ClassFileParserResult<TypePathEntry> {
+        let type_path_kind = buf.read_u8()?;
+        let type_argument_index = buf.read_u8()?;
+        match type_path_kind {
+            0 => Ok(TypePathEntry::Array),
+            1 => Ok(TypePathEntry::Nested),
+            2 => Ok(TypePathEntry::WildcardBound),
+            3 => Ok(TypePathEntry::TypeArgument(type_argument_index)),
+            _ => Err(ClassFileParserError::InvalidClassData {
+                name: format!("invalid type_path_kind {type_path_kind}"),
+                is_invalidate_constant_pool_idx: false,
+            }),
+        }
+    }
+}
+
+impl TypeAnnotations {
+    pub fn is_empty(&self) -> bool {
+        self.visible.is_empty() && self.invisible.is_empty()
+    }
+
+    /// 先是运行时可见的注解, 然后是不可见的注解
+    pub fn iter(&self) -> impl Iterator<Item = &TypeAnnotation> {
+        self.visible.iter().chain(self.invisible.iter())
+    }
+
+    /// 指定类型的注解, 例如 `org/checkerframework/checker/nullness/qual/Nullable`
+    pub fn of_type<'a>(&'a self, type_name: &'a str) -> impl Iterator<Item = &'a TypeAnnotation> {
+        self.iter().filter(move |annotation| annotation.annotation.type_name == type_name)
+    }
+}
+
+/// 与 javap 类似, 例如 `@Nullable METHOD_RETURN, location=[TYPE_ARGUMENT(0)]`
+impl fmt::Display for TypeAnnotation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.annotation, self.target)?;
+        if !self.type_path.is_empty() {
+            write!(f, ", location=[{}]", self.type_path.iter().join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TypeAnnotationTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeAnnotationTarget::ClassTypeParameter(index) => write!(f, "CLASS_TYPE_PARAMETER, param_index={index}"),
+            TypeAnnotationTarget::MethodTypeParameter(index) => {
+                write!(f, "METHOD_TYPE_PARAMETER, param_index={index}")
+            }
+            TypeAnnotationTarget::Supertype(index) => write!(f, "CLASS_EXTENDS, type_index={index}"),
+            TypeAnnotationTarget::ClassTypeParameterBound {
+                type_parameter_index,
+                bound_index,
+            } => write!(
+                f,
+                "CLASS_TYPE_PARAMETER_BOUND, param_index={type_parameter_index}, bound_index={bound_index}"
+            ),
+            TypeAnnotationTarget::MethodTypeParameterBound {
+                type_parameter_index,
+                bound_index,
+            } => write!(
+                f,
+                "METHOD_TYPE_PARAMETER_BOUND, param_index={type_parameter_index}, bound_index={bound_index}"
+            ),
+            TypeAnnotationTarget::Field => f.write_str("FIELD"),
+            TypeAnnotationTarget::MethodReturn => f.write_str("METHOD_RETURN"),
+            TypeAnnotationTarget::MethodReceiver => f.write_str("METHOD_RECEIVER"),
+            TypeAnnotationTarget::FormalParameter(index) => write!(f, "METHOD_FORMAL_PARAMETER, param_index={index}"),
+            TypeAnnotationTarget::Throws(index) => write!(f, "THROWS, type_index={index}"),
+            TypeAnnotationTarget::LocalVariable(targets) => {
+                write!(f, "LOCAL_VARIABLE, {{{}}}", targets.iter().join("; "))
+            }
+            TypeAnnotationTarget::ResourceVariable(targets) => {
+                write!(f, "RESOURCE_VARIABLE, {{{}}}", targets.iter().join("; "))
+            }
+            TypeAnnotationTarget::ExceptionParameter(index) => write!(f, "EXCEPTION_PARAMETER, exception_index={index}"),
+            TypeAnnotationTarget::Instanceof(offset) => write!(f, "INSTANCEOF, offset={offset}"),
+            TypeAnnotationTarget::New(offset) => write!(f, "NEW, offset={offset}"),
+            TypeAnnotationTarget::ConstructorReference(offset) => write!(f, "CONSTRUCTOR_REFERENCE, offset={offset}"),
+            TypeAnnotationTarget::MethodReference(offset) => write!(f, "METHOD_REFERENCE, offset={offset}"),
+            TypeAnnotationTarget::Cast {
+                program_counter,
+                type_argument_index,
+            } => write!(f, "CAST, offset={program_counter}, type_index={type_argument_index}"),
+            TypeAnnotationTarget::ConstructorInvocationTypeArgument {
+                program_counter,
+                type_argument_index,
+            } => write!(
+                f,
+                "CONSTRUCTOR_INVOCATION_TYPE_ARGUMENT, offset={program_counter}, type_index={type_argument_index}"
+            ),
+            TypeAnnotationTarget::MethodInvocationTypeArgument {
+                program_counter,
+                type_argument_index,
+            } => write!(
+                f,
+                "METHOD_INVOCATION_TYPE_ARGUMENT, offset={program_counter}, type_index={type_argument_index}"
+            ),
+            TypeAnnotationTarget::ConstructorReferenceTypeArgument {
+                program_counter,
+                type_argument_index,
+            } => write!(
+                f,
+                "CONSTRUCTOR_REFERENCE_TYPE_ARGUMENT, offset={program_counter}, type_index={type_argument_index}"
+            ),
+            TypeAnnotationTarget::MethodReferenceTypeArgument {
+                program_counter,
+                type_argument_index,
+            } => write!(
+                f,
+                "METHOD_REFERENCE_TYPE_ARGUMENT, offset={program_counter}, type_index={type_argument_index}"
+            ),
+        }
+    }
+}
+
+impl fmt::Display for LocalVariableTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "start_pc={}, length={}, index={}",
+            self.range.start,
+            self.range.end.0 - self.range.start.0,
+            self.slot
+        )
+    }
+}
+
+impl fmt::Display for TypePathEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TypePathEntry::Array => f.write_str("ARRAY"),
+            TypePathEntry::Nested => f.write_str("INNER_TYPE"),
+            TypePathEntry::WildcardBound => f.write_str("WILDCARD"),
+            TypePathEntry::TypeArgument(index) => write!(f, "TYPE_ARGUMENT({index})"),
+        }
+    }
+}
@@ -1,8 +1,21 @@
 use std::fmt;
+use crate::annotation::annotations::Annotations;
+use crate::annotation::type_annotations::TypeAnnotations;
+use crate::class_attributes::bootstrap_methods::{BootstrapMethod, CallSite};
+use crate::class_attributes::inner_classes::{EnclosingMethod, InnerClass};
+use crate::class_attributes::module::ModuleDescriptor;
+use crate::class_attributes::record::RecordComponent;
+use crate::class_attributes::source_map::{SourceLocation, SourceMap, JAVA_STRATUM};
 use crate::constant_pool::constant_pool::ConstantPool;
+use crate::error::{ClassFileParserError, ClassFileParserResult};
 use crate::field::class_filed::ClassFileField;
+use crate::custom_attribute::CustomAttributes;
 use crate::flags::ClassAccessFlags;
 use crate::method::class_method::ClassFileMethod;
+use crate::signature::class_signature::ClassSignature;
+use crate::utils::attribute::Attribute;
+use crate::utils::instruction::Instruction;
+use crate::utils::pc::ProgramCounter;
 use crate::version::ClassFileVersion;
 
 /// Represents the content of a .class file.
@@ -18,6 +31,136 @@ pub struct ClassFile {
     pub methods: Vec<ClassFileMethod>,
     pub deprecated: bool,
     pub source_file: Option<String>,
+    /// SourceDebugExtension 属性的内容, JVM 不关心其格式, 通常是 JSR-45 SMAP
+    pub source_debug_extension: Option<String>,
+    /// 从 SourceDebugExtension 中解析出的 SMAP, 内容不以 `SMAP` 开头或者无法解析时为 None。
+    /// 写回 class 文件时只使用 source_debug_extension
+    pub source_map: Option<SourceMap>,
+    /// 泛型类的 Signature 属性, 写回 class 文件时使用原始属性
+    pub signature: Option<ClassSignature>,
+    /// BootstrapMethods 属性, 下标即 InvokeDynamic 与 Dynamic 常量中的 bootstrap_method_attr_index
+    pub bootstrap_methods: Vec<BootstrapMethod>,
+    /// 类上的注解, 写回 class 文件时使用原始属性
+    pub annotations: Annotations,
+    /// 类型形参、父类与接口上的类型注解
+    pub type_annotations: TypeAnnotations,
+    /// InnerClasses 属性, 包括当前类自己以及它用到的所有嵌套类
+    pub inner_classes: Vec<InnerClass>,
+    /// 局部类与匿名类的 EnclosingMethod 属性
+    pub enclosing_method: Option<EnclosingMethod>,
+    /// Java 11 之后嵌套类的 NestHost 属性, 即最外层的类
+    pub nest_host: Option<String>,
+    /// Java 11 之后最外层的类的 NestMembers 属性, 即它包含的所有嵌套类
+    pub nest_members: Vec<String>,
+    /// record 类的组件, 不是 record 时为 None, 没有组件的 record 为空
+    pub record_components: Option<Vec<RecordComponent>>,
+    /// sealed 类或接口允许的直接子类
+    pub permitted_subclasses: Vec<String>,
+    /// module-info.class 描述的模块, 其他 class 文件为 None
+    pub module: Option<ModuleDescriptor>,
+    /// 使用 [crate::read_buffer_with] 读取时解码的自定义属性, 写回时重新编码
+    pub custom_attributes: CustomAttributes,
+    /// 类的原始属性, 包括已经解析到上面字段中的 SourceFile 与 Deprecated
+    pub attributes: Vec<Attribute>,
+}
+
+impl ClassFile {
+    /// 根据方法名与描述符查找方法, 描述符例如 `([Ljava/lang/String;)V`
+    pub fn find_method(&self, name: &str, type_descriptor: &str) -> Option<&ClassFileMethod> {
+        self.methods
+            .iter()
+            .find(|method| method.name == name && method.type_descriptor == type_descriptor)
+    }
+
+    pub fn is_record(&self) -> bool {
+        self.record_components.is_some()
+    }
+
+    /// module-info.class 设置了 ACC_MODULE, 它没有父类、字段与方法
+    pub fn is_module(&self) -> bool {
+        self.flags.contains(ClassAccessFlags::MODULE)
+    }
+
+    /// 有 PermittedSubclasses 属性的类或接口就是 sealed 的
+    pub fn is_sealed(&self) -> bool {
+        !self.permitted_subclasses.is_empty()
+    }
+
+    /// 当前类在 InnerClasses 中对应的一项, 只有嵌套类才有
+    pub fn inner_class_info(&self) -> Option<&InnerClass> {
+        self.inner_classes.iter().find(|inner_class| inner_class.inner_class == self.name)
+    }
+
+    /// 匿名类, 例如 `new Runnable() { ... }`
+    pub fn is_anonymous(&self) -> bool {
+        self.inner_class_info().is_some_and(InnerClass::is_anonymous)
+    }
+
+    /// 直接包含当前类的类, 局部类与匿名类来自 EnclosingMethod 属性, 顶层类没有
+    pub fn outer_class(&self) -> Option<&str> {
+        self.inner_class_info()
+            .and_then(|inner_class| inner_class.outer_class.as_deref())
+            .or_else(|| self.enclosing_method.as_ref().map(|method| method.class_name.as_str()))
+    }
+
+    /// nest 的宿主, 没有 NestHost 属性时就是当前类自己
+    /// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-5.html#jvms-5.4.4
+    pub fn nest_host_name(&self) -> &str {
+        self.nest_host.as_deref().unwrap_or(&self.name)
+    }
+
+    /// 从当前 class 文件可以知道的同一个 nest 中的类, 第一个是宿主, 包括当前类自己。
+    /// 嵌套类中只记录了宿主, 其他成员需要从宿主的 NestMembers 属性中获取
+    pub fn nest_mates(&self) -> Vec<&str> {
+        let mut nest_mates = vec![self.nest_host_name()];
+        if self.nest_host.is_some() {
+            nest_mates.push(&self.name);
+        } else {
+            nest_mates.extend(self.nest_members.iter().map(String::as_str));
+        }
+        nest_mates
+    }
+
+    /// 同一个 nest 中的类可以互相访问私有成员
+    pub fn is_nest_mate_of(&self, other: &ClassFile) -> bool {
+        self.nest_host_name() == other.nest_host_name()
+    }
+
+    /// 方法中 pc 处的指令在 stratum 中对应的源码位置, stratum 为 None 时使用 SMAP 的默认 stratum。
+    /// 没有 SMAP 时只有 Java stratum, 即 LineNumberTable 中的行号与 SourceFile
+    pub fn source_location(
+        &self,
+        method: &ClassFileMethod,
+        pc: ProgramCounter,
+        stratum: Option<&str>,
+    ) -> Option<SourceLocation> {
+        let line = method.code.as_ref()?.line_number_of(pc)?;
+        match &self.source_map {
+            Some(source_map) => source_map.map_line(line, stratum),
+            None if stratum.unwrap_or(JAVA_STRATUM) == JAVA_STRATUM => Some(SourceLocation {
+                file_name: self.source_file.clone()?,
+                path: None,
+                line,
+            }),
+            None => None,
+        }
+    }
+
+    /// 解析常量池中 index 处的 InvokeDynamic 常量, 得到 bootstrap 方法、静态参数以及调用点的名称与类型
+    pub fn resolve_invoke_dynamic(&self, index: u16) -> ClassFileParserResult<CallSite> {
+        CallSite::resolve(&self.constants, &self.bootstrap_methods, index)
+    }
+
+    /// 解析 invokedynamic 指令的调用点
+    pub fn resolve_call_site(&self, instruction: &Instruction) -> ClassFileParserResult<CallSite> {
+        match instruction {
+            Instruction::Invokedynamic(index) => self.resolve_invoke_dynamic(*index),
+            _ => Err(ClassFileParserError::InvalidClassData {
+                name: format!("{instruction:?} is not an invokedynamic instruction"),
+                is_invalidate_constant_pool_idx: false,
+            }),
+        }
+    }
 }
 
 impl fmt::Display for ClassFile {
@@ -30,10 +173,49 @@ impl fmt::Display for ClassFile {
         write!(f, "{:?}", self.constants)?;
         writeln!(
             f,
-            "flags: {}, deprecated: {}",
+            "flags: {:#}, deprecated: {}",
             self.flags, self.deprecated
         )?;
         writeln!(f, "interfaces: {:?}", self.interfaces)?;
+        if let Some(signature) = &self.signature {
+            writeln!(f, "signature: {signature}")?;
+        }
+        if let Some(source_debug_extension) = &self.source_debug_extension {
+            writeln!(f, "source debug extension: {source_debug_extension:?}")?;
+        }
+        for annotation in self.annotations.iter() {
+            writeln!(f, "annotation: {annotation}")?;
+        }
+        for type_annotation in self.type_annotations.iter() {
+            writeln!(f, "type annotation: {type_annotation}")?;
+        }
+        for inner_class in self.inner_classes.iter() {
+            writeln!(f, "inner class: {inner_class}")?;
+        }
+        if let Some(enclosing_method) = &self.enclosing_method {
+            writeln!(f, "enclosing method: {enclosing_method}")?;
+        }
+        if let Some(nest_host) = &self.nest_host {
+            writeln!(f, "nest host: {nest_host}")?;
+        }
+        if !self.nest_members.is_empty() {
+            writeln!(f, "nest members: {:?}", self.nest_members)?;
+        }
+        for record_component in self.record_components.iter().flatten() {
+            writeln!(f, "record component: {record_component}")?;
+        }
+        if self.is_sealed() {
+            writeln!(f, "permitted subclasses: {:?}", self.permitted_subclasses)?;
+        }
+        if let Some(module) = &self.module {
+            writeln!(f, "{module}")?;
+        }
+        if !self.custom_attributes.is_empty() {
+            writeln!(f, "custom attributes: {}", self.custom_attributes)?;
+        }
+        for (index, bootstrap_method) in self.bootstrap_methods.iter().enumerate() {
+            writeln!(f, "bootstrap method {index}: {bootstrap_method:?}")?;
+        }
         writeln!(f, "fields:")?;
         for field in self.fields.iter() {
             writeln!(f, "  - {field}")?;
@@ -0,0 +1,262 @@
+use std::fmt;
+use std::fmt::Formatter;
+
+use itertools::Itertools;
+
+use crate::constant_pool::constant_pool::{ConstantPool, ConstantPoolEntry, ReferenceKind};
+use crate::error::{ClassFileParserError, ClassFileParserResult};
+use crate::utils::buffer::{Buffer, BufferWriter};
+
+/// BootstrapMethods 属性中的一项, 保存的都是常量池的索引
+/// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.23
+#[derive(Debug, Clone, PartialEq)]
+pub struct BootstrapMethod {
+    /// 指向一个 MethodHandle 常量
+    pub method_handle_index: u16,
+    /// 静态参数, 指向可以被 ldc 加载的常量
+    pub argument_indexes: Vec<u16>,
+}
+
+/// 解析完成的 MethodHandle 常量
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodHandleReference {
+    pub kind: ReferenceKind,
+    pub class_name: String,
+    pub name: String,
+    pub type_descriptor: String,
+}
+
+/// 解析完成的 bootstrap 方法静态参数
+#[derive(Debug, Clone, PartialEq)]
+pub enum BootstrapArgument {
+    Integer(i32),
+    Float(f32),
+    Long(i64),
+    Double(f64),
+    Class(String),
+    String(String),
+    MethodHandle(MethodHandleReference),
+    MethodType(String),
+    /// 动态常量, 为了避免循环引用, 不再继续解析它的 bootstrap 方法
+    Dynamic {
+        bootstrap_method_index: u16,
+        name: String,
+        type_descriptor: String,
+    },
+}
+
+/// 一个 invokedynamic 指令的调用点
+#[derive(Debug, Clone, PartialEq)]
+pub struct CallSite {
+    pub bootstrap_method_index: u16,
+    pub bootstrap_method: MethodHandleReference,
+    pub static_arguments: Vec<BootstrapArgument>,
+    /// 调用点的名称, 例如 lambda 实现的接口方法名 `get`
+    pub name: String,
+    /// 调用点的方法描述符, 例如 `(Ljava/lang/String;)Ljava/util/function/Supplier;`
+    pub type_descriptor: String,
+}
+
+impl BootstrapMethod {
+    /// 读取 BootstrapMethods 属性的内容
+    pub(crate) fn read_all(bytes: &[u8]) -> ClassFileParserResult<Vec<BootstrapMethod>> {
+        let mut buf = Buffer::new(bytes);
+        let num_bootstrap_methods = buf.read_u16()?;
+        let mut bootstrap_methods = Vec::with_capacity(num_bootstrap_methods as usize);
+        for _ in 0..num_bootstrap_methods {
+            let method_handle_index = buf.read_u16()?;
+            let num_arguments = buf.read_u16()?;
+            let argument_indexes = (0..num_arguments)
+                .map(|_| buf.read_u16())
+                .collect::<ClassFileParserResult<Vec<u16>>>()?;
+            bootstrap_methods.push(BootstrapMethod {
+                method_handle_index,
+                argument_indexes,
+            });
+        }
+        Ok(bootstrap_methods)
+    }
+
+    /// 写出 BootstrapMethods 属性的内容, 是 [BootstrapMethod::read_all] 的逆操作
+    pub(crate) fn write_all(bootstrap_methods: &[BootstrapMethod]) -> ClassFileParserResult<Vec<u8>> {
+        let too_many = |len: usize| ClassFileParserError::InvalidClassData {
+            name: format!("too many bootstrap methods or arguments to be written: {len}"),
+            is_invalidate_constant_pool_idx: false,
+        };
+        let mut buffer = BufferWriter::new();
+        buffer.write_u16(u16::try_from(bootstrap_methods.len()).map_err(|_| too_many(bootstrap_methods.len()))?);
+        for bootstrap_method in bootstrap_methods {
+            let num_arguments = bootstrap_method.argument_indexes.len();
+            buffer.write_u16(bootstrap_method.method_handle_index);
+            buffer.write_u16(u16::try_from(num_arguments).map_err(|_| too_many(num_arguments))?);
+            for argument_index in bootstrap_method.argument_indexes.iter() {
+                buffer.write_u16(*argument_index);
+            }
+        }
+        Ok(buffer.into_bytes())
+    }
+
+    /// 解析 bootstrap 方法的句柄以及静态参数
+    pub fn resolve(
+        &self,
+        constants: &ConstantPool,
+    ) -> ClassFileParserResult<(MethodHandleReference, Vec<BootstrapArgument>)> {
+        let method_handle = MethodHandleReference::resolve(constants, self.method_handle_index)?;
+        let arguments = self
+            .argument_indexes
+            .iter()
+            .map(|index| BootstrapArgument::resolve(constants, *index))
+            .collect::<ClassFileParserResult<Vec<BootstrapArgument>>>()?;
+        Ok((method_handle, arguments))
+    }
+}
+
+impl MethodHandleReference {
+    pub fn resolve(constants: &ConstantPool, index: u16) -> ClassFileParserResult<MethodHandleReference> {
+        match constants.get_entry(index)? {
+            ConstantPoolEntry::MethodHandle(kind, reference_index) => {
+                let (class_name, name, type_descriptor) = constants.member_reference_of(*reference_index)?;
+                Ok(MethodHandleReference {
+                    kind: *kind,
+                    class_name,
+                    name,
+                    type_descriptor,
+                })
+            }
+            entry => Err(ClassFileParserError::InvalidClassData {
+                name: format!("expected a MethodHandle constant at index {index} but found {entry:?}"),
+                is_invalidate_constant_pool_idx: true,
+            }),
+        }
+    }
+}
+
+impl BootstrapArgument {
+    pub fn resolve(constants: &ConstantPool, index: u16) -> ClassFileParserResult<BootstrapArgument> {
+        Ok(match constants.get_entry(index)? {
+            ConstantPoolEntry::Integer(value) => BootstrapArgument::Integer(*value),
+            ConstantPoolEntry::Float(value) => BootstrapArgument::Float(*value),
+            ConstantPoolEntry::Long(value) => BootstrapArgument::Long(*value),
+            ConstantPoolEntry::Double(value) => BootstrapArgument::Double(*value),
+            ConstantPoolEntry::ClassReference(name_index) => BootstrapArgument::Class(constants.text_of(*name_index)?),
+            ConstantPoolEntry::StringReference(string_index) => {
+                BootstrapArgument::String(constants.text_of(*string_index)?)
+            }
+            ConstantPoolEntry::MethodHandle(_, _) => {
+                BootstrapArgument::MethodHandle(MethodHandleReference::resolve(constants, index)?)
+            }
+            ConstantPoolEntry::MethodType(descriptor_index) => {
+                BootstrapArgument::MethodType(constants.text_of(*descriptor_index)?)
+            }
+            ConstantPoolEntry::Dynamic(bootstrap_method_index, name_and_type_index) => {
+                let (name, type_descriptor) = constants.name_and_type_of(*name_and_type_index)?;
+                BootstrapArgument::Dynamic {
+                    bootstrap_method_index: *bootstrap_method_index,
+                    name,
+                    type_descriptor,
+                }
+            }
+            entry => {
+                return Err(ClassFileParserError::InvalidClassData {
+                    name: format!("the constant {entry:?} at index {index} is not a loadable bootstrap argument"),
+                    is_invalidate_constant_pool_idx: true,
+                })
+            }
+        })
+    }
+}
+
+impl CallSite {
+    /// 解析常量池中 index 处的 InvokeDynamic 常量
+    pub fn resolve(
+        constants: &ConstantPool,
+        bootstrap_methods: &[BootstrapMethod],
+        index: u16,
+    ) -> ClassFileParserResult<CallSite> {
+        let (bootstrap_method_index, name_and_type_index) = match constants.get_entry(index)? {
+            ConstantPoolEntry::InvokeDynamic(i, j) => (*i, *j),
+            entry => {
+                return Err(ClassFileParserError::InvalidClassData {
+                    name: format!("expected an InvokeDynamic constant at index {index} but found {entry:?}"),
+                    is_invalidate_constant_pool_idx: true,
+                })
+            }
+        };
+        let bootstrap_method = bootstrap_methods
+            .get(bootstrap_method_index as usize)
+            .ok_or_else(|| ClassFileParserError::InvalidClassData {
+                name: format!("bootstrap method {bootstrap_method_index} not found"),
+                is_invalidate_constant_pool_idx: false,
+            })?;
+        let (method_handle, static_arguments) = bootstrap_method.resolve(constants)?;
+        let (name, type_descriptor) = constants.name_and_type_of(name_and_type_index)?;
+        Ok(CallSite {
+            bootstrap_method_index,
+            bootstrap_method: method_handle,
+            static_arguments,
+            name,
+            type_descriptor,
+        })
+    }
+
+    /// 由 `LambdaMetafactory` 创建的调用点, 即 lambda 表达式以及方法引用
+    pub fn is_lambda(&self) -> bool {
+        self.bootstrap_method.class_name == "java/lang/invoke/LambdaMetafactory"
+    }
+
+    /// 由 `StringConcatFactory` 创建的调用点, 即 Java 9 之后的字符串拼接
+    pub fn is_string_concat(&self) -> bool {
+        self.bootstrap_method.class_name == "java/lang/invoke/StringConcatFactory"
+    }
+
+    /// lambda 的实现方法, 也就是 `LambdaMetafactory.metafactory` 的第二个静态参数
+    pub fn lambda_implementation(&self) -> Option<&MethodHandleReference> {
+        if !self.is_lambda() {
+            return None;
+        }
+        match self.static_arguments.get(1) {
+            Some(BootstrapArgument::MethodHandle(method_handle)) => Some(method_handle),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for MethodHandleReference {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}.{}:{}", self.kind, self.class_name, self.name, self.type_descriptor)
+    }
+}
+
+impl fmt::Display for BootstrapArgument {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            BootstrapArgument::Integer(value) => write!(f, "{value}"),
+            BootstrapArgument::Float(value) => write!(f, "{value}f"),
+            BootstrapArgument::Long(value) => write!(f, "{value}l"),
+            BootstrapArgument::Double(value) => write!(f, "{value}d"),
+            BootstrapArgument::Class(name) => write!(f, "class {name}"),
+            BootstrapArgument::String(value) => write!(f, "{value:?}"),
+            BootstrapArgument::MethodHandle(method_handle) => write!(f, "{method_handle}"),
+            BootstrapArgument::MethodType(descriptor) => f.write_str(descriptor),
+            BootstrapArgument::Dynamic {
+                bootstrap_method_index,
+                name,
+                type_descriptor,
+            } => write!(f, "#{bootstrap_method_index}:{name}:{type_descriptor}"),
+        }
+    }
+}
+
+impl fmt::Display for CallSite {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "#{}:{}:{} bootstrap: {} arguments: [{}]",
+            self.bootstrap_method_index,
+            self.name,
+            self.type_descriptor,
+            self.bootstrap_method,
+            self.static_arguments.iter().join(", ")
+        )
+    }
+}
@@ -0,0 +1,95 @@
+use std::fmt;
+use std::fmt::Formatter;
+
+use crate::class_attributes::optional;
+use crate::constant_pool::constant_pool::ConstantPool;
+use crate::error::ClassFileParserResult;
+use crate::flags::InnerClassAccessFlags;
+use crate::utils::buffer::Buffer;
+
+/// InnerClasses 属性中的一项, 描述一个嵌套类与包含它的类之间的关系。
+/// 外部类与内部类都会记录这一项, 内部类中使用到的其他嵌套类也会记录
+/// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.6
+#[derive(Debug, Clone, PartialEq)]
+pub struct InnerClass {
+    /// 嵌套类的内部名称, 例如 `java/util/Map$Entry`
+    pub inner_class: String,
+    /// 声明它的外部类, 局部类与匿名类没有外部类
+    pub outer_class: Option<String>,
+    /// 源码中的简单名称, 例如 `Entry`, 匿名类没有名称
+    pub simple_name: Option<String>,
+    pub flags: InnerClassAccessFlags,
+}
+
+/// 局部类与匿名类所在的方法
+/// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.7
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnclosingMethod {
+    pub class_name: String,
+    /// 方法的 (名称, 描述符), 在字段初始化或者初始化块中声明的类没有所在的方法
+    pub method: Option<(String, String)>,
+}
+
+impl InnerClass {
+    /// 读取 InnerClasses 属性的内容
+    pub(crate) fn read_all(bytes: &[u8], constants: &ConstantPool) -> ClassFileParserResult<Vec<InnerClass>> {
+        let mut buf = Buffer::new(bytes);
+        let number_of_classes = buf.read_u16()?;
+        (0..number_of_classes)
+            .map(|_| {
+                let inner_class = constants.class_name_of(buf.read_u16()?)?;
+                let outer_class = optional(buf.read_u16()?, |index| constants.class_name_of(index))?;
+                let simple_name = optional(buf.read_u16()?, |index| constants.text_of(index))?;
+                // 未知的 flag bits 由 ClassFileReader 检查, 宽松模式下保留它们
+                let flags = InnerClassAccessFlags::from_bits_retain(buf.read_u16()?);
+                Ok(InnerClass {
+                    inner_class,
+                    outer_class,
+                    simple_name,
+                    flags,
+                })
+            })
+            .collect()
+    }
+
+    /// 匿名类在 InnerClasses 中没有简单名称
+    pub fn is_anonymous(&self) -> bool {
+        self.simple_name.is_none()
+    }
+}
+
+impl EnclosingMethod {
+    /// 读取 EnclosingMethod 属性的内容
+    pub(crate) fn read(bytes: &[u8], constants: &ConstantPool) -> ClassFileParserResult<EnclosingMethod> {
+        let mut buf = Buffer::new(bytes);
+        let class_name = constants.class_name_of(buf.read_u16()?)?;
+        let method = optional(buf.read_u16()?, |index| constants.name_and_type_of(index))?;
+        Ok(EnclosingMethod { class_name, method })
+    }
+}
+
+impl fmt::Display for InnerClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let modifiers = self.flags.modifiers();
+        if !modifiers.is_empty() {
+            write!(f, "{} ", modifiers.join(" "))?;
+        }
+        write!(f, "{}", self.inner_class)?;
+        if let Some(simple_name) = &self.simple_name {
+            write!(f, " ({simple_name})")?;
+        }
+        if let Some(outer_class) = &self.outer_class {
+            write!(f, " of {outer_class}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for EnclosingMethod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.method {
+            Some((name, type_descriptor)) => write!(f, "{}.{name}:{type_descriptor}", self.class_name),
+            None => f.write_str(&self.class_name),
+        }
+    }
+}
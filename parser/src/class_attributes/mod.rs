@@ -0,0 +1,32 @@
+pub mod bootstrap_methods;
+pub mod inner_classes;
+pub mod module;
+pub mod record;
+pub mod source_map;
+#[cfg(test)]
+mod test;
+
+use crate::constant_pool::constant_pool::ConstantPool;
+use crate::error::ClassFileParserResult;
+use crate::utils::buffer::Buffer;
+
+/// 读取由 Class 常量索引组成的列表, 例如 NestMembers 与 PermittedSubclasses 属性
+pub(crate) fn read_class_names(bytes: &[u8], constants: &ConstantPool) -> ClassFileParserResult<Vec<String>> {
+    let mut buf = Buffer::new(bytes);
+    let number_of_classes = buf.read_u16()?;
+    (0..number_of_classes)
+        .map(|_| constants.class_name_of(buf.read_u16()?))
+        .collect()
+}
+
+/// 常量池索引为 0 时表示没有值
+pub(crate) fn optional<T>(
+    index: u16,
+    resolve: impl FnOnce(u16) -> ClassFileParserResult<T>,
+) -> ClassFileParserResult<Option<T>> {
+    if index == 0 {
+        Ok(None)
+    } else {
+        resolve(index).map(Some)
+    }
+}
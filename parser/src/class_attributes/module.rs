@@ -0,0 +1,299 @@
+use std::fmt;
+use std::fmt::Formatter;
+
+use bitflags::Flags;
+use itertools::Itertools;
+
+use crate::class_attributes::optional;
+use crate::constant_pool::constant_pool::ConstantPool;
+use crate::error::ClassFileParserResult;
+use crate::flags::{ModuleExportsFlags, ModuleFlags, ModuleRequiresFlags};
+use crate::utils::buffer::Buffer;
+
+/// module-info.class 描述的模块, 来自 Module、ModulePackages 与 ModuleMainClass 属性。
+/// 模块名称使用 `.` 分隔, 例如 `java.base`, 包名与类名则是内部名称, 例如 `java/util`
+/// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.25
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ModuleDescriptor {
+    pub name: String,
+    pub flags: ModuleFlags,
+    /// 使用 `jar --module-version` 或者 `javac --module-version` 指定的版本
+    pub version: Option<String>,
+    pub requires: Vec<ModuleRequires>,
+    pub exports: Vec<ModuleExports>,
+    pub opens: Vec<ModuleOpens>,
+    /// 使用 ServiceLoader 加载的服务接口
+    pub uses: Vec<String>,
+    pub provides: Vec<ModuleProvides>,
+    /// ModulePackages 属性, 模块中所有的包, 包括没有导出的包
+    /// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.26
+    pub packages: Vec<String>,
+    /// ModuleMainClass 属性, 即 `jar --main-class` 指定的主类
+    /// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.27
+    pub main_class: Option<String>,
+}
+
+/// 依赖的模块
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleRequires {
+    pub module: String,
+    pub flags: ModuleRequiresFlags,
+    /// 编译时依赖的模块的版本
+    pub version: Option<String>,
+}
+
+/// 导出的包, `to` 不为空时只导出给这些模块
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleExports {
+    pub package: String,
+    pub flags: ModuleExportsFlags,
+    pub to: Vec<String>,
+}
+
+/// 允许在运行时被反射访问的包, `to` 不为空时只对这些模块开放
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleOpens {
+    pub package: String,
+    pub flags: ModuleExportsFlags,
+    pub to: Vec<String>,
+}
+
+/// 服务接口以及模块中的实现类
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModuleProvides {
+    pub service: String,
+    pub with: Vec<String>,
+}
+
+impl ModuleDescriptor {
+    /// 读取 Module 属性的内容, ModulePackages 与 ModuleMainClass 是单独的属性, 由调用者填充
+    pub(crate) fn read(bytes: &[u8], constants: &ConstantPool) -> ClassFileParserResult<ModuleDescriptor> {
+        let mut buf = Buffer::new(bytes);
+        let name = constants.module_name_of(buf.read_u16()?)?;
+        let flags = read_flags(&mut buf)?;
+        let version = optional(buf.read_u16()?, |index| constants.text_of(index))?;
+
+        let requires_count = buf.read_u16()?;
+        let requires = (0..requires_count)
+            .map(|_| {
+                let module = constants.module_name_of(buf.read_u16()?)?;
+                let flags = read_flags(&mut buf)?;
+                let version = optional(buf.read_u16()?, |index| constants.text_of(index))?;
+                Ok(ModuleRequires { module, flags, version })
+            })
+            .collect::<ClassFileParserResult<Vec<ModuleRequires>>>()?;
+
+        let exports_count = buf.read_u16()?;
+        let exports = (0..exports_count)
+            .map(|_| {
+                let (package, flags, to) = read_package_directive(&mut buf, constants)?;
+                Ok(ModuleExports { package, flags, to })
+            })
+            .collect::<ClassFileParserResult<Vec<ModuleExports>>>()?;
+
+        let opens_count = buf.read_u16()?;
+        let opens = (0..opens_count)
+            .map(|_| {
+                let (package, flags, to) = read_package_directive(&mut buf, constants)?;
+                Ok(ModuleOpens { package, flags, to })
+            })
+            .collect::<ClassFileParserResult<Vec<ModuleOpens>>>()?;
+
+        let uses_count = buf.read_u16()?;
+        let uses = (0..uses_count)
+            .map(|_| constants.class_name_of(buf.read_u16()?))
+            .collect::<ClassFileParserResult<Vec<String>>>()?;
+
+        let provides_count = buf.read_u16()?;
+        let provides = (0..provides_count)
+            .map(|_| {
+                let service = constants.class_name_of(buf.read_u16()?)?;
+                let with_count = buf.read_u16()?;
+                let with = (0..with_count)
+                    .map(|_| constants.class_name_of(buf.read_u16()?))
+                    .collect::<ClassFileParserResult<Vec<String>>>()?;
+                Ok(ModuleProvides { service, with })
+            })
+            .collect::<ClassFileParserResult<Vec<ModuleProvides>>>()?;
+
+        Ok(ModuleDescriptor {
+            name,
+            flags,
+            version,
+            requires,
+            exports,
+            opens,
+            uses,
+            provides,
+            packages: Vec::new(),
+            main_class: None,
+        })
+    }
+
+    /// 读取 ModulePackages 属性的内容
+    pub(crate) fn read_packages(bytes: &[u8], constants: &ConstantPool) -> ClassFileParserResult<Vec<String>> {
+        let mut buf = Buffer::new(bytes);
+        let package_count = buf.read_u16()?;
+        (0..package_count)
+            .map(|_| constants.package_name_of(buf.read_u16()?))
+            .collect()
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.flags.contains(ModuleFlags::OPEN)
+    }
+
+    /// 查找对某个模块的依赖, 例如 `java.sql`
+    pub fn find_requires(&self, module: &str) -> Option<&ModuleRequires> {
+        self.requires.iter().find(|requires| requires.module == module)
+    }
+
+    /// 包是否导出给了指定的模块, 包名是内部名称, 例如 `com/example/api`
+    pub fn is_exported_to(&self, package: &str, module: &str) -> bool {
+        self.exports
+            .iter()
+            .any(|exports| exports.package == package && exports.is_exported_to(module))
+    }
+
+    /// 包是否在运行时对指定的模块开放, `open module` 开放了所有的包
+    pub fn is_opened_to(&self, package: &str, module: &str) -> bool {
+        self.is_open()
+            || self
+                .opens
+                .iter()
+                .any(|opens| opens.package == package && opens.is_opened_to(module))
+    }
+}
+
+impl ModuleRequires {
+    /// `requires transitive`, 依赖当前模块的模块也可以读取这个模块
+    pub fn is_transitive(&self) -> bool {
+        self.flags.contains(ModuleRequiresFlags::TRANSITIVE)
+    }
+
+    /// `requires static`, 运行时是可选的
+    pub fn is_static(&self) -> bool {
+        self.flags.contains(ModuleRequiresFlags::STATIC_PHASE)
+    }
+}
+
+impl ModuleOpens {
+    pub fn is_opened_to(&self, module: &str) -> bool {
+        self.to.is_empty() || self.to.iter().any(|to| to == module)
+    }
+}
+
+impl ModuleExports {
+    /// 限定导出, 即 `exports ... to ...`
+    pub fn is_qualified(&self) -> bool {
+        !self.to.is_empty()
+    }
+
+    pub fn is_exported_to(&self, module: &str) -> bool {
+        !self.is_qualified() || self.to.iter().any(|to| to == module)
+    }
+}
+
+/// 未知的 flag bits 由 ClassFileReader 检查, 宽松模式下保留它们
+fn read_flags<F: Flags<Bits = u16>>(buf: &mut Buffer) -> ClassFileParserResult<F> {
+    Ok(F::from_bits_retain(buf.read_u16()?))
+}
+
+/// exports 与 opens 的结构相同: 包、flags 以及目标模块
+fn read_package_directive(
+    buf: &mut Buffer,
+    constants: &ConstantPool,
+) -> ClassFileParserResult<(String, ModuleExportsFlags, Vec<String>)> {
+    let package = constants.package_name_of(buf.read_u16()?)?;
+    let flags = read_flags(buf)?;
+    let to_count = buf.read_u16()?;
+    let to = (0..to_count)
+        .map(|_| constants.module_name_of(buf.read_u16()?))
+        .collect::<ClassFileParserResult<Vec<String>>>()?;
+    Ok((package, flags, to))
+}
+
+fn fmt_targets(f: &mut Formatter<'_>, keyword: &str, targets: &[String]) -> fmt::Result {
+    if !targets.is_empty() {
+        write!(f, " {keyword} {}", targets.iter().map(|target| target.replace('/', ".")).join(", "))?;
+    }
+    Ok(())
+}
+
+fn fmt_modifiers(f: &mut Formatter<'_>, modifiers: Vec<&'static str>) -> fmt::Result {
+    for modifier in modifiers {
+        write!(f, "{modifier} ")?;
+    }
+    Ok(())
+}
+
+/// 按照 module-info.java 的形式输出, 版本写在名称后面, 例如 `requires java.base@17;`
+impl fmt::Display for ModuleDescriptor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fmt_modifiers(f, self.flags.modifiers())?;
+        write!(f, "module {}", self.name)?;
+        if let Some(version) = &self.version {
+            write!(f, "@{version}")?;
+        }
+        writeln!(f, " {{")?;
+        for requires in self.requires.iter() {
+            writeln!(f, "    {requires}")?;
+        }
+        for exports in self.exports.iter() {
+            writeln!(f, "    {exports}")?;
+        }
+        for opens in self.opens.iter() {
+            writeln!(f, "    {opens}")?;
+        }
+        for service in self.uses.iter() {
+            writeln!(f, "    uses {};", service.replace('/', "."))?;
+        }
+        for provides in self.provides.iter() {
+            writeln!(f, "    {provides}")?;
+        }
+        if !self.packages.is_empty() {
+            let mut packages = self.packages.iter().map(|package| package.replace('/', "."));
+            writeln!(f, "    // packages: {}", packages.join(", "))?;
+        }
+        if let Some(main_class) = &self.main_class {
+            writeln!(f, "    // main class: {}", main_class.replace('/', "."))?;
+        }
+        write!(f, "}}")
+    }
+}
+
+impl fmt::Display for ModuleRequires {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "requires ")?;
+        fmt_modifiers(f, self.flags.modifiers())?;
+        write!(f, "{}", self.module)?;
+        if let Some(version) = &self.version {
+            write!(f, "@{version}")?;
+        }
+        write!(f, ";")
+    }
+}
+
+impl fmt::Display for ModuleExports {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "exports {}", self.package.replace('/', "."))?;
+        fmt_targets(f, "to", &self.to)?;
+        write!(f, ";")
+    }
+}
+
+impl fmt::Display for ModuleOpens {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "opens {}", self.package.replace('/', "."))?;
+        fmt_targets(f, "to", &self.to)?;
+        write!(f, ";")
+    }
+}
+
+impl fmt::Display for ModuleProvides {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "provides {}", self.service.replace('/', "."))?;
+        fmt_targets(f, "with", &self.with)?;
+        write!(f, ";")
+    }
+}
@@ -0,0 +1,40 @@
+use std::fmt;
+use std::fmt::Formatter;
+
+use crate::annotation::annotations::Annotations;
+use crate::annotation::type_annotations::TypeAnnotations;
+use crate::signature::field_signature::FieldSignature;
+use crate::utils::attribute::Attribute;
+use crate::utils::types::Type;
+
+/// Record 属性中的一个组件, 与字段类似, 有自己的签名与注解
+/// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.30
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordComponent {
+    pub name: String,
+    pub type_descriptor: Type,
+    /// 泛型组件的 Signature 属性
+    pub signature: Option<FieldSignature>,
+    /// 只有 `@Target` 包括 RECORD_COMPONENT 的注解才会出现在这里,
+    /// 其他注解会被传递到对应的字段、访问方法与构造器参数上
+    pub annotations: Annotations,
+    pub type_annotations: TypeAnnotations,
+    /// 组件的原始属性
+    pub attributes: Vec<Attribute>,
+}
+
+impl fmt::Display for RecordComponent {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.type_descriptor, self.name)?;
+        if let Some(signature) = &self.signature {
+            write!(f, " signature: {signature}")?;
+        }
+        for annotation in self.annotations.iter() {
+            write!(f, " {annotation}")?;
+        }
+        for type_annotation in self.type_annotations.iter() {
+            write!(f, " [{type_annotation}]")?;
+        }
+        Ok(())
+    }
+}
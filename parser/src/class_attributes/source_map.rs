@@ -0,0 +1,273 @@
+use std::fmt;
+use std::fmt::Formatter;
+use std::iter::Peekable;
+
+use crate::error::{ClassFileParserError, ClassFileParserResult};
+use crate::utils::line_number::LineNumber;
+
+/// 隐式存在的 Java stratum, 即 class 文件中 LineNumberTable 与 SourceFile 给出的位置
+pub const JAVA_STRATUM: &str = "Java";
+
+/// SourceDebugExtension 属性中的 JSR-45 Source Map (SMAP), Kotlin 的内联函数与 JSP 生成的类会使用它
+/// 把 LineNumberTable 中的行号映射回原始的源文件。每个 stratum 是一种源语言的视角, 例如 Kotlin 的
+/// `Kotlin` 把内联函数的代码映射到定义它的文件, `KotlinDebug` 把它映射到调用处
+/// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.11
+/// https://jcp.org/en/jsr/detail?id=45
+#[derive(Debug, Clone, PartialEq)]
+pub struct SourceMap {
+    /// 生成的源文件的名称, 与 SourceFile 属性相同
+    pub output_file_name: String,
+    /// 没有指定 stratum 时使用的 stratum
+    pub default_stratum: String,
+    pub strata: Vec<Stratum>,
+}
+
+/// SMAP 中 `*S` 开始的一个 stratum
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stratum {
+    pub name: String,
+    /// `*F` 中的源文件
+    pub files: Vec<FileInfo>,
+    /// `*L` 中的行号映射
+    pub lines: Vec<LineInfo>,
+}
+
+/// 源文件, 例如 `+ 2 Inline.kt` 后面跟着路径 `com/example/InlineKt`
+#[derive(Debug, Clone, PartialEq)]
+pub struct FileInfo {
+    pub id: u32,
+    pub name: String,
+    /// 相对于源码根目录的路径, 只有以 `+` 开头的文件才有
+    pub path: Option<String>,
+}
+
+/// `InputStartLine#LineFileID,RepeatCount:OutputStartLine,OutputLineIncrement`,
+/// 输入文件中从 input_start_line 开始的 repeat_count 行, 每一行对应输出文件中的 output_line_increment 行
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LineInfo {
+    pub input_start_line: u32,
+    pub file_id: u32,
+    pub repeat_count: u32,
+    pub output_start_line: u32,
+    pub output_line_increment: u32,
+}
+
+/// 映射之后的源码位置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    pub file_name: String,
+    pub path: Option<String>,
+    pub line: LineNumber,
+}
+
+/// (从 1 开始的行号, 去掉了行尾空白的内容)
+type Lines<'a> = Peekable<std::vec::IntoIter<(usize, &'a str)>>;
+
+impl SourceMap {
+    /// 解析已经被 resolve 过的 SMAP, 即不包含 `*O` 与 `*C` 嵌入的 SMAP。未知的 section 与 `*V` 会被忽略
+    pub fn parse(text: &str) -> ClassFileParserResult<SourceMap> {
+        let numbered: Vec<(usize, &str)> = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, line.trim_end()))
+            .collect();
+        let mut lines: Lines = numbered.into_iter().peekable();
+
+        match lines.next() {
+            Some((_, "SMAP")) => {}
+            Some((line_number, _)) => return Err(invalid(line_number, "expected the SMAP header")),
+            None => return Err(invalid(1, "empty source map")),
+        }
+        let output_file_name = header_line(&mut lines, "output file name")?;
+        let default_stratum = header_line(&mut lines, "default stratum")?;
+
+        let mut strata: Vec<Stratum> = Vec::new();
+        let mut ended = false;
+        let mut last_line_number = 3;
+        while let Some((line_number, line)) = lines.next() {
+            last_line_number = line_number;
+            let section = line
+                .strip_prefix('*')
+                .ok_or_else(|| invalid(line_number, "expected a section header"))?;
+            ended = false;
+            match section.chars().next() {
+                Some('S') => {
+                    let name = section[1..].trim();
+                    if name.is_empty() {
+                        return Err(invalid(line_number, "missing stratum name"));
+                    }
+                    strata.push(Stratum {
+                        name: name.to_string(),
+                        files: Vec::new(),
+                        lines: Vec::new(),
+                    });
+                }
+                Some('F') => {
+                    let stratum = strata
+                        .last_mut()
+                        .ok_or_else(|| invalid(line_number, "file section outside of a stratum"))?;
+                    stratum.files.extend(read_files(&mut lines)?);
+                }
+                Some('L') => {
+                    let stratum = strata
+                        .last_mut()
+                        .ok_or_else(|| invalid(line_number, "line section outside of a stratum"))?;
+                    // 省略 LineFileID 时沿用上一行的值, 每个 stratum 从 0 开始
+                    let mut file_id = 0;
+                    while let Some((line_number, line)) = lines.next_if(|(_, line)| !line.starts_with('*')) {
+                        let line_info = LineInfo::parse(line, file_id).ok_or_else(|| {
+                            invalid(line_number, &format!("invalid line info {line:?}"))
+                        })?;
+                        file_id = line_info.file_id;
+                        stratum.lines.push(line_info);
+                    }
+                }
+                // Kotlin 在每个 stratum 之后都会输出 `*E`, 所以 `*E` 之后还可以有新的 stratum
+                Some('E') => ended = true,
+                Some('O') | Some('C') => {
+                    return Err(invalid(line_number, "embedded source maps must be resolved"));
+                }
+                // `*V` 以及未来的 section
+                _ => while lines.next_if(|(_, line)| !line.starts_with('*')).is_some() {},
+            }
+        }
+        if !ended {
+            return Err(invalid(last_line_number, "missing the *E end section"));
+        }
+        Ok(SourceMap {
+            output_file_name,
+            default_stratum,
+            strata,
+        })
+    }
+
+    pub fn stratum(&self, name: &str) -> Option<&Stratum> {
+        self.strata.iter().find(|stratum| stratum.name == name)
+    }
+
+    /// 把 LineNumberTable 中的行号映射到 stratum 中的源码位置, stratum 为 None 时使用默认的 stratum。
+    /// 没有定义的 Java stratum 就是生成的源文件本身
+    pub fn map_line(&self, output_line: LineNumber, stratum: Option<&str>) -> Option<SourceLocation> {
+        let name = stratum.unwrap_or(&self.default_stratum);
+        match self.stratum(name) {
+            Some(stratum) => stratum.map_line(output_line),
+            None if name == JAVA_STRATUM => Some(SourceLocation {
+                file_name: self.output_file_name.clone(),
+                path: None,
+                line: output_line,
+            }),
+            None => None,
+        }
+    }
+}
+
+impl Stratum {
+    pub fn file(&self, id: u32) -> Option<&FileInfo> {
+        self.files.iter().find(|file| file.id == id)
+    }
+
+    /// 输出文件中的行对应的源码位置, 使用第一个包含这一行的映射
+    pub fn map_line(&self, output_line: LineNumber) -> Option<SourceLocation> {
+        self.lines.iter().find_map(|line_info| {
+            let input_line = line_info.input_line_of(u32::from(output_line.0))?;
+            let file = self.file(line_info.file_id)?;
+            Some(SourceLocation {
+                file_name: file.name.clone(),
+                path: file.path.clone(),
+                line: LineNumber(u16::try_from(input_line).ok()?),
+            })
+        })
+    }
+}
+
+impl LineInfo {
+    fn parse(text: &str, default_file_id: u32) -> Option<LineInfo> {
+        let (input, output) = text.split_once(':')?;
+        let (input, repeat_count) = match input.split_once(',') {
+            Some((input, repeat_count)) => (input, repeat_count.trim().parse().ok()?),
+            None => (input, 1),
+        };
+        let (input_start_line, file_id) = match input.split_once('#') {
+            Some((input_start_line, file_id)) => (input_start_line, file_id.trim().parse().ok()?),
+            None => (input, default_file_id),
+        };
+        let (output_start_line, output_line_increment) = match output.split_once(',') {
+            Some((output_start_line, increment)) => (output_start_line, increment.trim().parse().ok()?),
+            None => (output, 1),
+        };
+        Some(LineInfo {
+            input_start_line: input_start_line.trim().parse().ok()?,
+            file_id,
+            repeat_count,
+            output_start_line: output_start_line.trim().parse().ok()?,
+            output_line_increment,
+        })
+    }
+
+    /// 输出文件中的行对应的输入行, 不在这一项的范围内时为 None
+    pub fn input_line_of(&self, output_line: u32) -> Option<u32> {
+        let offset = output_line.checked_sub(self.output_start_line)?;
+        // increment 为 0 时所有的输入行都对应 output_start_line 这一行
+        if self.output_line_increment == 0 {
+            return (offset == 0).then_some(self.input_start_line);
+        }
+        let index = offset / self.output_line_increment;
+        if index >= self.repeat_count {
+            return None;
+        }
+        self.input_start_line.checked_add(index)
+    }
+}
+
+fn header_line(lines: &mut Lines, name: &str) -> ClassFileParserResult<String> {
+    match lines.next() {
+        Some((line_number, line)) if line.is_empty() || line.starts_with('*') => {
+            Err(invalid(line_number, &format!("missing the {name}")))
+        }
+        Some((_, line)) => Ok(line.trim().to_string()),
+        None => Err(invalid(1, &format!("missing the {name}"))),
+    }
+}
+
+/// `+ 1 Main.kt` 的下一行是文件的路径, `1 Main.kt` 没有路径
+fn read_files(lines: &mut Lines) -> ClassFileParserResult<Vec<FileInfo>> {
+    let mut files = Vec::new();
+    while let Some((line_number, line)) = lines.next_if(|(_, line)| !line.starts_with('*')) {
+        let (has_path, file) = match line.strip_prefix('+') {
+            Some(file) => (true, file.trim_start()),
+            None => (false, line),
+        };
+        let (id, name) = file
+            .split_once(' ')
+            .and_then(|(id, name)| Some((id.parse().ok()?, name.trim())))
+            .ok_or_else(|| invalid(line_number, &format!("invalid file info {line:?}")))?;
+        let path = if has_path {
+            match lines.next() {
+                Some((_, path)) if !path.starts_with('*') => Some(path.trim().to_string()),
+                _ => return Err(invalid(line_number + 1, "missing the file path")),
+            }
+        } else {
+            None
+        };
+        files.push(FileInfo {
+            id,
+            name: name.to_string(),
+            path,
+        });
+    }
+    Ok(files)
+}
+
+fn invalid(line: usize, message: &str) -> ClassFileParserError {
+    ClassFileParserError::InvalidSourceMap {
+        line,
+        message: message.to_string(),
+    }
+}
+
+/// 与 Java 的堆栈一样输出, 例如 `Inline.kt:5`
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.file_name, self.line)
+    }
+}
@@ -0,0 +1,317 @@
+use crate::class_attributes::bootstrap_methods::{BootstrapArgument, BootstrapMethod, CallSite};
+use crate::class_attributes::inner_classes::{EnclosingMethod, InnerClass};
+use crate::class_attributes::module::{ModuleDescriptor, ModuleExports, ModuleRequires};
+use crate::class_attributes::read_class_names;
+use crate::class_attributes::source_map::{LineInfo, SourceMap};
+use crate::constant_pool::constant_pool::{ConstantPool, ConstantPoolEntry, ReferenceKind};
+use crate::error::ClassFileParserError;
+use crate::flags::{InnerClassAccessFlags, ModuleExportsFlags, ModuleRequiresFlags};
+use crate::utils::line_number::LineNumber;
+
+/// 构造一个包含 invokedynamic 调用点的常量池:
+/// `#9 = InvokeDynamic #0:run:()Ljava/lang/Runnable;`
+fn invoke_dynamic_constants() -> ConstantPool {
+    let mut constants = ConstantPool::default();
+    constants.add_entry(ConstantPoolEntry::Utf8("rjvm/Factory".to_string())); // 1
+    constants.add_entry(ConstantPoolEntry::ClassReference(1)); // 2
+    constants.add_entry(ConstantPoolEntry::Utf8("bootstrap".to_string())); // 3
+    constants.add_entry(ConstantPoolEntry::Utf8("()V".to_string())); // 4
+    constants.add_entry(ConstantPoolEntry::NameAndTypeDescriptor(3, 4)); // 5
+    constants.add_entry(ConstantPoolEntry::MethodReference(2, 5)); // 6
+    constants.add_entry(ConstantPoolEntry::MethodHandle(ReferenceKind::InvokeStatic, 6)); // 7
+    constants.add_entry(ConstantPoolEntry::Long(42)); // 8, 9
+    constants.add_entry(ConstantPoolEntry::Utf8("run".to_string())); // 10
+    constants.add_entry(ConstantPoolEntry::Utf8("()Ljava/lang/Runnable;".to_string())); // 11
+    constants.add_entry(ConstantPoolEntry::NameAndTypeDescriptor(10, 11)); // 12
+    constants.add_entry(ConstantPoolEntry::InvokeDynamic(0, 12)); // 13
+    constants.add_entry(ConstantPoolEntry::MethodType(4)); // 14
+    constants
+}
+
+#[test]
+fn can_read_and_write_bootstrap_methods() {
+    let bytes = [0, 2, 0, 7, 0, 2, 0, 8, 0, 14, 0, 7, 0, 0];
+    let bootstrap_methods = BootstrapMethod::read_all(&bytes).unwrap();
+    assert_eq!(
+        vec![
+            BootstrapMethod {
+                method_handle_index: 7,
+                argument_indexes: vec![8, 14],
+            },
+            BootstrapMethod {
+                method_handle_index: 7,
+                argument_indexes: vec![],
+            },
+        ],
+        bootstrap_methods
+    );
+    assert_eq!(Ok(bytes.to_vec()), BootstrapMethod::write_all(&bootstrap_methods));
+    assert_eq!(Err(ClassFileParserError::UnexpectedEndOfData), BootstrapMethod::read_all(&bytes[..5]));
+}
+
+#[test]
+fn can_resolve_call_site() {
+    let constants = invoke_dynamic_constants();
+    let bootstrap_methods = vec![BootstrapMethod {
+        method_handle_index: 7,
+        argument_indexes: vec![8, 14],
+    }];
+    let call_site = CallSite::resolve(&constants, &bootstrap_methods, 13).unwrap();
+    assert_eq!("run", call_site.name);
+    assert_eq!("()Ljava/lang/Runnable;", call_site.type_descriptor);
+    assert_eq!(ReferenceKind::InvokeStatic, call_site.bootstrap_method.kind);
+    assert_eq!(
+        vec![BootstrapArgument::Long(42), BootstrapArgument::MethodType("()V".to_string())],
+        call_site.static_arguments
+    );
+    assert!(!call_site.is_lambda());
+    assert_eq!(
+        "#0:run:()Ljava/lang/Runnable; bootstrap: REF_invokeStatic rjvm/Factory.bootstrap:()V arguments: [42l, ()V]",
+        call_site.to_string()
+    );
+}
+
+#[test]
+fn cannot_resolve_invalid_call_site() {
+    let constants = invoke_dynamic_constants();
+    // 没有对应的 bootstrap 方法
+    assert!(CallSite::resolve(&constants, &[], 13).is_err());
+    // 不是 InvokeDynamic 常量
+    let bootstrap_methods = vec![BootstrapMethod {
+        method_handle_index: 7,
+        argument_indexes: vec![],
+    }];
+    assert!(CallSite::resolve(&constants, &bootstrap_methods, 12).is_err());
+    // bootstrap 方法不是 MethodHandle, 参数不能被 ldc 加载
+    let bootstrap_methods = vec![BootstrapMethod {
+        method_handle_index: 6,
+        argument_indexes: vec![],
+    }];
+    assert!(CallSite::resolve(&constants, &bootstrap_methods, 13).is_err());
+    let bootstrap_methods = vec![BootstrapMethod {
+        method_handle_index: 7,
+        argument_indexes: vec![5],
+    }];
+    assert!(CallSite::resolve(&constants, &bootstrap_methods, 13).is_err());
+}
+
+#[test]
+fn can_read_inner_classes() {
+    let constants = invoke_dynamic_constants();
+    // 一个名为 bootstrap 的静态嵌套类, 以及一个匿名类
+    let bytes = [0, 2, 0, 2, 0, 2, 0, 3, 0, 0x08, 0, 2, 0, 0, 0, 0, 0, 0x10];
+    let inner_classes = InnerClass::read_all(&bytes, &constants).unwrap();
+    assert_eq!(
+        vec![
+            InnerClass {
+                inner_class: "rjvm/Factory".to_string(),
+                outer_class: Some("rjvm/Factory".to_string()),
+                simple_name: Some("bootstrap".to_string()),
+                flags: InnerClassAccessFlags::STATIC,
+            },
+            InnerClass {
+                inner_class: "rjvm/Factory".to_string(),
+                outer_class: None,
+                simple_name: None,
+                flags: InnerClassAccessFlags::FINAL,
+            },
+        ],
+        inner_classes
+    );
+    assert!(!inner_classes[0].is_anonymous());
+    assert!(inner_classes[1].is_anonymous());
+    assert_eq!("static rjvm/Factory (bootstrap) of rjvm/Factory", inner_classes[0].to_string());
+
+    // 内部类必须指向 Class 常量
+    let result = InnerClass::read_all(&[0, 1, 0, 1, 0, 0, 0, 0, 0, 0], &constants);
+    assert!(matches!(
+        result,
+        Err(ClassFileParserError::InvalidClassData {
+            is_invalidate_constant_pool_idx: true,
+            ..
+        })
+    ));
+    // 未知的 flag bits 由 ClassFileReader 检查, 这里原样保留
+    let inner_classes = InnerClass::read_all(&[0, 1, 0, 2, 0, 0, 0, 0, 0x80, 0], &constants).unwrap();
+    assert_eq!(0x8000, inner_classes[0].flags.bits());
+}
+
+#[test]
+fn can_read_enclosing_method_and_nest_members() {
+    let constants = invoke_dynamic_constants();
+    assert_eq!(
+        Ok(EnclosingMethod {
+            class_name: "rjvm/Factory".to_string(),
+            method: Some(("bootstrap".to_string(), "()V".to_string())),
+        }),
+        EnclosingMethod::read(&[0, 2, 0, 5], &constants)
+    );
+    let enclosing_method = EnclosingMethod::read(&[0, 2, 0, 0], &constants).unwrap();
+    assert_eq!(None, enclosing_method.method);
+    assert!(EnclosingMethod::read(&[0, 2, 0, 3], &constants).is_err());
+
+    assert_eq!(Ok(vec!["rjvm/Factory".to_string()]), read_class_names(&[0, 1, 0, 2], &constants));
+    assert_eq!(Err(ClassFileParserError::UnexpectedEndOfData), read_class_names(&[0, 2, 0, 2], &constants));
+}
+
+/// `module app { requires transitive java.base; exports api to java.base; uses Service; provides Service with Impl; }`
+fn module_constants() -> ConstantPool {
+    let mut constants = ConstantPool::default();
+    constants.add_entry(ConstantPoolEntry::Utf8("app".to_string())); // 1
+    constants.add_entry(ConstantPoolEntry::ModuleReference(1)); // 2
+    constants.add_entry(ConstantPoolEntry::Utf8("java.base".to_string())); // 3
+    constants.add_entry(ConstantPoolEntry::ModuleReference(3)); // 4
+    constants.add_entry(ConstantPoolEntry::Utf8("com/example/api".to_string())); // 5
+    constants.add_entry(ConstantPoolEntry::PackageReference(5)); // 6
+    constants.add_entry(ConstantPoolEntry::Utf8("com/example/api/Service".to_string())); // 7
+    constants.add_entry(ConstantPoolEntry::ClassReference(7)); // 8
+    constants.add_entry(ConstantPoolEntry::Utf8("com/example/Impl".to_string())); // 9
+    constants.add_entry(ConstantPoolEntry::ClassReference(9)); // 10
+    constants.add_entry(ConstantPoolEntry::Utf8("17".to_string())); // 11
+    constants
+}
+
+#[test]
+fn can_read_module() {
+    let constants = module_constants();
+    let bytes = [
+        0, 2, 0, 0, 0, 0, // module app
+        0, 1, 0, 4, 0, 0x20, 0, 11, // requires transitive java.base@17
+        0, 1, 0, 6, 0, 0, 0, 1, 0, 4, // exports com.example.api to java.base
+        0, 0, // opens
+        0, 1, 0, 8, // uses
+        0, 1, 0, 8, 0, 1, 0, 10, // provides
+    ];
+    let module = ModuleDescriptor::read(&bytes, &constants).unwrap();
+    assert_eq!("app", module.name);
+    assert!(!module.is_open());
+    assert_eq!(None, module.version);
+    assert_eq!(
+        vec![ModuleRequires {
+            module: "java.base".to_string(),
+            flags: ModuleRequiresFlags::TRANSITIVE,
+            version: Some("17".to_string()),
+        }],
+        module.requires
+    );
+    assert_eq!(
+        vec![ModuleExports {
+            package: "com/example/api".to_string(),
+            flags: ModuleExportsFlags::empty(),
+            to: vec!["java.base".to_string()],
+        }],
+        module.exports
+    );
+    assert!(module.is_exported_to("com/example/api", "java.base"));
+    assert!(!module.is_exported_to("com/example/api", "app"));
+    assert!(!module.is_opened_to("com/example/api", "java.base"));
+    assert_eq!(vec!["com/example/api/Service".to_string()], module.uses);
+    assert_eq!(vec!["com/example/Impl".to_string()], module.provides[0].with);
+    assert_eq!(
+        "module app {\n    requires transitive java.base@17;\n    exports com.example.api to java.base;\n    \
+         uses com.example.api.Service;\n    provides com.example.api.Service with com.example.Impl;\n}",
+        module.to_string()
+    );
+
+    assert_eq!(Ok(vec!["com/example/api".to_string()]), ModuleDescriptor::read_packages(&[0, 1, 0, 6], &constants));
+    // 模块名称必须指向 Module 常量, 包名必须指向 Package 常量
+    assert!(ModuleDescriptor::read(&[0, 1, 0, 0, 0, 0], &constants).is_err());
+    assert!(ModuleDescriptor::read_packages(&[0, 1, 0, 5], &constants).is_err());
+    // 0x0001 不是合法的 requires flag
+    assert!(ModuleDescriptor::read(&[0, 2, 0, 0, 0, 0, 0, 1, 0, 4, 0, 0x01, 0, 0], &constants).is_err());
+    assert_eq!(Err(ClassFileParserError::UnexpectedEndOfData), ModuleDescriptor::read(&bytes[..20], &constants));
+}
+
+/// Kotlin 编译器为调用了内联函数的类生成的 SMAP
+const KOTLIN_SOURCE_MAP: &str = "SMAP
+Main.kt
+Kotlin
+*S Kotlin
+*F
++ 1 Main.kt
+com/example/MainKt
++ 2 Collections.kt
+kotlin/collections/CollectionsKt___CollectionsKt
+*L
+1#1,20:1
+1849#2,2:21
+*E
+*S KotlinDebug
+*F
++ 1 Main.kt
+com/example/MainKt
+*L
+7#1:21,2
+*E
+";
+
+#[test]
+fn can_read_source_map() {
+    let source_map = SourceMap::parse(KOTLIN_SOURCE_MAP).unwrap();
+    assert_eq!("Main.kt", source_map.output_file_name);
+    assert_eq!("Kotlin", source_map.default_stratum);
+    assert_eq!(2, source_map.strata.len());
+
+    let kotlin = source_map.stratum("Kotlin").unwrap();
+    assert_eq!(2, kotlin.files.len());
+    assert_eq!(Some("kotlin/collections/CollectionsKt___CollectionsKt"), kotlin.file(2).unwrap().path.as_deref());
+    assert_eq!(
+        LineInfo {
+            input_start_line: 1849,
+            file_id: 2,
+            repeat_count: 2,
+            output_start_line: 21,
+            output_line_increment: 1,
+        },
+        kotlin.lines[1]
+    );
+
+    // 内联函数的代码在默认的 Kotlin stratum 中映射到定义它的文件, 在 KotlinDebug 中映射到调用处
+    let location = source_map.map_line(LineNumber(22), None).unwrap();
+    assert_eq!("Collections.kt:1850", location.to_string());
+    let location = source_map.map_line(LineNumber(22), Some("KotlinDebug")).unwrap();
+    assert_eq!("Main.kt:7", location.to_string());
+    assert_eq!(Some(LineNumber(5)), source_map.map_line(LineNumber(5), None).map(|location| location.line));
+    assert_eq!(None, source_map.map_line(LineNumber(5), Some("KotlinDebug")));
+    assert_eq!(None, source_map.map_line(LineNumber(23), None));
+    // 没有定义的 Java stratum 就是生成的文件本身
+    assert_eq!("Main.kt:22", source_map.map_line(LineNumber(22), Some("Java")).unwrap().to_string());
+    assert_eq!(None, source_map.map_line(LineNumber(22), Some("JSP")));
+}
+
+#[test]
+fn can_read_line_info_defaults() {
+    // 省略 LineFileID 时沿用上一行的值, RepeatCount 与 OutputLineIncrement 默认为 1, 未知的 section 被忽略
+    let text = "SMAP\nindex_jsp.java\nJSP\n*S JSP\n*F\n0 index.jsp\n1 header.jsp\n*L\n\
+                1#1,3:10,2\n8:20\n5#0:30,0\n*V\nvendor data\n*E\n";
+    let source_map = SourceMap::parse(text).unwrap();
+    let jsp = &source_map.strata[0];
+    assert_eq!(None, jsp.file(0).unwrap().path);
+    assert_eq!(1, jsp.lines[1].file_id);
+    assert_eq!(1, jsp.lines[1].repeat_count);
+    assert_eq!(1, jsp.lines[1].output_line_increment);
+    // 每个输入行对应两个输出行
+    assert_eq!(Some(2), jsp.lines[0].input_line_of(13));
+    assert_eq!(Some(3), jsp.lines[0].input_line_of(15));
+    assert_eq!(None, jsp.lines[0].input_line_of(16));
+    assert_eq!("header.jsp:8", jsp.map_line(LineNumber(20)).unwrap().to_string());
+    assert_eq!("index.jsp:5", jsp.map_line(LineNumber(30)).unwrap().to_string());
+    assert_eq!(None, jsp.map_line(LineNumber(31)));
+}
+
+#[test]
+fn cannot_read_invalid_source_map() {
+    let error_line = |text: &str| match SourceMap::parse(text) {
+        Err(ClassFileParserError::InvalidSourceMap { line, .. }) => line,
+        result => panic!("expected an invalid source map but got {result:?}"),
+    };
+    assert_eq!(1, error_line("SMAP2\nMain.kt\nKotlin\n*E"));
+    assert_eq!(3, error_line("SMAP\nMain.kt\n*S Kotlin\n*E"));
+    assert_eq!(4, error_line("SMAP\nMain.kt\nKotlin\n*F\n1 Main.kt\n*E"));
+    assert_eq!(6, error_line("SMAP\nMain.kt\nKotlin\n*S Kotlin\n*L\n1#x:1\n*E"));
+    // 路径应该在下一行
+    assert_eq!(7, error_line("SMAP\nMain.kt\nKotlin\n*S Kotlin\n*F\n+ 1 Main.kt\n*E"));
+    assert_eq!(5, error_line("SMAP\nMain.kt\nKotlin\n*S Kotlin\n*O JSP\n*C JSP\n*E"));
+    // 缺少结尾的 *E
+    assert_eq!(5, error_line("SMAP\nMain.kt\nKotlin\n*S Kotlin\n*L\n1:1"));
+}
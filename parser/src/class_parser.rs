@@ -1,16 +1,36 @@
 use bitflags::Flags;
 use log::warn;
 use result::OptionResultExt;
+use crate::annotation::annotations::{Annotation, Annotations, ElementValue};
+use crate::annotation::type_annotations::{TypeAnnotation, TypeAnnotations};
 use crate::class::ClassFile;
-use crate::constant_pool::constant_pool::{ConstantPool, ConstantPoolEntry};
+use crate::class_attributes::bootstrap_methods::BootstrapMethod;
+use crate::class_attributes::inner_classes::{EnclosingMethod, InnerClass};
+use crate::class_attributes::module::ModuleDescriptor;
+use crate::class_attributes::record::RecordComponent;
+use crate::class_attributes::source_map::SourceMap;
+use crate::class_attributes::read_class_names;
+use crate::constant_pool::constant_pool::{ConstantPool, ConstantPoolEntry, ReferenceKind};
+use crate::custom_attribute::{AttributeRegistry, CustomAttributes};
+use crate::error::diagnostic::{Diagnostic, Recovery, Severity};
+use crate::error::location::PathSegment;
 use crate::error::{ClassFileParserError, ClassFileParserResult};
 use crate::field::class_filed::{ClassFileField, FieldConstantValue};
-use crate::flags::{ClassAccessFlags, FieldFlags, MethodFlags};
+use crate::flags::{
+    ClassAccessFlags, FieldFlags, InnerClassAccessFlags, MethodFlags, MethodParameterFlags, ModuleExportsFlags,
+    ModuleFlags, ModuleRequiresFlags,
+};
 use crate::method::class_method::{ClassFileMethod, ClassFileMethodCode};
 use crate::method::descriptor::MethodDescriptor;
 use crate::method::exception_table::{ExceptionTable, ExceptionTableEntry};
 use crate::method::line_number_table::{LineNumberTable, LineNumberTableEntry};
-use crate::utils::attribute::Attribute;
+use crate::method::local_variable_table::{LocalVariable, LocalVariableTable};
+use crate::method::method_parameters::MethodParameter;
+use crate::method::stack_map_table::StackMapTable;
+use crate::signature::class_signature::ClassSignature;
+use crate::signature::field_signature::FieldSignature;
+use crate::signature::method_signature::MethodSignature;
+use crate::utils::attribute::{locate, skip_attributes, Attribute, LocatedAttributes};
 use crate::utils::base_type_convert::ToUsizeSafe;
 use crate::utils::buffer::Buffer;
 use crate::utils::line_number::LineNumber;
@@ -18,47 +38,186 @@ use crate::utils::pc::ProgramCounter;
 use crate::utils::types::Type;
 use crate::version::ClassFileVersion;
 
-/// A reader of a byte array representing a class. Supports only a subset of Java 7 class format,
-/// in particular it does not support generics.
+/// A reader of a byte array representing a class. Supports only a subset of the class format,
+/// generics are read from the Signature attribute.
 pub struct ClassFileReader<'a> {
     buffer: Buffer<'a>,
     /// The class being read, created empty and updated in place
     class_file: ClassFile,
+    /// 自定义属性的解码器, 没有时不解码自定义属性
+    registry: Option<&'a AttributeRegistry>,
+    /// 严格模式下遇到错误立即返回, 宽松模式下记录诊断信息并继续读取
+    recovery: Recovery,
+}
+
+/// 宽松模式读取的结果, 见 [crate::read_buffer_lenient]
+pub struct ParseReport {
+    /// 尽可能读取到的内容, 无法读取的部分保持默认值, 被跳过的字段与方法不在其中
+    pub class_file: ClassFile,
+    /// 按照发现的顺序排列
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ParseReport {
+    /// 是否丢失了数据, 只有 warning 时 class_file 包含了输入中的所有结构
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+
+    pub fn errors(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics.iter().filter(|diagnostic| diagnostic.severity == Severity::Error)
+    }
+
+    pub fn warnings(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.diagnostics.iter().filter(|diagnostic| diagnostic.severity == Severity::Warning)
+    }
 }
 
 /// Reference: https://docs.oracle.com/javase/specs/jvms/se7/html/jvms-4.html
 impl<'a> ClassFileReader<'a> {
-    pub(crate) fn new(data: &[u8]) -> ClassFileReader {
+    pub(crate) fn new(data: &[u8]) -> ClassFileReader<'_> {
+        ClassFileReader {
+            buffer: Buffer::new(data),
+            class_file: Default::default(),
+            registry: None,
+            recovery: Recovery::strict(),
+        }
+    }
+
+    pub(crate) fn lenient(data: &'a [u8], registry: Option<&'a AttributeRegistry>) -> ClassFileReader<'a> {
+        ClassFileReader {
+            buffer: Buffer::new(data),
+            class_file: Default::default(),
+            registry,
+            recovery: Recovery::lenient(),
+        }
+    }
+
+    pub(crate) fn with_registry(data: &'a [u8], registry: &'a AttributeRegistry) -> ClassFileReader<'a> {
         ClassFileReader {
             buffer: Buffer::new(data),
             class_file: Default::default(),
+            registry: Some(registry),
+            recovery: Recovery::strict(),
         }
     }
 
     pub(crate) fn read(mut self) -> ClassFileParserResult<ClassFile> {
-        self.check_magic_number()?;
-        self.read_version()?;
+        self.read_class()?;
+        Ok(self.class_file)
+    }
+
+    /// 宽松模式下读取, 无法继续读取的错误也作为诊断信息返回
+    pub(crate) fn read_lenient(mut self) -> ParseReport {
+        if let Err(err) = self.read_class() {
+            // 宽松模式下 report 不会返回错误
+            let _ = self.recovery.report(Severity::Error, err, self.buffer.last_read_offset());
+        }
+        ParseReport {
+            class_file: self.class_file,
+            diagnostics: self.recovery.into_diagnostics(),
+        }
+    }
+
+    fn read_class(&mut self) -> ClassFileParserResult<()> {
+        self.located(PathSegment::Field("magic"), Self::check_magic_number)?;
+        self.located(PathSegment::Field("version"), Self::read_version)?;
         self.read_constants()?;
-        self.read_access_flags()?;
-        self.class_file.name = self.read_class_reference()?;
-        self.class_file.superclass = self.read_class_reference_optional()?;
+        self.located(PathSegment::Field("access_flags"), Self::read_access_flags)?;
+        let name = self.located(PathSegment::Field("this_class"), Self::read_class_reference);
+        self.class_file.name = self.recover(name)?.unwrap_or_default();
+        let superclass = self.located(PathSegment::Field("super_class"), Self::read_class_reference_optional);
+        self.class_file.superclass = self.recover(superclass)?.flatten();
         self.read_interfaces()?;
         self.read_fields()?;
         self.read_methods()?;
-        self.read_class_attributes()?;
+        self.read_class_attributes()
+    }
 
-        Ok(self.class_file)
+    /// 使用 read 读取 segment 对应的结构, 出错时记录最近一次读取的位置, 也就是出错的值的位置
+    fn located<T>(
+        &mut self,
+        segment: PathSegment,
+        read: impl FnOnce(&mut Self) -> ClassFileParserResult<T>,
+    ) -> ClassFileParserResult<T> {
+        self.recovery.enter(segment.clone());
+        let result = read(self);
+        self.recovery.leave();
+        result.map_err(|err| err.locate(self.buffer.last_read_offset(), segment))
+    }
+
+    /// 宽松模式下跳过已经完整读取但是无法解析的值, 例如指向错误常量的索引。
+    /// 数据提前结束时之后的结构都无法读取, 总是返回错误
+    fn recover<T>(&self, result: ClassFileParserResult<T>) -> ClassFileParserResult<Option<T>> {
+        match result {
+            Err(err) if err.is_truncated() => Err(err),
+            result => self.recovery.recover(Severity::Error, result, self.buffer.last_read_offset()),
+        }
+    }
+
+    /// 在属性 name 的上下文中解码, 错误不会被恢复
+    fn decode_attribute<T>(
+        &self,
+        raw_attributes: &LocatedAttributes,
+        name: &str,
+        decode: impl FnOnce(&[u8], usize) -> ClassFileParserResult<T>,
+    ) -> ClassFileParserResult<Option<T>> {
+        self.recovery.enter(PathSegment::Attribute(name.to_string()));
+        let result = raw_attributes.decode_with_offset(name, decode);
+        self.recovery.leave();
+        result
+    }
+
+    /// 解码属性 name, 宽松模式下跳过无法解码的属性, 原始属性仍然保留在 attributes 中
+    fn decode<T>(
+        &self,
+        raw_attributes: &LocatedAttributes,
+        name: &str,
+        decode: impl FnOnce(&[u8]) -> ClassFileParserResult<T>,
+    ) -> ClassFileParserResult<Option<T>> {
+        self.decode_with_offset(raw_attributes, name, |bytes, _| decode(bytes))
+    }
+
+    fn decode_with_offset<T>(
+        &self,
+        raw_attributes: &LocatedAttributes,
+        name: &str,
+        decode: impl FnOnce(&[u8], usize) -> ClassFileParserResult<T>,
+    ) -> ClassFileParserResult<Option<T>> {
+        let result = self.decode_attribute(raw_attributes, name, decode);
+        let decoded = self.recovery.recover(Severity::Warning, result, self.buffer.last_read_offset())?;
+        Ok(decoded.flatten())
+    }
+
+    /// 宽松模式下保留未知的 flag bits
+    fn check_flags<F: Flags<Bits = u16>>(&self, bits: u16, kind: &str) -> ClassFileParserResult<F> {
+        if let Some(flags) = F::from_bits(bits) {
+            return Ok(flags);
+        }
+        let err = ClassFileParserError::InvalidClassData {
+            name: format!("invalid {kind} flags: {bits:#0x}"),
+            is_invalidate_constant_pool_idx: false,
+        };
+        self.recovery.report(Severity::Warning, err, self.buffer.last_read_offset())?;
+        Ok(F::from_bits_retain(bits))
+    }
+
+    /// 表前面的 u16 长度, 例如 `fields_count`
+    fn read_count(&mut self, name: &'static str) -> ClassFileParserResult<u16> {
+        self.located(PathSegment::Field(name), |reader| reader.buffer.read_u16())
     }
 
     fn check_magic_number(&mut self) -> ClassFileParserResult<()> {
         match self.buffer.read_u32() {
             Ok(0xCAFEBABE) => Ok(()),
-            Ok(_) => Err(
-                ClassFileParserError::InvalidClassData {
+            Ok(_) => {
+                let err = ClassFileParserError::InvalidClassData {
                     name: "invalid magic number".to_owned(),
                     is_invalidate_constant_pool_idx: false,
-                }),
-            Err(err) => Err(err.into()),
+                };
+                self.recovery.report(Severity::Error, err, self.buffer.last_read_offset())
+            }
+            Err(err) => Err(err),
         }
     }
 
@@ -66,43 +225,26 @@ impl<'a> ClassFileReader<'a> {
         let minor_version = self.buffer.read_u16()?;
         let major_version = self.buffer.read_u16()?;
 
-        self.class_file.version = ClassFileVersion::new(major_version, minor_version)?;
+        self.class_file.version = match ClassFileVersion::new(major_version, minor_version) {
+            Ok(version) => version,
+            Err(err) => {
+                self.recovery.report(Severity::Warning, err, self.buffer.last_read_offset())?;
+                ClassFileVersion::new_unchecked(major_version, minor_version)
+            }
+        };
         Ok(())
     }
 
     fn read_constants(&mut self) -> ClassFileParserResult<()> {
-        let constants_count = self.buffer.read_u16()? - 1;
+        let constants_count = self.read_count("constant_pool_count")? - 1;
         let mut i = 0;
         while i < constants_count {
-            let tag = self.buffer.read_u8()?;
-            let constant = match tag {
-                1 => self.read_utf8_constant()?,
-                3 => self.read_int_constant()?,
-                4 => self.read_float_constant()?,
-                5 => {
-                    i += 1; // long constants takes up two slots in the pool
-                    self.read_long_constant()?
-                }
-                6 => {
-                    i += 1; // double constants takes up two slots in the pool
-                    self.read_double_constant()?
-                }
-                7 => self.read_class_reference_constant()?,
-                8 => self.read_string_reference_constant()?,
-                9 => self.read_field_reference_constant()?,
-                10 => self.read_method_reference_constant()?,
-                11 => self.read_interface_method_reference_constant()?,
-                12 => self.read_name_and_type_constant()?,
-                // For newer versions of java, there are more constant types
-                _ => {
-                    warn!("invalid entry in constant pool at index {} tag {}", i, tag);
-                    return Err(
-                        ClassFileParserError::InvalidClassData {
-                            name: format!("Unknown constant type: 0x{tag:X}"),
-                            is_invalidate_constant_pool_idx: false,
-                        })
-                }
-            };
+            let segment = PathSegment::Item("constant_pool", usize::from(i) + 1);
+            let constant = self.located(segment, |reader| reader.read_constant(i))?;
+            // long and double constants take up two slots in the pool
+            if matches!(constant, ConstantPoolEntry::Long(_) | ConstantPoolEntry::Double(_)) {
+                i += 1;
+            }
             self.class_file.constants.add_entry(constant);
 
             i += 1;
@@ -111,40 +253,74 @@ impl<'a> ClassFileReader<'a> {
         Ok(())
     }
 
+    /// 读取 data 中 offset 处的单个常量, index 是从 1 开始的常量池索引, 用于按需解码常量
+    pub(crate) fn read_constant_at(data: &[u8], offset: usize, index: u16) -> ClassFileParserResult<ConstantPoolEntry> {
+        let mut reader = ClassFileReader::new(&[]);
+        reader.buffer = Buffer::with_offset(data.get(offset..).unwrap_or_default(), offset);
+        reader.read_constant(index - 1)
+    }
+
+    fn read_constant(&mut self, i: u16) -> ClassFileParserResult<ConstantPoolEntry> {
+        let tag = self.buffer.read_u8()?;
+        let constant = match tag {
+            1 => self.read_utf8_constant()?,
+            3 => self.read_int_constant()?,
+            4 => self.read_float_constant()?,
+            5 => self.read_long_constant()?,
+            6 => self.read_double_constant()?,
+            7 => self.read_class_reference_constant()?,
+            8 => self.read_string_reference_constant()?,
+            9 => self.read_field_reference_constant()?,
+            10 => self.read_method_reference_constant()?,
+            11 => self.read_interface_method_reference_constant()?,
+            12 => self.read_name_and_type_constant()?,
+            15 => self.read_method_handle_constant()?,
+            16 => self.read_method_type_constant()?,
+            17 => self.read_dynamic_constant()?,
+            18 => self.read_invoke_dynamic_constant()?,
+            19 => self.read_module_constant()?,
+            20 => self.read_package_constant()?,
+            _ => {
+                warn!("invalid entry in constant pool at index {} tag {}", i, tag);
+                return Err(
+                    ClassFileParserError::InvalidClassData {
+                        name: format!("Unknown constant type: 0x{tag:X}"),
+                        is_invalidate_constant_pool_idx: false,
+                    })
+            }
+        };
+        Ok(constant)
+    }
+
     fn read_utf8_constant(&mut self) -> ClassFileParserResult<ConstantPoolEntry> {
         let len = self.buffer.read_u16()?;
         self.buffer
             .read_utf8(len as usize)
             .map(ConstantPoolEntry::Utf8)
-            .map_err(|err| err.into())
     }
 
     fn read_int_constant(&mut self) -> ClassFileParserResult<ConstantPoolEntry> {
         self.buffer
             .read_i32()
             .map(ConstantPoolEntry::Integer)
-            .map_err(|err| err.into())
     }
 
     fn read_float_constant(&mut self) -> ClassFileParserResult<ConstantPoolEntry> {
         self.buffer
             .read_f32()
             .map(ConstantPoolEntry::Float)
-            .map_err(|err| err.into())
     }
 
     fn read_long_constant(&mut self) -> ClassFileParserResult<ConstantPoolEntry> {
         self.buffer
             .read_i64()
             .map(ConstantPoolEntry::Long)
-            .map_err(|err| err.into())
     }
 
     fn read_double_constant(&mut self) -> ClassFileParserResult<ConstantPoolEntry> {
         self.buffer
             .read_f64()
             .map(ConstantPoolEntry::Double)
-            .map_err(|err| err.into())
     }
 
     fn read_class_reference_constant(&mut self) -> ClassFileParserResult<ConstantPoolEntry> {
@@ -193,19 +369,49 @@ impl<'a> ClassFileReader<'a> {
         ))
     }
 
+    fn read_method_handle_constant(&mut self) -> ClassFileParserResult<ConstantPoolEntry> {
+        let reference_kind = ReferenceKind::from_u8(self.buffer.read_u8()?)?;
+        let reference_index = self.buffer.read_u16()?;
+        Ok(ConstantPoolEntry::MethodHandle(reference_kind, reference_index))
+    }
+
+    fn read_method_type_constant(&mut self) -> ClassFileParserResult<ConstantPoolEntry> {
+        let descriptor_index = self.buffer.read_u16()?;
+        Ok(ConstantPoolEntry::MethodType(descriptor_index))
+    }
+
+    fn read_dynamic_constant(&mut self) -> ClassFileParserResult<ConstantPoolEntry> {
+        let bootstrap_method_attr_index = self.buffer.read_u16()?;
+        let name_and_type = self.buffer.read_u16()?;
+        Ok(ConstantPoolEntry::Dynamic(
+            bootstrap_method_attr_index,
+            name_and_type,
+        ))
+    }
+
+    fn read_invoke_dynamic_constant(&mut self) -> ClassFileParserResult<ConstantPoolEntry> {
+        let bootstrap_method_attr_index = self.buffer.read_u16()?;
+        let name_and_type = self.buffer.read_u16()?;
+        Ok(ConstantPoolEntry::InvokeDynamic(
+            bootstrap_method_attr_index,
+            name_and_type,
+        ))
+    }
+
+    fn read_module_constant(&mut self) -> ClassFileParserResult<ConstantPoolEntry> {
+        let name_index = self.buffer.read_u16()?;
+        Ok(ConstantPoolEntry::ModuleReference(name_index))
+    }
+
+    fn read_package_constant(&mut self) -> ClassFileParserResult<ConstantPoolEntry> {
+        let name_index = self.buffer.read_u16()?;
+        Ok(ConstantPoolEntry::PackageReference(name_index))
+    }
+
     fn read_access_flags(&mut self) -> ClassFileParserResult<()> {
         let num = self.buffer.read_u16()?;
-        match ClassAccessFlags::from_bits(num) {
-            Some(flags) => {
-                self.class_file.flags = flags;
-                Ok(())
-            }
-            None => Err(
-                ClassFileParserError::InvalidClassData {
-                    name: format!("invalid class flags: {num}"),
-                    is_invalidate_constant_pool_idx: false,
-                }),
-        }
+        self.class_file.flags = self.check_flags::<ClassAccessFlags>(num, "class")?;
+        Ok(())
     }
 
     fn read_class_reference(&mut self) -> ClassFileParserResult<String> {
@@ -227,27 +433,33 @@ impl<'a> ClassFileReader<'a> {
     }
 
     fn read_string_reference_from(constants_pool: &ConstantPool, index: u16) -> ClassFileParserResult<String> {
-        constants_pool.text_of(index).map_err(|err| err.into())
+        constants_pool.text_of(index)
     }
 
     fn read_interfaces(&mut self) -> ClassFileParserResult<()> {
-        let interfaces_count = self.buffer.read_u16()?;
-        self.class_file.interfaces = (0..interfaces_count)
-            .map(|_| self.read_class_reference())
-            .collect::<ClassFileParserResult<Vec<String>>>()?;
+        let interfaces_count = self.read_count("interfaces_count")?;
+        for index in 0..interfaces_count {
+            let segment = PathSegment::Item("interfaces", usize::from(index));
+            let interface = self.located(segment, Self::read_class_reference);
+            if let Some(interface) = self.recover(interface)? {
+                self.class_file.interfaces.push(interface);
+            }
+        }
         Ok(())
     }
 
     fn read_fields(&mut self) -> ClassFileParserResult<()> {
-        let fields_count = self.buffer.read_u16()?;
-        self.class_file.fields = (0..fields_count)
-            .map(|_| self.read_field())
-            .collect::<ClassFileParserResult<Vec<ClassFileField>>>()?;
+        let fields_count = self.read_count("fields_count")?;
+        for index in 0..fields_count {
+            if let Some(field) = self.read_member(PathSegment::Item("fields", usize::from(index)), Self::read_field)? {
+                self.class_file.fields.push(field);
+            }
+        }
         Ok(())
     }
 
     fn read_field(&mut self) -> ClassFileParserResult<ClassFileField> {
-        let flags = self.read_field_flags()?;
+        let flags = self.located(PathSegment::Field("access_flags"), Self::read_field_flags)?;
         let name_constant_index = self.buffer.read_u16()?;
         let name = self.read_string_reference(name_constant_index)?;
         let type_constant_index = self.buffer.read_u16()?;
@@ -257,6 +469,10 @@ impl<'a> ClassFileReader<'a> {
         let raw_attributes = self.read_raw_attributes()?;
         let constant_value = self.extract_constant_value(&raw_attributes)?;
         let deprecated = self.search_deprecated_attribute(&raw_attributes);
+        let signature = self.search_signature_attribute(&raw_attributes, FieldSignature::parse)?;
+        let annotations = self.extract_annotations(&raw_attributes)?;
+        let type_annotations = self.extract_type_annotations(&raw_attributes)?;
+        let custom_attributes = self.extract_custom_attributes(&raw_attributes)?;
 
         Ok(ClassFileField {
             flags,
@@ -264,43 +480,35 @@ impl<'a> ClassFileReader<'a> {
             type_descriptor,
             constant_value,
             deprecated,
+            signature,
+            annotations,
+            type_annotations,
+            custom_attributes,
+            attributes: raw_attributes.into_attributes(),
         })
     }
 
     fn read_field_flags(&mut self) -> ClassFileParserResult<FieldFlags> {
         let field_flags_bits = self.buffer.read_u16()?;
-        match FieldFlags::from_bits(field_flags_bits) {
-            Some(flags) => Ok(flags),
-            None => Err(
-                ClassFileParserError::InvalidClassData {
-                    name:format!(
-                        "invalid field flags: {field_flags_bits:#0x}"
-                    ),
-                    is_invalidate_constant_pool_idx: false
-                })
-        }
+        self.check_flags(field_flags_bits, "field")
     }
 
     fn extract_constant_value(
         &self,
-        raw_attributes: &[Attribute],
+        raw_attributes: &LocatedAttributes,
     ) -> ClassFileParserResult<Option<FieldConstantValue>> {
-        raw_attributes
-            .iter()
-            .filter(|attr| attr.name == "ConstantValue")
-            .map(|attr| {
-                if attr.bytes.len() != std::mem::size_of::<u16>() {
+        self
+            .decode(raw_attributes, "ConstantValue", |attribute_bytes| {
+                if attribute_bytes.len() != std::mem::size_of::<u16>() {
                     Err(ClassFileParserError::InvalidClassData {
                         name: "invalid attribute of type ConstantValue".to_string(),
                         is_invalidate_constant_pool_idx: false
                     })
                 } else {
-                    let attribute_bytes: &[u8] = &attr.bytes;
                     let constant_index = u16::from_be_bytes(attribute_bytes.try_into().unwrap());
                     self.class_file
                         .constants
                         .get_entry(constant_index)
-                        .map_err(|err| err.into())
                         .and_then(|entry| match entry {
                             ConstantPoolEntry::StringReference(v) => {
                                 let referred_string = self.read_string_reference(*v)?;
@@ -318,8 +526,6 @@ impl<'a> ClassFileReader<'a> {
                         })
                 }
             })
-            .next()
-            .invert()
     }
 
     fn search_deprecated_attribute(&self, raw_attributes: &[Attribute]) -> bool {
@@ -327,15 +533,45 @@ impl<'a> ClassFileReader<'a> {
     }
 
     fn read_methods(&mut self) -> ClassFileParserResult<()> {
-        let methods_count = self.buffer.read_u16()?;
-        self.class_file.methods = (0..methods_count)
-            .map(|_| self.read_method())
-            .collect::<ClassFileParserResult<Vec<ClassFileMethod>>>()?;
+        let methods_count = self.read_count("methods_count")?;
+        for index in 0..methods_count {
+            let segment = PathSegment::Item("methods", usize::from(index));
+            if let Some(method) = self.read_member(segment, Self::read_method)? {
+                self.class_file.methods.push(method);
+            }
+        }
+        Ok(())
+    }
+
+    /// 读取字段或者方法。宽松模式下无法读取的成员, 例如名称不是 Utf8 常量, 会按照属性声明的长度整个跳过
+    fn read_member<T>(
+        &mut self,
+        segment: PathSegment,
+        read: fn(&mut Self) -> ClassFileParserResult<T>,
+    ) -> ClassFileParserResult<Option<T>> {
+        let start = self.buffer.offset();
+        match self.located(segment.clone(), read) {
+            Ok(member) => Ok(Some(member)),
+            Err(err) if self.recovery.is_lenient() && !err.is_truncated() => {
+                self.recovery.report(Severity::Error, err, start)?;
+                self.buffer.seek(start);
+                self.located(segment, Self::skip_member)?;
+                Ok(None)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// 跳过 access_flags、name_index、descriptor_index 以及所有属性, 不解析其中的任何常量
+    /// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.5
+    fn skip_member(&mut self) -> ClassFileParserResult<()> {
+        self.buffer.read_bytes(3 * std::mem::size_of::<u16>())?;
+        skip_attributes(&mut self.buffer)?;
         Ok(())
     }
 
     fn read_method(&mut self) -> ClassFileParserResult<ClassFileMethod> {
-        let flags = self.read_method_flags()?;
+        let flags = self.located(PathSegment::Field("access_flags"), Self::read_method_flags)?;
         let name_constant_index = self.buffer.read_u16()?;
         let name = self.read_string_reference(name_constant_index)?;
         let type_constant_index = self.buffer.read_u16()?;
@@ -345,53 +581,80 @@ impl<'a> ClassFileReader<'a> {
         let code = if flags.contains(MethodFlags::NATIVE) || flags.contains(MethodFlags::ABSTRACT) {
             None
         } else {
-            Some(self.extract_code(&raw_attributes, &name)?)
+            // 缺少或者无法解码 Code 属性时原始属性仍然保留
+            let code = self.extract_code(&raw_attributes, &name);
+            self.recovery.recover(Severity::Warning, code, self.buffer.last_read_offset())?
         };
         let deprecated = self.search_deprecated_attribute(&raw_attributes);
         let thrown_exceptions = self.extract_thrown_exceptions(&raw_attributes)?;
+        let signature = self.search_signature_attribute(&raw_attributes, MethodSignature::parse)?;
+        let annotations = self.extract_annotations(&raw_attributes)?;
+        let type_annotations = self.extract_type_annotations(&raw_attributes)?;
+        let parameter_annotations = self.extract_parameter_annotations(&raw_attributes)?;
+        let constants = &self.class_file.constants;
+        let annotation_default =
+            self.decode(&raw_attributes, "AnnotationDefault", |bytes| ElementValue::read_default(bytes, constants))?;
+        let parameters = self
+            .decode(&raw_attributes, "MethodParameters", |bytes| {
+                let parameters = MethodParameter::read_all(bytes, constants)?;
+                for parameter in parameters.iter() {
+                    self.check_flags::<MethodParameterFlags>(parameter.flags.bits(), "method parameter")?;
+                }
+                Ok(parameters)
+            })?
+            .unwrap_or_default();
+        let custom_attributes = self.extract_custom_attributes(&raw_attributes)?;
 
         Ok(ClassFileMethod {
             flags,
             name,
             type_descriptor,
             parsed_type_descriptor,
-            attributes: raw_attributes,
+            attributes: raw_attributes.into_attributes(),
             code,
             deprecated,
             thrown_exceptions,
+            signature,
+            annotations,
+            type_annotations,
+            parameter_annotations,
+            annotation_default,
+            parameters,
+            custom_attributes,
         })
     }
 
     fn read_method_flags(&mut self) -> ClassFileParserResult<MethodFlags> {
         let method_flags_bits = self.buffer.read_u16()?;
-        match MethodFlags::from_bits(method_flags_bits) {
-            Some(flags) => Ok(flags),
-            None => Err(
-                ClassFileParserError::InvalidClassData {
-                    name: format!("invalid method flags: {method_flags_bits:#0x}"),
-                    is_invalidate_constant_pool_idx: false
-                })
-        }
+        self.check_flags(method_flags_bits, "method")
     }
 
     fn extract_code(
         &self,
-        raw_attributes: &[Attribute],
+        raw_attributes: &LocatedAttributes,
         name: &str,
     ) -> ClassFileParserResult<ClassFileMethodCode> {
-        raw_attributes
-            .iter()
-            .filter(|attr| attr.name == "Code")
-            .map(|attr| {
-                let mut buf = Buffer::new(&attr.bytes);
+        self
+            .decode_attribute(raw_attributes, "Code", |bytes, offset| {
+                let mut buf = Buffer::with_offset(bytes, offset);
                 let max_stack = buf.read_u16()?;
                 let max_locals = buf.read_u16()?;
                 let code_length = buf.read_u32()?.into_usize_safe();
                 let code = Vec::from(buf.read_bytes(code_length)?);
                 let exception_table = self.read_exception_table(&mut buf)?;
                 let attributes =
-                    Self::read_raw_attributes_from(&self.class_file.constants, &mut buf)?;
+                    Self::read_raw_attributes_from(&self.class_file.constants, &mut buf, &self.recovery)?;
                 let line_number_table = self.extract_line_number_table(&attributes)?;
+                let local_variable_table = self.extract_local_variable_table(&attributes);
+                let local_variable_table = self
+                    .recovery
+                    .recover(Severity::Warning, local_variable_table, buf.last_read_offset())?
+                    .flatten();
+                let constants = &self.class_file.constants;
+                let stack_map_table =
+                    self.decode(&attributes, "StackMapTable", |bytes| StackMapTable::read(bytes, constants))?;
+                let type_annotations = self.extract_type_annotations(&attributes)?;
+                let custom_attributes = self.extract_custom_attributes(&attributes)?;
 
                 ClassFileParserResult::<ClassFileMethodCode>::Ok(ClassFileMethodCode {
                     max_stack,
@@ -399,11 +662,13 @@ impl<'a> ClassFileReader<'a> {
                     code,
                     exception_table,
                     line_number_table,
-                    attributes,
+                    local_variable_table,
+                    stack_map_table,
+                    type_annotations,
+                    custom_attributes,
+                    attributes: attributes.into_attributes(),
                 })
-            })
-            .next()
-            .invert()?
+            })?
             .ok_or_else(|| {
                 ClassFileParserError::InvalidClassData {
                     name:format!(
@@ -417,34 +682,37 @@ impl<'a> ClassFileReader<'a> {
     fn read_exception_table(&self, buf: &mut Buffer) -> ClassFileParserResult<ExceptionTable> {
         let exception_table_length = buf.read_u16()?.into_usize_safe();
         let mut entries: Vec<ExceptionTableEntry> = Vec::with_capacity(exception_table_length / 8);
-        for _ in 0..exception_table_length {
-            let start_pc = buf.read_u16()?;
-            let end_pc = buf.read_u16()?;
-            let handler_pc = buf.read_u16()?;
-            let catch_class_constant = buf.read_u16()?;
-            let catch_class = if catch_class_constant == 0 {
-                None
-            } else {
-                Some(self.read_string_reference(catch_class_constant)?)
+        for index in 0..exception_table_length {
+            let mut read_entry = || {
+                let start_pc = buf.read_u16()?;
+                let end_pc = buf.read_u16()?;
+                let handler_pc = buf.read_u16()?;
+                let catch_class_constant = buf.read_u16()?;
+                let catch_class = if catch_class_constant == 0 {
+                    None
+                } else {
+                    Some(self.read_string_reference(catch_class_constant)?)
+                };
+                ClassFileParserResult::Ok(ExceptionTableEntry {
+                    range: ProgramCounter(start_pc)..ProgramCounter(end_pc),
+                    handler_pc: ProgramCounter(handler_pc),
+                    catch_class,
+                })
             };
-            entries.push(ExceptionTableEntry {
-                range: ProgramCounter(start_pc)..ProgramCounter(end_pc),
-                handler_pc: ProgramCounter(handler_pc),
-                catch_class,
-            })
+            let entry = read_entry()
+                .map_err(|err| err.locate(buf.last_read_offset(), PathSegment::Item("exception_table", index)))?;
+            entries.push(entry);
         }
         Ok(ExceptionTable::new(entries))
     }
 
     fn extract_line_number_table(
         &self,
-        raw_attributes: &[Attribute],
+        raw_attributes: &LocatedAttributes,
     ) -> ClassFileParserResult<Option<LineNumberTable>> {
-        raw_attributes
-            .iter()
-            .find(|attr| attr.name == "LineNumberTable")
-            .map(|attr| {
-                let mut buf = Buffer::new(&attr.bytes);
+        self
+            .decode(raw_attributes, "LineNumberTable", |bytes| {
+                let mut buf = Buffer::new(bytes);
                 let num_entries = buf.read_u16()?.into_usize_safe();
                 let mut entries = Vec::with_capacity(num_entries);
                 for _ in 0..num_entries {
@@ -457,15 +725,76 @@ impl<'a> ClassFileReader<'a> {
                 }
                 Ok(LineNumberTable::new(entries))
             })
-            .invert()
     }
 
-    fn extract_thrown_exceptions(&self, raw_attributes: &[Attribute]) -> ClassFileParserResult<Vec<String>> {
-        raw_attributes
-            .iter()
-            .find(|attr| attr.name == "Exceptions")
-            .map(|attr| {
-                let mut buf = Buffer::new(&attr.bytes);
+    /// LocalVariableTable 可以有多个, LocalVariableTypeTable 中的签名按照作用域、slot 与名称合并到对应的局部变量上
+    fn extract_local_variable_table(
+        &self,
+        raw_attributes: &LocatedAttributes,
+    ) -> ClassFileParserResult<Option<LocalVariableTable>> {
+        let read_entries = |name: &str| {
+            let mut entries = Vec::new();
+            for (attr, offset) in raw_attributes.with_offsets().filter(|(attr, _)| attr.name == name) {
+                let mut buf = Buffer::with_offset(&attr.bytes, offset);
+                let mut read_attribute = || {
+                    let num_entries = buf.read_u16()?;
+                    for _ in 0..num_entries {
+                        let start_pc = buf.read_u16()?;
+                        let entry_offset = buf.last_read_offset();
+                        let length = buf.read_u16()?;
+                        let end_pc = start_pc.checked_add(length).ok_or_else(|| ClassFileParserError::InvalidClassData {
+                            name: format!("local variable range {start_pc}+{length} overflows the code"),
+                            is_invalidate_constant_pool_idx: false,
+                        })?;
+                        let name = self.read_string_reference(buf.read_u16()?)?;
+                        let descriptor = self.read_string_reference(buf.read_u16()?)?;
+                        let slot = buf.read_u16()?;
+                        let range = ProgramCounter(start_pc)..ProgramCounter(end_pc);
+                        entries.push((range, slot, name, descriptor, entry_offset));
+                    }
+                    ClassFileParserResult::Ok(())
+                };
+                read_attribute().map_err(|err| locate(err, attr, buf.last_read_offset()))?;
+            }
+            ClassFileParserResult::Ok(entries)
+        };
+
+        let variables = read_entries("LocalVariableTable")?;
+        if variables.is_empty() {
+            return Ok(None);
+        }
+        let types = read_entries("LocalVariableTypeTable")?;
+        let mut entries = Vec::with_capacity(variables.len());
+        // 描述符与签名在读取完所有条目之后才解析, 出错时使用条目的偏移
+        let locate_entry = |name: &'static str, offset: usize| {
+            move |err: ClassFileParserError| err.locate(offset, PathSegment::Attribute(name.to_string()))
+        };
+        for (range, slot, name, descriptor, offset) in variables {
+            let signature = types
+                .iter()
+                .find(|(type_range, type_slot, type_name, _, _)| {
+                    *type_range == range && *type_slot == slot && *type_name == name
+                })
+                .map(|(_, _, _, signature, offset)| {
+                    FieldSignature::parse(signature).map_err(locate_entry("LocalVariableTypeTable", *offset))
+                })
+                .invert()?;
+            let type_descriptor = Type::parse(&descriptor).map_err(locate_entry("LocalVariableTable", offset))?;
+            entries.push(LocalVariable {
+                range,
+                slot,
+                name,
+                type_descriptor,
+                signature,
+            });
+        }
+        Ok(Some(LocalVariableTable::new(entries)))
+    }
+
+    fn extract_thrown_exceptions(&self, raw_attributes: &LocatedAttributes) -> ClassFileParserResult<Vec<String>> {
+        self
+            .decode(raw_attributes, "Exceptions", |bytes| {
+                let mut buf = Buffer::new(bytes);
                 let num_entries = buf.read_u16()?.into_usize_safe();
                 let mut exceptions = Vec::with_capacity(num_entries);
                 for _ in 0..num_entries {
@@ -475,27 +804,153 @@ impl<'a> ClassFileReader<'a> {
                 }
                 Ok(exceptions)
             })
-            .unwrap_or(Ok(Vec::new()))
+            .map(Option::unwrap_or_default)
     }
 
     fn read_class_attributes(&mut self) -> ClassFileParserResult<()> {
         let raw_attributes = self.read_raw_attributes()?;
         self.class_file.deprecated = self.search_deprecated_attribute(&raw_attributes);
         self.class_file.source_file = self.search_source_file_attribute(&raw_attributes)?;
+        let source_debug_extension = self.decode_with_offset(&raw_attributes, "SourceDebugExtension", |bytes, offset| {
+            let text = Buffer::new(bytes).read_utf8(bytes.len())?;
+            // 无法解析的 SMAP 不影响读取 class 文件, 保留原始内容, 宽松模式下报告一个 warning
+            let source_map = if text.starts_with("SMAP") {
+                self.recovery.recover(Severity::Warning, SourceMap::parse(&text), offset).ok().flatten()
+            } else {
+                None
+            };
+            Ok((text, source_map))
+        })?;
+        if let Some((text, source_map)) = source_debug_extension {
+            self.class_file.source_debug_extension = Some(text);
+            self.class_file.source_map = source_map;
+        }
+        self.class_file.signature = self.search_signature_attribute(&raw_attributes, ClassSignature::parse)?;
+        self.class_file.bootstrap_methods = self
+            .decode(&raw_attributes, "BootstrapMethods", BootstrapMethod::read_all)?
+            .unwrap_or_default();
+        self.class_file.annotations = self.extract_annotations(&raw_attributes)?;
+        self.class_file.type_annotations = self.extract_type_annotations(&raw_attributes)?;
+        self.extract_nesting(&raw_attributes)?;
+        self.class_file.record_components = self
+            .decode_with_offset(&raw_attributes, "Record", |bytes, offset| self.read_record_components(bytes, offset))?;
+        let constants = &self.class_file.constants;
+        self.class_file.permitted_subclasses = self
+            .decode(&raw_attributes, "PermittedSubclasses", |bytes| read_class_names(bytes, constants))?
+            .unwrap_or_default();
+        self.class_file.module = self.extract_module(&raw_attributes)?;
+        self.class_file.custom_attributes = self.extract_custom_attributes(&raw_attributes)?;
+        self.class_file.attributes = raw_attributes.into_attributes();
+        Ok(())
+    }
+
+    /// InnerClasses、EnclosingMethod、NestHost 与 NestMembers 属性描述了类的嵌套关系
+    fn extract_nesting(&mut self, raw_attributes: &LocatedAttributes) -> ClassFileParserResult<()> {
+        let constants = &self.class_file.constants;
+        let inner_classes = self
+            .decode(raw_attributes, "InnerClasses", |bytes| {
+                let inner_classes = InnerClass::read_all(bytes, constants)?;
+                for inner_class in inner_classes.iter() {
+                    self.check_flags::<InnerClassAccessFlags>(inner_class.flags.bits(), "inner class")?;
+                }
+                Ok(inner_classes)
+            })?
+            .unwrap_or_default();
+        let enclosing_method =
+            self.decode(raw_attributes, "EnclosingMethod", |bytes| EnclosingMethod::read(bytes, constants))?;
+        let nest_host = self.decode(raw_attributes, "NestHost", |bytes| {
+            let mut buf = Buffer::new(bytes);
+            constants.class_name_of(buf.read_u16()?)
+        })?;
+        let nest_members = self
+            .decode(raw_attributes, "NestMembers", |bytes| read_class_names(bytes, constants))?
+            .unwrap_or_default();
+        self.class_file.inner_classes = inner_classes;
+        self.class_file.enclosing_method = enclosing_method;
+        self.class_file.nest_host = nest_host;
+        self.class_file.nest_members = nest_members;
         Ok(())
     }
 
-    fn search_source_file_attribute(&self, raw_attributes: &[Attribute]) -> ClassFileParserResult<Option<String>> {
-        raw_attributes
-            .iter()
-            .find(|attr| attr.name == "SourceFile")
-            .map(|attr| {
-                let attribute_bytes: &[u8] = &attr.bytes;
-                let constant_index = u16::from_be_bytes(attribute_bytes.try_into().unwrap());
+    fn extract_custom_attributes(&self, raw_attributes: &LocatedAttributes) -> ClassFileParserResult<CustomAttributes> {
+        match self.registry {
+            Some(registry) => {
+                let custom_attributes = registry.decode_all(raw_attributes, &self.class_file.constants);
+                let custom_attributes =
+                    self.recovery.recover(Severity::Warning, custom_attributes, self.buffer.last_read_offset())?;
+                Ok(custom_attributes.unwrap_or_default())
+            }
+            None => Ok(CustomAttributes::default()),
+        }
+    }
+
+    /// Module 属性以及只能与它一起出现的 ModulePackages 与 ModuleMainClass 属性
+    fn extract_module(&self, raw_attributes: &LocatedAttributes) -> ClassFileParserResult<Option<ModuleDescriptor>> {
+        let constants = &self.class_file.constants;
+        let Some(mut module) = self.decode(raw_attributes, "Module", |bytes| {
+            let module = ModuleDescriptor::read(bytes, constants)?;
+            self.check_flags::<ModuleFlags>(module.flags.bits(), "module")?;
+            for requires in module.requires.iter() {
+                self.check_flags::<ModuleRequiresFlags>(requires.flags.bits(), "requires")?;
+            }
+            for exports in module.exports.iter() {
+                self.check_flags::<ModuleExportsFlags>(exports.flags.bits(), "exports")?;
+            }
+            for opens in module.opens.iter() {
+                self.check_flags::<ModuleExportsFlags>(opens.flags.bits(), "opens")?;
+            }
+            Ok(module)
+        })?
+        else {
+            return Ok(None);
+        };
+        module.packages = self
+            .decode(raw_attributes, "ModulePackages", |bytes| ModuleDescriptor::read_packages(bytes, constants))?
+            .unwrap_or_default();
+        module.main_class = self.decode(raw_attributes, "ModuleMainClass", |bytes| {
+            let mut buf = Buffer::new(bytes);
+            constants.class_name_of(buf.read_u16()?)
+        })?;
+        Ok(Some(module))
+    }
+
+    /// record 组件的结构与字段类似, 只是没有 flags
+    /// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.30
+    fn read_record_components(&self, bytes: &[u8], offset: usize) -> ClassFileParserResult<Vec<RecordComponent>> {
+        let mut buf = Buffer::with_offset(bytes, offset);
+        let components_count = buf.read_u16()?;
+        (0..components_count)
+            .map(|index| {
+                let mut read_component = || {
+                    let name = self.read_string_reference(buf.read_u16()?)?;
+                    let type_descriptor = Type::parse(&self.read_string_reference(buf.read_u16()?)?)?;
+                    let attributes =
+                        Self::read_raw_attributes_from(&self.class_file.constants, &mut buf, &self.recovery)?;
+                    ClassFileParserResult::Ok(RecordComponent {
+                        name,
+                        type_descriptor,
+                        signature: self.search_signature_attribute(&attributes, FieldSignature::parse)?,
+                        annotations: self.extract_annotations(&attributes)?,
+                        type_annotations: self.extract_type_annotations(&attributes)?,
+                        attributes: attributes.into_attributes(),
+                    })
+                };
+                let segment = PathSegment::Item("components", usize::from(index));
+                read_component().map_err(|err| err.locate(buf.last_read_offset(), segment))
+            })
+            .collect()
+    }
+
+    fn search_source_file_attribute(
+        &self,
+        raw_attributes: &LocatedAttributes,
+    ) -> ClassFileParserResult<Option<String>> {
+        self
+            .decode(raw_attributes, "SourceFile", |bytes| {
+                let constant_index = Buffer::new(bytes).read_u16()?;
                 self.class_file
                     .constants
                     .get_entry(constant_index)
-                    .map_err(|err| err.into())
                     .and_then(|entry| match entry {
                         ConstantPoolEntry::Utf8(file_name) => Ok(file_name.clone()),
                         _ => Err(
@@ -506,31 +961,120 @@ impl<'a> ClassFileReader<'a> {
                         ),
                     })
             })
-            .invert()
     }
 
-    fn read_raw_attributes(&mut self) -> ClassFileParserResult<Vec<Attribute>> {
-        Self::read_raw_attributes_from(&self.class_file.constants, &mut self.buffer)
+    /// Signature 属性的内容是一个指向 Utf8 常量的索引, 使用 parse 解析类、字段或者方法的签名
+    /// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.9
+    fn search_signature_attribute<T>(
+        &self,
+        raw_attributes: &LocatedAttributes,
+        parse: fn(&str) -> ClassFileParserResult<T>,
+    ) -> ClassFileParserResult<Option<T>> {
+        self.decode(raw_attributes, "Signature", |bytes| {
+            let mut buf = Buffer::new(bytes);
+            let constant_index = buf.read_u16()?;
+            match self.class_file.constants.get_entry(constant_index)? {
+                ConstantPoolEntry::Utf8(signature) => parse(signature),
+                _ => Err(ClassFileParserError::InvalidClassData {
+                    name: "invalid Signature attribute".to_string(),
+                    is_invalidate_constant_pool_idx: true,
+                }),
+            }
+        })
+    }
+
+    /// 运行时可见与不可见的注解分别保存在 RuntimeVisibleAnnotations 与 RuntimeInvisibleAnnotations 属性中
+    /// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.16
+    fn extract_annotations(&self, raw_attributes: &LocatedAttributes) -> ClassFileParserResult<Annotations> {
+        let read = |name: &str| -> ClassFileParserResult<Vec<Annotation>> {
+            self.decode(raw_attributes, name, |bytes| Annotation::read_all(bytes, &self.class_file.constants))
+                .map(Option::unwrap_or_default)
+        };
+        Ok(Annotations {
+            visible: read("RuntimeVisibleAnnotations")?,
+            invisible: read("RuntimeInvisibleAnnotations")?,
+        })
+    }
+
+    /// 类型注解与注解类似, 分为 RuntimeVisibleTypeAnnotations 与 RuntimeInvisibleTypeAnnotations 两个属性
+    /// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.20
+    fn extract_type_annotations(&self, raw_attributes: &LocatedAttributes) -> ClassFileParserResult<TypeAnnotations> {
+        let read = |name: &str| -> ClassFileParserResult<Vec<TypeAnnotation>> {
+            self.decode(raw_attributes, name, |bytes| TypeAnnotation::read_all(bytes, &self.class_file.constants))
+                .map(Option::unwrap_or_default)
+        };
+        Ok(TypeAnnotations {
+            visible: read("RuntimeVisibleTypeAnnotations")?,
+            invisible: read("RuntimeInvisibleTypeAnnotations")?,
+        })
     }
 
+    /// 可见与不可见的参数注解各自给出了参数个数, 按照参数的下标合并
+    /// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.18
+    fn extract_parameter_annotations(
+        &self,
+        raw_attributes: &LocatedAttributes,
+    ) -> ClassFileParserResult<Vec<Annotations>> {
+        let read = |name: &str| -> ClassFileParserResult<Vec<Vec<Annotation>>> {
+            self.decode(raw_attributes, name, |bytes| Annotation::read_parameters(bytes, &self.class_file.constants))
+                .map(Option::unwrap_or_default)
+        };
+        let visible = read("RuntimeVisibleParameterAnnotations")?;
+        let invisible = read("RuntimeInvisibleParameterAnnotations")?;
+        let mut parameter_annotations = vec![Annotations::default(); visible.len().max(invisible.len())];
+        for (index, annotations) in visible.into_iter().enumerate() {
+            parameter_annotations[index].visible = annotations;
+        }
+        for (index, annotations) in invisible.into_iter().enumerate() {
+            parameter_annotations[index].invisible = annotations;
+        }
+        Ok(parameter_annotations)
+    }
+
+    fn read_raw_attributes(&mut self) -> ClassFileParserResult<LocatedAttributes> {
+        Self::read_raw_attributes_from(&self.class_file.constants, &mut self.buffer, &self.recovery)
+    }
+
+    /// 宽松模式下跳过名称无法解析的属性
     fn read_raw_attributes_from(
         constants_pool: &ConstantPool,
         buffer: &mut Buffer,
-    ) -> ClassFileParserResult<Vec<Attribute>> {
-        let attributes_count = buffer.read_u16()?;
-        (0..attributes_count)
-            .map(|_| Self::read_raw_attribute(constants_pool, buffer))
-            .collect::<ClassFileParserResult<Vec<Attribute>>>()
+        recovery: &Recovery,
+    ) -> ClassFileParserResult<LocatedAttributes> {
+        let attributes_count = buffer
+            .read_u16()
+            .map_err(|err| err.locate(buffer.last_read_offset(), PathSegment::Field("attributes_count")))?;
+        let mut attributes = LocatedAttributes::default();
+        for index in 0..attributes_count {
+            let attribute = Self::read_raw_attribute(constants_pool, buffer).map_err(|err| {
+                err.locate(buffer.last_read_offset(), PathSegment::Item("attributes", usize::from(index)))
+            });
+            match attribute {
+                Ok((attribute, offset)) => attributes.push(attribute, offset),
+                Err(err) if err.is_truncated() => return Err(err),
+                Err(err) => recovery.report(Severity::Warning, err, buffer.last_read_offset())?,
+            }
+        }
+        Ok(attributes)
     }
 
-    fn read_raw_attribute(constants_pool: &ConstantPool, buffer: &mut Buffer) -> ClassFileParserResult<Attribute> {
+    /// 返回属性以及属性的内容在输入中的偏移
+    fn read_raw_attribute(
+        constants_pool: &ConstantPool,
+        buffer: &mut Buffer,
+    ) -> ClassFileParserResult<(Attribute, usize)> {
         let name_constant_index = buffer.read_u16()?;
-        let name = Self::read_string_reference_from(constants_pool, name_constant_index)?;
+        let name_offset = buffer.last_read_offset();
         let len = buffer.read_u32()?;
+        let offset = buffer.offset();
         let bytes = buffer.read_bytes(len.into_usize_safe())?;
-        Ok(Attribute {
+        // 先读取完整个属性, 名称无法解析时可以跳过这个属性
+        let name = Self::read_string_reference_from(constants_pool, name_constant_index)
+            .map_err(|err| err.locate(name_offset, PathSegment::Field("attribute_name_index")))?;
+        let attribute = Attribute {
             name,
             bytes: Vec::from(bytes),
-        })
+        };
+        Ok((attribute, offset))
     }
 }
\ No newline at end of file
@@ -0,0 +1,559 @@
+use std::collections::HashMap;
+
+use cesu8::to_java_cesu8;
+
+use crate::class::ClassFile;
+use crate::class_attributes::bootstrap_methods::BootstrapMethod;
+use crate::class_parser::ClassFileReader;
+use crate::constant_pool::constant_pool::{ConstantPool, ConstantPoolEntry};
+use crate::custom_attribute::{ConstantPoolBuilder, CustomAttributes};
+use crate::error::{ClassFileParserError, ClassFileParserResult};
+use crate::field::class_filed::{ClassFileField, FieldConstantValue};
+use crate::method::class_method::{ClassFileMethod, ClassFileMethodCode};
+use crate::utils::attribute::Attribute;
+use crate::utils::buffer::BufferWriter;
+
+/// 将 [ClassFile] 写回 class 文件的字节流, 是 `ClassFileReader` 的逆操作
+///
+/// 常量池原样写出, 字段、方法中引用的常量按内容在常量池中查找(取第一个匹配项),
+/// 找不到时追加到常量池的末尾, 因此未修改的 ClassFile 会得到与原文件完全相同的字节。
+/// 已经解析出来的属性(Code、Exceptions、ConstantValue、SourceFile、SourceDebugExtension、BootstrapMethods、
+/// StackMapTable 以及 Deprecated)由对应的字段重新编码,
+/// 自定义属性由 [crate::custom_attribute::CustomAttribute::encode] 重新编码, 其余属性原样写出。
+/// 原样写出的属性(例如 Signature、注解、InnerClasses 与 LineNumberTable)对应的字段被修改之后,
+/// 写出的内容会与字段不一致, 这时返回错误而不是静默地丢弃修改。
+/// Reference: https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html
+pub(crate) struct ClassFileWriter<'a> {
+    class_file: &'a ClassFile,
+    constants: ConstantPool,
+    utf8_indexes: HashMap<String, u16>,
+    class_indexes: HashMap<String, u16>,
+}
+
+impl<'a> ClassFileWriter<'a> {
+    pub(crate) fn new(class_file: &'a ClassFile) -> ClassFileWriter<'a> {
+        let constants = class_file.constants.clone();
+        let mut utf8_indexes = HashMap::new();
+        let mut class_indexes = HashMap::new();
+        for (index, entry) in constants.iter() {
+            match entry {
+                ConstantPoolEntry::Utf8(text) => {
+                    utf8_indexes.entry(text.clone()).or_insert(index);
+                }
+                // 只记录名称是 Utf8 的类常量, 不合法的常量池不会在这里被间接地解析
+                ConstantPoolEntry::ClassReference(name_index) => {
+                    if let Ok(ConstantPoolEntry::Utf8(name)) = constants.get_entry(*name_index) {
+                        class_indexes.entry(name.clone()).or_insert(index);
+                    }
+                }
+                _ => {}
+            }
+        }
+        ClassFileWriter {
+            class_file,
+            constants,
+            utf8_indexes,
+            class_indexes,
+        }
+    }
+
+    pub(crate) fn write(mut self) -> ClassFileParserResult<Vec<u8>> {
+        // 先写出常量池之后的内容, 期间可能会向常量池中追加新的常量
+        let mut body = BufferWriter::new();
+        self.write_class_body(&mut body)?;
+
+        let mut buffer = BufferWriter::new();
+        buffer.write_u32(0xCAFEBABE);
+        buffer.write_u16(self.class_file.version.minor_version());
+        buffer.write_u16(self.class_file.version.major_version());
+        self.write_constants(&mut buffer)?;
+        buffer.write_bytes(&body.into_bytes());
+        let bytes = buffer.into_bytes();
+        Self::check_raw_attributes(self.class_file, &bytes)?;
+        Ok(bytes)
+    }
+
+    /// 重新读取写出的字节, 检查原样写出的属性与 ClassFile 中对应的字段是否一致
+    fn check_raw_attributes(class_file: &ClassFile, bytes: &[u8]) -> ClassFileParserResult<()> {
+        let written = ClassFileReader::lenient(bytes, None).read_lenient().class_file;
+        let class = format!("class {}", class_file.name);
+        check_unchanged(&class, "signature", &class_file.signature, &written.signature)?;
+        check_unchanged(&class, "annotations", &class_file.annotations, &written.annotations)?;
+        check_unchanged(&class, "type_annotations", &class_file.type_annotations, &written.type_annotations)?;
+        check_unchanged(&class, "inner_classes", &class_file.inner_classes, &written.inner_classes)?;
+        check_unchanged(&class, "enclosing_method", &class_file.enclosing_method, &written.enclosing_method)?;
+        check_unchanged(&class, "nest_host", &class_file.nest_host, &written.nest_host)?;
+        check_unchanged(&class, "nest_members", &class_file.nest_members, &written.nest_members)?;
+        check_unchanged(&class, "record_components", &class_file.record_components, &written.record_components)?;
+        check_unchanged(
+            &class,
+            "permitted_subclasses",
+            &class_file.permitted_subclasses,
+            &written.permitted_subclasses,
+        )?;
+        check_unchanged(&class, "module", &class_file.module, &written.module)?;
+        check_unchanged(
+            &class,
+            "source_debug_extension",
+            &class_file.source_debug_extension,
+            &written.source_debug_extension,
+        )?;
+
+        for (field, written) in class_file.fields.iter().zip(written.fields.iter()) {
+            let owner = format!("field {}", field.name);
+            check_unchanged(&owner, "signature", &field.signature, &written.signature)?;
+            check_unchanged(&owner, "annotations", &field.annotations, &written.annotations)?;
+            check_unchanged(&owner, "type_annotations", &field.type_annotations, &written.type_annotations)?;
+        }
+        for (method, written) in class_file.methods.iter().zip(written.methods.iter()) {
+            let owner = format!("method {}{}", method.name, method.type_descriptor);
+            check_unchanged(&owner, "signature", &method.signature, &written.signature)?;
+            check_unchanged(&owner, "annotations", &method.annotations, &written.annotations)?;
+            check_unchanged(&owner, "type_annotations", &method.type_annotations, &written.type_annotations)?;
+            check_unchanged(
+                &owner,
+                "parameter_annotations",
+                &method.parameter_annotations,
+                &written.parameter_annotations,
+            )?;
+            check_unchanged(&owner, "annotation_default", &method.annotation_default, &written.annotation_default)?;
+            check_unchanged(&owner, "parameters", &method.parameters, &written.parameters)?;
+            if let (Some(code), Some(written)) = (&method.code, &written.code) {
+                check_unchanged(&owner, "line_number_table", &code.line_number_table, &written.line_number_table)?;
+                check_unchanged(
+                    &owner,
+                    "local_variable_table",
+                    &code.local_variable_table,
+                    &written.local_variable_table,
+                )?;
+                check_unchanged(&owner, "code type_annotations", &code.type_annotations, &written.type_annotations)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_class_body(&mut self, buffer: &mut BufferWriter) -> ClassFileParserResult<()> {
+        let class_file = self.class_file;
+        buffer.write_u16(class_file.flags.bits());
+        let this_class = self.class_index(&class_file.name)?;
+        buffer.write_u16(this_class);
+        let super_class = match &class_file.superclass {
+            Some(superclass) => self.class_index(superclass)?,
+            None => 0,
+        };
+        buffer.write_u16(super_class);
+
+        buffer.write_u16(Self::length_u16(class_file.interfaces.len(), "interfaces")?);
+        for interface in class_file.interfaces.iter() {
+            let index = self.class_index(interface)?;
+            buffer.write_u16(index);
+        }
+
+        buffer.write_u16(Self::length_u16(class_file.fields.len(), "fields")?);
+        for field in class_file.fields.iter() {
+            self.write_field(buffer, field)?;
+        }
+
+        buffer.write_u16(Self::length_u16(class_file.methods.len(), "methods")?);
+        for method in class_file.methods.iter() {
+            self.write_method(buffer, method)?;
+        }
+
+        self.write_class_attributes(buffer)
+    }
+
+    fn write_constants(&self, buffer: &mut BufferWriter) -> ClassFileParserResult<()> {
+        buffer.write_u16(Self::length_u16(self.constants.len() + 1, "constant pool entries")?);
+        for (_, entry) in self.constants.iter() {
+            match entry {
+                ConstantPoolEntry::Utf8(text) => {
+                    buffer.write_u8(1);
+                    buffer.write_utf8(text)?;
+                }
+                ConstantPoolEntry::Integer(value) => {
+                    buffer.write_u8(3);
+                    buffer.write_i32(*value);
+                }
+                ConstantPoolEntry::Float(value) => {
+                    buffer.write_u8(4);
+                    buffer.write_f32(*value);
+                }
+                ConstantPoolEntry::Long(value) => {
+                    buffer.write_u8(5);
+                    buffer.write_i64(*value);
+                }
+                ConstantPoolEntry::Double(value) => {
+                    buffer.write_u8(6);
+                    buffer.write_f64(*value);
+                }
+                ConstantPoolEntry::ClassReference(index) => {
+                    buffer.write_u8(7);
+                    buffer.write_u16(*index);
+                }
+                ConstantPoolEntry::StringReference(index) => {
+                    buffer.write_u8(8);
+                    buffer.write_u16(*index);
+                }
+                ConstantPoolEntry::FieldReference(class_index, name_and_type_index) => {
+                    buffer.write_u8(9);
+                    buffer.write_u16(*class_index);
+                    buffer.write_u16(*name_and_type_index);
+                }
+                ConstantPoolEntry::MethodReference(class_index, name_and_type_index) => {
+                    buffer.write_u8(10);
+                    buffer.write_u16(*class_index);
+                    buffer.write_u16(*name_and_type_index);
+                }
+                ConstantPoolEntry::InterfaceMethodReference(class_index, name_and_type_index) => {
+                    buffer.write_u8(11);
+                    buffer.write_u16(*class_index);
+                    buffer.write_u16(*name_and_type_index);
+                }
+                ConstantPoolEntry::NameAndTypeDescriptor(name_index, type_index) => {
+                    buffer.write_u8(12);
+                    buffer.write_u16(*name_index);
+                    buffer.write_u16(*type_index);
+                }
+                ConstantPoolEntry::MethodHandle(kind, reference_index) => {
+                    buffer.write_u8(15);
+                    buffer.write_u8(*kind as u8);
+                    buffer.write_u16(*reference_index);
+                }
+                ConstantPoolEntry::MethodType(index) => {
+                    buffer.write_u8(16);
+                    buffer.write_u16(*index);
+                }
+                ConstantPoolEntry::Dynamic(bootstrap_index, name_and_type_index) => {
+                    buffer.write_u8(17);
+                    buffer.write_u16(*bootstrap_index);
+                    buffer.write_u16(*name_and_type_index);
+                }
+                ConstantPoolEntry::InvokeDynamic(bootstrap_index, name_and_type_index) => {
+                    buffer.write_u8(18);
+                    buffer.write_u16(*bootstrap_index);
+                    buffer.write_u16(*name_and_type_index);
+                }
+                ConstantPoolEntry::ModuleReference(index) => {
+                    buffer.write_u8(19);
+                    buffer.write_u16(*index);
+                }
+                ConstantPoolEntry::PackageReference(index) => {
+                    buffer.write_u8(20);
+                    buffer.write_u16(*index);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn write_field(&mut self, buffer: &mut BufferWriter, field: &ClassFileField) -> ClassFileParserResult<()> {
+        buffer.write_u16(field.flags.bits());
+        let name_index = self.utf8_index(&field.name)?;
+        buffer.write_u16(name_index);
+        let type_index = self.utf8_index(&field.type_descriptor.descriptor())?;
+        buffer.write_u16(type_index);
+
+        let constant_value = match &field.constant_value {
+            Some(constant_value) => {
+                let index = self.constant_value_index(constant_value)?;
+                Some(Self::u16_attribute("ConstantValue", index))
+            }
+            None => None,
+        };
+        let mut encoded = vec![
+            ("ConstantValue", constant_value),
+            ("Deprecated", Self::deprecated_attribute(field.deprecated)),
+        ];
+        self.add_custom_attributes(&mut encoded, &field.custom_attributes)?;
+        let attributes = Self::merge_attributes(&field.attributes, &encoded);
+        self.write_attributes(buffer, attributes.into_iter())
+    }
+
+    fn write_method(&mut self, buffer: &mut BufferWriter, method: &ClassFileMethod) -> ClassFileParserResult<()> {
+        buffer.write_u16(method.flags.bits());
+        let name_index = self.utf8_index(&method.name)?;
+        buffer.write_u16(name_index);
+        let type_index = self.utf8_index(&method.type_descriptor)?;
+        buffer.write_u16(type_index);
+
+        let code = match &method.code {
+            Some(code) => Some(Attribute {
+                name: "Code".to_string(),
+                bytes: self.encode_code(code)?,
+            }),
+            None => None,
+        };
+        let exceptions = if method.thrown_exceptions.is_empty() {
+            None
+        } else {
+            Some(self.encode_exceptions(&method.thrown_exceptions)?)
+        };
+        let mut encoded = vec![
+            ("Code", code),
+            ("Exceptions", exceptions),
+            ("Deprecated", Self::deprecated_attribute(method.deprecated)),
+        ];
+        self.add_custom_attributes(&mut encoded, &method.custom_attributes)?;
+        let attributes = Self::merge_attributes(&method.attributes, &encoded);
+        self.write_attributes(buffer, attributes.into_iter())
+    }
+
+    /// Code 属性的格式参考: https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.3
+    fn encode_code(&mut self, code: &ClassFileMethodCode) -> ClassFileParserResult<Vec<u8>> {
+        let mut buffer = BufferWriter::new();
+        buffer.write_u16(code.max_stack);
+        buffer.write_u16(code.max_locals);
+        let code_length = u32::try_from(code.code.len()).map_err(|_| Self::too_long("code", code.code.len()))?;
+        buffer.write_u32(code_length);
+        buffer.write_bytes(&code.code);
+
+        let entries = code.exception_table.entries();
+        buffer.write_u16(Self::length_u16(entries.len(), "exception table entries")?);
+        for entry in entries {
+            buffer.write_u16(entry.range.start.0);
+            buffer.write_u16(entry.range.end.0);
+            buffer.write_u16(entry.handler_pc.0);
+            let catch_type = match &entry.catch_class {
+                Some(catch_class) => self.class_index(catch_class)?,
+                None => 0,
+            };
+            buffer.write_u16(catch_type);
+        }
+
+        let stack_map_table = match &code.stack_map_table {
+            Some(stack_map_table) => Some(Attribute {
+                name: "StackMapTable".to_string(),
+                bytes: stack_map_table.write(&mut |class_name| self.class_index(class_name))?,
+            }),
+            None => None,
+        };
+        let mut encoded = vec![("StackMapTable", stack_map_table)];
+        self.add_custom_attributes(&mut encoded, &code.custom_attributes)?;
+        let attributes = Self::merge_attributes(&code.attributes, &encoded);
+        self.write_attributes(&mut buffer, attributes.into_iter())?;
+        Ok(buffer.into_bytes())
+    }
+
+    fn encode_exceptions(&mut self, thrown_exceptions: &[String]) -> ClassFileParserResult<Attribute> {
+        let mut buffer = BufferWriter::new();
+        buffer.write_u16(Self::length_u16(thrown_exceptions.len(), "thrown exceptions")?);
+        for exception in thrown_exceptions {
+            let index = self.class_index(exception)?;
+            buffer.write_u16(index);
+        }
+        Ok(Attribute {
+            name: "Exceptions".to_string(),
+            bytes: buffer.into_bytes(),
+        })
+    }
+
+    fn write_class_attributes(&mut self, buffer: &mut BufferWriter) -> ClassFileParserResult<()> {
+        let class_file = self.class_file;
+        let source_file = match &class_file.source_file {
+            Some(source_file) => Some(Self::u16_attribute("SourceFile", self.utf8_index(source_file)?)),
+            None => None,
+        };
+        let source_debug_extension = class_file.source_debug_extension.as_ref().map(|text| Attribute {
+            name: "SourceDebugExtension".to_string(),
+            bytes: to_java_cesu8(text).into_owned(),
+        });
+        let bootstrap_methods = if class_file.bootstrap_methods.is_empty() {
+            None
+        } else {
+            Some(Attribute {
+                name: "BootstrapMethods".to_string(),
+                bytes: BootstrapMethod::write_all(&class_file.bootstrap_methods)?,
+            })
+        };
+        let mut encoded = vec![
+            ("SourceFile", source_file),
+            ("Deprecated", Self::deprecated_attribute(class_file.deprecated)),
+            ("SourceDebugExtension", source_debug_extension),
+            ("BootstrapMethods", bootstrap_methods),
+        ];
+        self.add_custom_attributes(&mut encoded, &class_file.custom_attributes)?;
+        let attributes = Self::merge_attributes(&class_file.attributes, &encoded);
+        self.write_attributes(buffer, attributes.into_iter())
+    }
+
+    /// 自定义属性排在标准属性之后, 与标准属性同名的自定义属性不会被写出
+    fn add_custom_attributes<'b>(
+        &mut self,
+        encoded: &mut Vec<(&'b str, Option<Attribute>)>,
+        custom_attributes: &'b CustomAttributes,
+    ) -> ClassFileParserResult<()> {
+        let standard_names: Vec<&str> = encoded.iter().map(|(name, _)| *name).collect();
+        let custom = custom_attributes.encode_all(self, &standard_names)?;
+        encoded.extend(custom.into_iter().map(|(name, attribute)| (name, Some(attribute))));
+        Ok(())
+    }
+
+    /// 用重新编码的属性替换原始属性中的同名属性, 保持原始属性的顺序, 原始属性中没有的追加到末尾。
+    /// 重新编码的值为 None 时表示对应的字段为空, 原始属性中的同名属性会被删除。
+    fn merge_attributes<'b>(
+        raw_attributes: &'b [Attribute],
+        encoded: &'b [(&str, Option<Attribute>)],
+    ) -> Vec<&'b Attribute> {
+        let mut written = vec![false; encoded.len()];
+        let mut attributes = Vec::with_capacity(raw_attributes.len() + encoded.len());
+        for attribute in raw_attributes {
+            match encoded.iter().position(|(name, _)| *name == attribute.name) {
+                Some(index) => {
+                    if !written[index] {
+                        written[index] = true;
+                        attributes.extend(encoded[index].1.as_ref());
+                    }
+                }
+                None => attributes.push(attribute),
+            }
+        }
+        for (index, (_, attribute)) in encoded.iter().enumerate() {
+            if !written[index] {
+                attributes.extend(attribute.as_ref());
+            }
+        }
+        attributes
+    }
+
+    fn write_attributes<'b>(
+        &mut self,
+        buffer: &mut BufferWriter,
+        attributes: impl ExactSizeIterator<Item = &'b Attribute>,
+    ) -> ClassFileParserResult<()> {
+        buffer.write_u16(Self::length_u16(attributes.len(), "attributes")?);
+        for attribute in attributes {
+            let name_index = self.utf8_index(&attribute.name)?;
+            buffer.write_u16(name_index);
+            let len = u32::try_from(attribute.bytes.len())
+                .map_err(|_| Self::too_long(&attribute.name, attribute.bytes.len()))?;
+            buffer.write_u32(len);
+            buffer.write_bytes(&attribute.bytes);
+        }
+        Ok(())
+    }
+
+    fn deprecated_attribute(deprecated: bool) -> Option<Attribute> {
+        deprecated.then(|| Attribute {
+            name: "Deprecated".to_string(),
+            bytes: Vec::new(),
+        })
+    }
+
+    /// 内容只有一个常量池索引的属性, 例如 SourceFile 与 ConstantValue
+    fn u16_attribute(name: &str, index: u16) -> Attribute {
+        Attribute {
+            name: name.to_string(),
+            bytes: index.to_be_bytes().to_vec(),
+        }
+    }
+
+    fn utf8_index(&mut self, text: &str) -> ClassFileParserResult<u16> {
+        if let Some(index) = self.utf8_indexes.get(text) {
+            return Ok(*index);
+        }
+        let index = self.add_constant(ConstantPoolEntry::Utf8(text.to_string()))?;
+        self.utf8_indexes.insert(text.to_string(), index);
+        Ok(index)
+    }
+
+    fn class_index(&mut self, class_name: &str) -> ClassFileParserResult<u16> {
+        if let Some(index) = self.class_indexes.get(class_name) {
+            return Ok(*index);
+        }
+        let name_index = self.utf8_index(class_name)?;
+        let index = self.add_constant(ConstantPoolEntry::ClassReference(name_index))?;
+        self.class_indexes.insert(class_name.to_string(), index);
+        Ok(index)
+    }
+
+    fn constant_value_index(&mut self, constant_value: &FieldConstantValue) -> ClassFileParserResult<u16> {
+        let entry = match constant_value {
+            FieldConstantValue::Int(value) => ConstantPoolEntry::Integer(*value),
+            FieldConstantValue::Float(value) => ConstantPoolEntry::Float(*value),
+            FieldConstantValue::Long(value) => ConstantPoolEntry::Long(*value),
+            FieldConstantValue::Double(value) => ConstantPoolEntry::Double(*value),
+            FieldConstantValue::String(value) => ConstantPoolEntry::StringReference(self.utf8_index(value)?),
+        };
+        self.find_or_add_constant(entry)
+    }
+
+    /// 查找内容相同的常量, 找不到时追加到常量池的末尾
+    fn find_or_add_constant(&mut self, entry: ConstantPoolEntry) -> ClassFileParserResult<u16> {
+        let existing = self
+            .constants
+            .iter()
+            .find(|(_, candidate)| Self::is_same_constant(candidate, &entry))
+            .map(|(index, _)| index);
+        match existing {
+            Some(index) => Ok(index),
+            None => self.add_constant(entry),
+        }
+    }
+
+    /// 浮点数按照位进行比较, 这样 NaN 以及 -0.0 也能找到对应的常量
+    fn is_same_constant(candidate: &ConstantPoolEntry, entry: &ConstantPoolEntry) -> bool {
+        match (candidate, entry) {
+            (ConstantPoolEntry::Float(a), ConstantPoolEntry::Float(b)) => a.to_bits() == b.to_bits(),
+            (ConstantPoolEntry::Double(a), ConstantPoolEntry::Double(b)) => a.to_bits() == b.to_bits(),
+            _ => candidate == entry,
+        }
+    }
+
+    /// 追加一个常量, 返回它的索引
+    fn add_constant(&mut self, entry: ConstantPoolEntry) -> ClassFileParserResult<u16> {
+        let slots = match entry {
+            ConstantPoolEntry::Long(_) | ConstantPoolEntry::Double(_) => 2,
+            _ => 1,
+        };
+        // constant_pool_count 是 u16, 并且等于 slot 数量加一
+        if self.constants.len() + slots >= u16::MAX as usize {
+            return Err(ClassFileParserError::InvalidClassData {
+                name: "the constant pool is full".to_string(),
+                is_invalidate_constant_pool_idx: true,
+            });
+        }
+        let index = (self.constants.len() + 1) as u16;
+        self.constants.add_entry(entry);
+        Ok(index)
+    }
+
+    fn length_u16(len: usize, what: &str) -> ClassFileParserResult<u16> {
+        u16::try_from(len).map_err(|_| Self::too_long(what, len))
+    }
+
+    fn too_long(what: &str, len: usize) -> ClassFileParserError {
+        ClassFileParserError::InvalidClassData {
+            name: format!("too many {what} to be written: {len}"),
+            is_invalidate_constant_pool_idx: false,
+        }
+    }
+}
+
+/// 字段与重新读取的原始属性不一致时返回错误, 说明字段被修改过, 但是写回时只会使用原始属性
+fn check_unchanged<T: PartialEq>(owner: &str, what: &str, typed: &T, written: &T) -> ClassFileParserResult<()> {
+    if typed == written {
+        Ok(())
+    } else {
+        Err(ClassFileParserError::InvalidClassData {
+            name: format!("{what} of {owner} was modified, but it can only be written from its raw attribute"),
+            is_invalidate_constant_pool_idx: false,
+        })
+    }
+}
+
+impl ConstantPoolBuilder for ClassFileWriter<'_> {
+    fn utf8_index(&mut self, text: &str) -> ClassFileParserResult<u16> {
+        ClassFileWriter::utf8_index(self, text)
+    }
+
+    fn class_index(&mut self, class_name: &str) -> ClassFileParserResult<u16> {
+        ClassFileWriter::class_index(self, class_name)
+    }
+
+    fn constant_index(&mut self, entry: ConstantPoolEntry) -> ClassFileParserResult<u16> {
+        match entry {
+            ConstantPoolEntry::Utf8(text) => ClassFileWriter::utf8_index(self, &text),
+            entry => self.find_or_add_constant(entry),
+        }
+    }
+}
@@ -1,12 +1,11 @@
 use std::fmt;
-use std::fmt::{Debug, Display, Formatter, write};
-use log::debug;
+use std::fmt::{Debug, Formatter};
 use crate::constant_pool::constant_pool::ConstantPoolSlot::{Entry, PhantomEntry};
 use crate::error::{ClassFileParserError, ClassFileParserResult};
 
-/// 常量池的类型，目前支持了 11 个，参考文档:
-/// https://docs.oracle.com/javase/specs/jvms/se7/html/jvms-4.html#jvms-4.4
-#[derive(Debug, PartialEq)]
+/// 常量池的类型，目前支持了 17 个，参考文档:
+/// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.4
+#[derive(Debug, Clone, PartialEq)]
 pub enum ConstantPoolEntry {
     Utf8(String),
     Integer(i32),
@@ -19,12 +18,79 @@ pub enum ConstantPoolEntry {
     MethodReference(u16, u16),
     InterfaceMethodReference(u16, u16),
     NameAndTypeDescriptor(u16, u16),
+    /// Java 7 引入, 指向一个字段或方法引用, 以及对应的引用类型
+    MethodHandle(ReferenceKind, u16),
+    /// Java 7 引入, 指向一个方法描述符
+    MethodType(u16),
+    /// Java 11 引入, (bootstrap_method_attr_index, name_and_type_index)
+    Dynamic(u16, u16),
+    /// Java 7 引入, (bootstrap_method_attr_index, name_and_type_index)
+    InvokeDynamic(u16, u16),
+    /// Java 9 引入, 仅出现在 module-info 中, 指向模块名
+    ModuleReference(u16),
+    /// Java 9 引入, 仅出现在 module-info 中, 指向包名
+    PackageReference(u16),
 }
 
+/// MethodHandle 的引用类型, 决定了句柄的字节码行为, 参考文档:
+/// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-5.html#jvms-5.4.3.5
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceKind {
+    GetField = 1,
+    GetStatic = 2,
+    PutField = 3,
+    PutStatic = 4,
+    InvokeVirtual = 5,
+    InvokeStatic = 6,
+    InvokeSpecial = 7,
+    NewInvokeSpecial = 8,
+    InvokeInterface = 9,
+}
+
+impl ReferenceKind {
+    pub fn from_u8(kind: u8) -> ClassFileParserResult<Self> {
+        match kind {
+            1 => Ok(ReferenceKind::GetField),
+            2 => Ok(ReferenceKind::GetStatic),
+            3 => Ok(ReferenceKind::PutField),
+            4 => Ok(ReferenceKind::PutStatic),
+            5 => Ok(ReferenceKind::InvokeVirtual),
+            6 => Ok(ReferenceKind::InvokeStatic),
+            7 => Ok(ReferenceKind::InvokeSpecial),
+            8 => Ok(ReferenceKind::NewInvokeSpecial),
+            9 => Ok(ReferenceKind::InvokeInterface),
+            _ => Err(ClassFileParserError::InvalidClassData {
+                name: format!("invalid method handle reference kind: {kind}"),
+                is_invalidate_constant_pool_idx: false,
+            }),
+        }
+    }
+}
+
+impl fmt::Display for ReferenceKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ReferenceKind::GetField => "REF_getField",
+            ReferenceKind::GetStatic => "REF_getStatic",
+            ReferenceKind::PutField => "REF_putField",
+            ReferenceKind::PutStatic => "REF_putStatic",
+            ReferenceKind::InvokeVirtual => "REF_invokeVirtual",
+            ReferenceKind::InvokeStatic => "REF_invokeStatic",
+            ReferenceKind::InvokeSpecial => "REF_invokeSpecial",
+            ReferenceKind::NewInvokeSpecial => "REF_newInvokeSpecial",
+            ReferenceKind::InvokeInterface => "REF_invokeInterface",
+        };
+        f.write_str(name)
+    }
+}
+
+
+/// 输出常量时最多沿着引用解析的层数
+const MAX_REFERENCE_DEPTH: usize = 3;
 
 /// 常量池, 需要注意的是, 常量池的索引从 1 开始, 而不是 0!
 /// 常量池的极限大小是两个字节, u16
-#[derive(Default)]
+#[derive(Default, Clone)]
 pub struct ConstantPool {
     entries: Vec<ConstantPoolSlot>,
 }
@@ -33,7 +99,7 @@ pub struct ConstantPool {
 /// the second one, so we have a tombstone to ensure the indexes match.
 /// 这里参考了 Andrew 的处理策略, 使用另一个值来表示空的一个常量位, 确保 long 和 double 的第二位对应
 /// 但是实际上在 Rust 结构体中存储的数据是仅存储在第一位上的
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 enum ConstantPoolSlot {
     Entry(ConstantPoolEntry),
     // 幽灵 entry，意味着这个 entry 被使用但是实际上不可访问, 也即事实上是持有了这个 entry 的
@@ -55,6 +121,26 @@ impl ConstantPool {
         }
     }
 
+    /// 常量池占据的 slot 数量, long 和 double 占据两个 slot, 写入 class 文件时需要加一
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 按照索引顺序遍历所有 entry, 跳过 long 和 double 的第二个 slot
+    pub fn iter(&self) -> impl Iterator<Item = (u16, &ConstantPoolEntry)> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter_map(|(raw_idx, slot)| match slot {
+                Entry(entry) => Some(((raw_idx + 1) as u16, entry)),
+                PhantomEntry() => None,
+            })
+    }
+
     /// 获取一个 entry, 注意, JVM 规定常量池的索引从 1 开始, 而不是 0
     pub fn get_entry(&self, index: u16) -> ClassFileParserResult<&ConstantPoolEntry> {
         if index == 0 || index as usize > self.entries.len()  {
@@ -69,7 +155,14 @@ impl ConstantPool {
     }
 
     /// 组织 entry, 从中可以看到几个 reference 数组的含义, 本质上还是指向了常量池。
-    fn fmt_entry(&self, idx: u16) -> ClassFileParserResult<String> {
+    /// 合法的常量池中引用链最长是 MethodHandle -> Methodref -> Class -> Utf8, depth 避免了循环引用导致的无限递归
+    fn fmt_entry(&self, idx: u16, depth: usize) -> ClassFileParserResult<String> {
+        if depth > MAX_REFERENCE_DEPTH {
+            return Err(ClassFileParserError::InvalidClassData {
+                name: format!("the constant at index {idx} is nested too deeply"),
+                is_invalidate_constant_pool_idx: true,
+            });
+        }
         let entry = self.get_entry(idx)?;
         let text = match entry {
             ConstantPoolEntry::Utf8(ref s) => format!("String: \"{s}\""),
@@ -78,18 +171,18 @@ impl ConstantPool {
             ConstantPoolEntry::Long(n) => format!("Long: {n}"),
             ConstantPoolEntry::Double(n) => format!("Double: {n}"),
             ConstantPoolEntry::ClassReference(n) => {
-                format!("ClassReference: {} => ({})", n, self.fmt_entry(*n)?)
+                format!("ClassReference: {} => ({})", n, self.fmt_entry(*n, depth + 1)?)
             }
             ConstantPoolEntry::StringReference(n) => {
-                format!("StringReference: {} => ({})", n, self.fmt_entry(*n)?)
+                format!("StringReference: {} => ({})", n, self.fmt_entry(*n, depth + 1)?)
             }
             ConstantPoolEntry::FieldReference(i, j) => {
                 format!(
                     "FieldReference: {}, {} => ({}), ({})",
                     i,
                     j,
-                    self.fmt_entry(*i)?,
-                    self.fmt_entry(*j)?
+                    self.fmt_entry(*i, depth + 1)?,
+                    self.fmt_entry(*j, depth + 1)?
                 )
             }
             ConstantPoolEntry::MethodReference(i, j) => {
@@ -97,8 +190,8 @@ impl ConstantPool {
                     "MethodReference: {}, {} => ({}), ({})",
                     i,
                     j,
-                    self.fmt_entry(*i)?,
-                    self.fmt_entry(*j)?
+                    self.fmt_entry(*i, depth + 1)?,
+                    self.fmt_entry(*j, depth + 1)?
                 )
             }
             ConstantPoolEntry::InterfaceMethodReference(i, j) => {
@@ -106,8 +199,8 @@ impl ConstantPool {
                     "InterfaceMethodReference: {}, {} => ({}), ({})",
                     i,
                     j,
-                    self.fmt_entry(*i)?,
-                    self.fmt_entry(*j)?
+                    self.fmt_entry(*i, depth + 1)?,
+                    self.fmt_entry(*j, depth + 1)?
                 )
             }
             &ConstantPoolEntry::NameAndTypeDescriptor(i, j) => {
@@ -115,14 +208,33 @@ impl ConstantPool {
                     "NameAndTypeDescriptor: {}, {} => ({}), ({})",
                     i,
                     j,
-                    self.fmt_entry(i)?,
-                    self.fmt_entry(j)?
+                    self.fmt_entry(i, depth + 1)?,
+                    self.fmt_entry(j, depth + 1)?
                 )
             }
+            ConstantPoolEntry::MethodHandle(kind, n) => {
+                format!("MethodHandle: {}, {} => ({})", kind, n, self.fmt_entry(*n, depth + 1)?)
+            }
+            ConstantPoolEntry::MethodType(n) => {
+                format!("MethodType: {} => ({})", n, self.fmt_entry(*n, depth + 1)?)
+            }
+            ConstantPoolEntry::Dynamic(i, j) => {
+                format!("Dynamic: #{}, {} => ({})", i, j, self.fmt_entry(*j, depth + 1)?)
+            }
+            ConstantPoolEntry::InvokeDynamic(i, j) => {
+                format!("InvokeDynamic: #{}, {} => ({})", i, j, self.fmt_entry(*j, depth + 1)?)
+            }
+            ConstantPoolEntry::ModuleReference(n) => {
+                format!("ModuleReference: {} => ({})", n, self.fmt_entry(*n, depth + 1)?)
+            }
+            ConstantPoolEntry::PackageReference(n) => {
+                format!("PackageReference: {} => ({})", n, self.fmt_entry(*n, depth + 1)?)
+            }
         };
         Ok(text)
     }
 
+    /// 常量的文本形式, 只沿着 JVMS 允许的引用解析, 因此不合法的常量池(例如指向自己的 Class 常量)不会导致无限递归
     pub fn text_of(&self, idx: u16) -> ClassFileParserResult<String> {
         let entry = self.get_entry(idx)?;
         let text = match entry {
@@ -131,23 +243,106 @@ impl ConstantPool {
             ConstantPoolEntry::Float(n) => n.to_string(),
             ConstantPoolEntry::Long(n) => n.to_string(),
             ConstantPoolEntry::Double(n) => n.to_string(),
-            ConstantPoolEntry::ClassReference(n) => self.text_of(*n)?,
-            ConstantPoolEntry::StringReference(n) => self.text_of(*n)?,
-            ConstantPoolEntry::FieldReference(i, j) => {
-                format!("{}.{}", self.text_of(*i)?, self.text_of(*j)?)
+            ConstantPoolEntry::ClassReference(n)
+            | ConstantPoolEntry::StringReference(n)
+            | ConstantPoolEntry::MethodType(n)
+            | ConstantPoolEntry::ModuleReference(n)
+            | ConstantPoolEntry::PackageReference(n) => self.utf8_of(*n)?,
+            ConstantPoolEntry::FieldReference(..)
+            | ConstantPoolEntry::MethodReference(..)
+            | ConstantPoolEntry::InterfaceMethodReference(..) => {
+                let (class_name, name, type_descriptor) = self.member_reference_of(idx)?;
+                format!("{class_name}.{name}: {type_descriptor}")
             }
-            ConstantPoolEntry::MethodReference(i, j) => {
-                format!("{}.{}", self.text_of(*i)?, self.text_of(*j)?)
+            ConstantPoolEntry::NameAndTypeDescriptor(..) => {
+                let (name, type_descriptor) = self.name_and_type_of(idx)?;
+                format!("{name}: {type_descriptor}")
             }
-            ConstantPoolEntry::InterfaceMethodReference(i, j) => {
-                format!("{}.{}", self.text_of(*i)?, self.text_of(*j)?)
+            ConstantPoolEntry::MethodHandle(kind, n) => {
+                let (class_name, name, type_descriptor) = self.member_reference_of(*n)?;
+                format!("{kind} {class_name}.{name}: {type_descriptor}")
             }
-            ConstantPoolEntry::NameAndTypeDescriptor(i, j) => {
-                format!("{}: {}", self.text_of(*i)?, self.text_of(*j)?)
+            // bootstrap 方法位于 BootstrapMethods 属性中, 常量池中只能给出其下标
+            ConstantPoolEntry::Dynamic(i, j) | ConstantPoolEntry::InvokeDynamic(i, j) => {
+                let (name, type_descriptor) = self.name_and_type_of(*j)?;
+                format!("#{i}:{name}: {type_descriptor}")
             }
         };
         Ok(text)
     }
+
+    /// 名称、描述符等只能指向 Utf8 常量
+    fn utf8_of(&self, idx: u16) -> ClassFileParserResult<String> {
+        match self.get_entry(idx)? {
+            ConstantPoolEntry::Utf8(text) => Ok(text.clone()),
+            entry => Err(ClassFileParserError::InvalidClassData {
+                name: format!("expected a Utf8 constant at index {idx} but found {entry:?}"),
+                is_invalidate_constant_pool_idx: true,
+            }),
+        }
+    }
+
+    /// 解析 Class 常量, 返回类的内部名称, 例如 `java/lang/String`
+    pub fn class_name_of(&self, idx: u16) -> ClassFileParserResult<String> {
+        match self.get_entry(idx)? {
+            ConstantPoolEntry::ClassReference(name_idx) => self.utf8_of(*name_idx),
+            entry => Err(ClassFileParserError::InvalidClassData {
+                name: format!("expected a Class constant at index {idx} but found {entry:?}"),
+                is_invalidate_constant_pool_idx: true,
+            }),
+        }
+    }
+
+    /// 解析 Module 常量, 返回模块名称, 例如 `java.base`
+    pub fn module_name_of(&self, idx: u16) -> ClassFileParserResult<String> {
+        match self.get_entry(idx)? {
+            ConstantPoolEntry::ModuleReference(name_idx) => self.utf8_of(*name_idx),
+            entry => Err(ClassFileParserError::InvalidClassData {
+                name: format!("expected a Module constant at index {idx} but found {entry:?}"),
+                is_invalidate_constant_pool_idx: true,
+            }),
+        }
+    }
+
+    /// 解析 Package 常量, 返回包的内部名称, 例如 `java/util`
+    pub fn package_name_of(&self, idx: u16) -> ClassFileParserResult<String> {
+        match self.get_entry(idx)? {
+            ConstantPoolEntry::PackageReference(name_idx) => self.utf8_of(*name_idx),
+            entry => Err(ClassFileParserError::InvalidClassData {
+                name: format!("expected a Package constant at index {idx} but found {entry:?}"),
+                is_invalidate_constant_pool_idx: true,
+            }),
+        }
+    }
+
+    /// 解析 NameAndType 常量, 返回 (名称, 描述符)
+    pub fn name_and_type_of(&self, idx: u16) -> ClassFileParserResult<(String, String)> {
+        match self.get_entry(idx)? {
+            ConstantPoolEntry::NameAndTypeDescriptor(name_idx, type_idx) => {
+                Ok((self.utf8_of(*name_idx)?, self.utf8_of(*type_idx)?))
+            }
+            entry => Err(ClassFileParserError::InvalidClassData {
+                name: format!("expected a NameAndType constant at index {idx} but found {entry:?}"),
+                is_invalidate_constant_pool_idx: true,
+            }),
+        }
+    }
+
+    /// 解析字段、方法或者接口方法的引用, 返回 (类名, 名称, 描述符)
+    pub fn member_reference_of(&self, idx: u16) -> ClassFileParserResult<(String, String, String)> {
+        match self.get_entry(idx)? {
+            ConstantPoolEntry::FieldReference(class_idx, name_and_type_idx)
+            | ConstantPoolEntry::MethodReference(class_idx, name_and_type_idx)
+            | ConstantPoolEntry::InterfaceMethodReference(class_idx, name_and_type_idx) => {
+                let (name, type_descriptor) = self.name_and_type_of(*name_and_type_idx)?;
+                Ok((self.class_name_of(*class_idx)?, name, type_descriptor))
+            }
+            entry => Err(ClassFileParserError::InvalidClassData {
+                name: format!("expected a member reference at index {idx} but found {entry:?}"),
+                is_invalidate_constant_pool_idx: true,
+            }),
+        }
+    }
 }
 
 
@@ -157,14 +352,17 @@ impl Debug for ConstantPool {
         for (raw_idx, _) in self.entries.iter().enumerate() {
             let index = (raw_idx + 1) as u16;
             let entry_text = self
-                .fmt_entry(index);
+                .fmt_entry(index, 0);
             match entry_text {
                 Ok(str) => {
                     writeln!(f, "    {}, {}", index, str)?;
                 }
-                Err(_) => {
+                Err(ClassFileParserError::ConstantPoolIndexToPhantomEntryError(_)) => {
                     writeln!(f, "    {}, ------ PhantomEntry ------", index)?;
                 }
+                Err(err) => {
+                    writeln!(f, "    {}, ------ {} ------", index, err)?;
+                }
             }
         }
         Ok(())
@@ -1,3 +1,4 @@
-pub(crate) mod constant_pool;
+#[allow(clippy::module_inception)]
+pub mod constant_pool;
 #[cfg(test)]
 mod test;
\ No newline at end of file
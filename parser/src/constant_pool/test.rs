@@ -1,5 +1,6 @@
 use log::info;
-use crate::constant_pool::constant_pool::{ConstantPool, ConstantPoolEntry};
+use crate::constant_pool::constant_pool::{ConstantPool, ConstantPoolEntry, ReferenceKind};
+use crate::error::ClassFileParserError;
 use crate::error::ClassFileParserError::ConstantPoolIndexToPhantomEntryError;
 use crate::log::{init_log, LogLevel};
 
@@ -72,4 +73,70 @@ fn test_constant_pool() {
         ConstantPoolEntry::NameAndTypeDescriptor(1, 10),
         *cp.get_entry(14).unwrap()
     );
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_dynamic_constants() {
+    let mut cp = ConstantPool::default();
+    cp.add_entry(ConstantPoolEntry::Utf8("java/lang/invoke/LambdaMetafactory".to_string()));
+    cp.add_entry(ConstantPoolEntry::ClassReference(1));
+    cp.add_entry(ConstantPoolEntry::Utf8("metafactory".to_string()));
+    cp.add_entry(ConstantPoolEntry::Utf8("()V".to_string()));
+    cp.add_entry(ConstantPoolEntry::NameAndTypeDescriptor(3, 4));
+    cp.add_entry(ConstantPoolEntry::MethodReference(2, 5));
+    cp.add_entry(ConstantPoolEntry::MethodHandle(ReferenceKind::InvokeStatic, 6));
+    cp.add_entry(ConstantPoolEntry::MethodType(4));
+    cp.add_entry(ConstantPoolEntry::InvokeDynamic(0, 5));
+    cp.add_entry(ConstantPoolEntry::Dynamic(1, 5));
+    cp.add_entry(ConstantPoolEntry::Utf8("java.base".to_string()));
+    cp.add_entry(ConstantPoolEntry::ModuleReference(11));
+    cp.add_entry(ConstantPoolEntry::Utf8("java/lang".to_string()));
+    cp.add_entry(ConstantPoolEntry::PackageReference(13));
+
+    info!("{:?}", cp);
+    assert_eq!(
+        Ok("REF_invokeStatic java/lang/invoke/LambdaMetafactory.metafactory: ()V".to_string()),
+        cp.text_of(7)
+    );
+    assert_eq!(Ok("()V".to_string()), cp.text_of(8));
+    assert_eq!(Ok("#0:metafactory: ()V".to_string()), cp.text_of(9));
+    assert_eq!(Ok("#1:metafactory: ()V".to_string()), cp.text_of(10));
+    assert_eq!(Ok("java.base".to_string()), cp.text_of(12));
+    assert_eq!(Ok("java/lang".to_string()), cp.text_of(14));
+    assert_eq!(Ok("java.base".to_string()), cp.module_name_of(12));
+    assert_eq!(Ok("java/lang".to_string()), cp.package_name_of(14));
+    assert!(cp.module_name_of(14).is_err());
+    assert!(format!("{:?}", cp).contains("MethodHandle: REF_invokeStatic, 6"));
+}
+
+#[test]
+fn test_self_referencing_constants() {
+    let mut cp = ConstantPool::default();
+    cp.add_entry(ConstantPoolEntry::ClassReference(1));
+    cp.add_entry(ConstantPoolEntry::Utf8("run".to_string()));
+    cp.add_entry(ConstantPoolEntry::NameAndTypeDescriptor(2, 2));
+    cp.add_entry(ConstantPoolEntry::MethodReference(1, 3));
+    cp.add_entry(ConstantPoolEntry::MethodHandle(ReferenceKind::InvokeStatic, 5));
+
+    let expected = |index: u16, entry: &str| {
+        Err(ClassFileParserError::InvalidClassData {
+            name: format!("expected a Utf8 constant at index {index} but found {entry}"),
+            is_invalidate_constant_pool_idx: true,
+        })
+    };
+    // 只沿着 JVMS 允许的引用解析, 指向自己的常量返回错误而不是无限递归
+    assert_eq!(expected(1, "ClassReference(1)"), cp.text_of(1));
+    assert_eq!(expected(1, "ClassReference(1)"), cp.class_name_of(1));
+    assert_eq!(expected(1, "ClassReference(1)"), cp.text_of(4));
+    assert!(cp.text_of(5).is_err());
+    assert!(format!("{:?}", cp).contains("is nested too deeply"));
+}
+
+#[test]
+fn test_reference_kind() {
+    assert_eq!(Ok(ReferenceKind::GetField), ReferenceKind::from_u8(1));
+    assert_eq!(Ok(ReferenceKind::InvokeInterface), ReferenceKind::from_u8(9));
+    assert!(ReferenceKind::from_u8(0).is_err());
+    assert!(ReferenceKind::from_u8(10).is_err());
+    assert_eq!("REF_newInvokeSpecial", ReferenceKind::NewInvokeSpecial.to_string());
+}
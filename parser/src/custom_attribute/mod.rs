@@ -0,0 +1,207 @@
+#[cfg(test)]
+mod test;
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::fmt;
+use std::fmt::Formatter;
+
+use crate::constant_pool::constant_pool::{ConstantPool, ConstantPoolEntry};
+use crate::error::ClassFileParserResult;
+use crate::utils::attribute::{locate, Attribute, LocatedAttributes};
+
+/// 自定义属性, 例如编译器生成的厂商私有属性。
+/// 在 [AttributeRegistry] 中注册之后, 读取 class 文件时会被解码, 写回时会被重新编码
+/// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.1
+pub trait CustomAttribute: fmt::Debug + PartialEq + Send + Sync + 'static {
+    /// 解码属性的内容, 不包括 attribute_name_index 与 attribute_length
+    fn decode(bytes: &[u8], constants: &ConstantPool) -> ClassFileParserResult<Self>
+    where
+        Self: Sized;
+
+    /// 重新编码属性的内容, 引用的常量通过 constants 获取或者追加到常量池中
+    fn encode(&self, constants: &mut dyn ConstantPoolBuilder) -> ClassFileParserResult<Vec<u8>>;
+}
+
+/// 写回 class 文件时自定义属性使用的常量池, 已有的常量会被复用, 没有的追加到常量池的末尾
+pub trait ConstantPoolBuilder {
+    fn utf8_index(&mut self, text: &str) -> ClassFileParserResult<u16>;
+
+    /// Class 常量, 类名是内部名称, 例如 `java/lang/String`
+    fn class_index(&mut self, class_name: &str) -> ClassFileParserResult<u16>;
+
+    /// 其他常量, 内容相同的常量只会有一个
+    fn constant_index(&mut self, entry: ConstantPoolEntry) -> ClassFileParserResult<u16>;
+}
+
+/// 类型擦除之后的 [CustomAttribute], 用于在同一个列表中保存不同类型的属性
+trait AnyAttribute: fmt::Debug + Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn equals(&self, other: &dyn AnyAttribute) -> bool;
+    fn encode(&self, constants: &mut dyn ConstantPoolBuilder) -> ClassFileParserResult<Vec<u8>>;
+}
+
+impl<T: CustomAttribute> AnyAttribute for T {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn equals(&self, other: &dyn AnyAttribute) -> bool {
+        other.as_any().downcast_ref::<T>().is_some_and(|other| self == other)
+    }
+
+    fn encode(&self, constants: &mut dyn ConstantPoolBuilder) -> ClassFileParserResult<Vec<u8>> {
+        CustomAttribute::encode(self, constants)
+    }
+}
+
+type Decoder = fn(&[u8], &ConstantPool) -> ClassFileParserResult<Box<dyn AnyAttribute>>;
+
+fn decode_boxed<T: CustomAttribute>(
+    bytes: &[u8],
+    constants: &ConstantPool,
+) -> ClassFileParserResult<Box<dyn AnyAttribute>> {
+    Ok(Box::new(T::decode(bytes, constants)?))
+}
+
+/// 属性名称到解码器的映射, 通过 [crate::read_buffer_with] 使用
+#[derive(Default)]
+pub struct AttributeRegistry {
+    decoders: HashMap<String, Decoder>,
+}
+
+impl AttributeRegistry {
+    pub fn new() -> AttributeRegistry {
+        Default::default()
+    }
+
+    /// 使用 T 解码名为 name 的属性, 同一个名称重复注册时使用最后一次的类型。
+    /// 名称与标准属性相同时, 仍然会解析标准属性, 写回时使用标准属性的编码
+    pub fn register<T: CustomAttribute>(&mut self, name: impl Into<String>) -> &mut AttributeRegistry {
+        self.decoders.insert(name.into(), decode_boxed::<T>);
+        self
+    }
+
+    pub fn is_registered(&self, name: &str) -> bool {
+        self.decoders.contains_key(name)
+    }
+
+    /// 解码所有已经注册的属性, 没有注册的属性保留在原始属性中
+    pub(crate) fn decode_all(
+        &self,
+        raw_attributes: &LocatedAttributes,
+        constants: &ConstantPool,
+    ) -> ClassFileParserResult<CustomAttributes> {
+        let mut custom_attributes = CustomAttributes::default();
+        for (attribute, offset) in raw_attributes.with_offsets() {
+            if let Some(decode) = self.decoders.get(&attribute.name) {
+                let value = decode(&attribute.bytes, constants).map_err(|err| locate(err, attribute, offset))?;
+                custom_attributes.entries.push((attribute.name.clone(), value));
+            }
+        }
+        Ok(custom_attributes)
+    }
+}
+
+/// 类、字段、方法或者 Code 上已经解码的自定义属性, 写回 class 文件时会替换同名的原始属性
+#[derive(Debug, Default)]
+pub struct CustomAttributes {
+    entries: Vec<(String, Box<dyn AnyAttribute>)>,
+}
+
+impl CustomAttributes {
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.iter().any(|(entry_name, _)| entry_name == name)
+    }
+
+    /// 属性的名称, 按照在 class 文件中出现的顺序
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.entries.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// 名为 name 的属性, 属性不存在或者不是类型 T 时为 None
+    pub fn get<T: CustomAttribute>(&self, name: &str) -> Option<&T> {
+        self.entries
+            .iter()
+            .find(|(entry_name, _)| entry_name == name)
+            .and_then(|(_, value)| value.as_any().downcast_ref())
+    }
+
+    pub fn get_mut<T: CustomAttribute>(&mut self, name: &str) -> Option<&mut T> {
+        self.entries
+            .iter_mut()
+            .find(|(entry_name, _)| entry_name == name)
+            .and_then(|(_, value)| value.as_any_mut().downcast_mut())
+    }
+
+    /// 添加或者替换名为 name 的属性
+    pub fn insert<T: CustomAttribute>(&mut self, name: impl Into<String>, value: T) {
+        let name = name.into();
+        match self.entries.iter_mut().find(|(entry_name, _)| *entry_name == name) {
+            Some((_, existing)) => *existing = Box::new(value),
+            None => self.entries.push((name, Box::new(value))),
+        }
+    }
+
+    /// 删除名为 name 的属性, 原始属性中的同名属性仍然会原样写回
+    pub fn remove(&mut self, name: &str) -> bool {
+        let len = self.entries.len();
+        self.entries.retain(|(entry_name, _)| entry_name != name);
+        self.entries.len() != len
+    }
+
+    /// 编码名称不在 skipped 中的属性, 返回 (名称, 属性)
+    pub(crate) fn encode_all(
+        &self,
+        constants: &mut dyn ConstantPoolBuilder,
+        skipped: &[&str],
+    ) -> ClassFileParserResult<Vec<(&str, Attribute)>> {
+        self.entries
+            .iter()
+            .filter(|(name, _)| !skipped.contains(&name.as_str()))
+            .map(|(name, value)| {
+                let attribute = Attribute {
+                    name: name.clone(),
+                    bytes: value.encode(constants)?,
+                };
+                Ok((name.as_str(), attribute))
+            })
+            .collect()
+    }
+}
+
+impl PartialEq for CustomAttributes {
+    fn eq(&self, other: &Self) -> bool {
+        self.entries.len() == other.entries.len()
+            && self
+                .entries
+                .iter()
+                .zip(other.entries.iter())
+                .all(|((name, value), (other_name, other_value))| {
+                    name == other_name && value.equals(other_value.as_ref())
+                })
+    }
+}
+
+impl fmt::Display for CustomAttributes {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut map = f.debug_map();
+        for (name, value) in self.entries.iter() {
+            map.entry(name, value);
+        }
+        map.finish()
+    }
+}
@@ -0,0 +1,144 @@
+use crate::constant_pool::constant_pool::{ConstantPool, ConstantPoolEntry};
+use crate::custom_attribute::{AttributeRegistry, ConstantPoolBuilder, CustomAttribute};
+use crate::error::{ClassFileParserError, ClassFileParserResult};
+use crate::error::location::PathSegment;
+use crate::utils::attribute::{Attribute, LocatedAttributes};
+use crate::utils::buffer::{Buffer, BufferWriter};
+
+/// 编译器写入的私有属性: 一个 u16 的版本号以及指向 Utf8 常量的编译器名称
+#[derive(Debug, PartialEq)]
+struct CompilerInfo {
+    version: u16,
+    compiler: String,
+}
+
+impl CustomAttribute for CompilerInfo {
+    fn decode(bytes: &[u8], constants: &ConstantPool) -> ClassFileParserResult<Self> {
+        let mut buf = Buffer::new(bytes);
+        let version = buf.read_u16()?;
+        let compiler = constants.text_of(buf.read_u16()?)?;
+        Ok(CompilerInfo { version, compiler })
+    }
+
+    fn encode(&self, constants: &mut dyn ConstantPoolBuilder) -> ClassFileParserResult<Vec<u8>> {
+        let mut buffer = BufferWriter::new();
+        buffer.write_u16(self.version);
+        buffer.write_u16(constants.utf8_index(&self.compiler)?);
+        Ok(buffer.into_bytes())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Marker;
+
+impl CustomAttribute for Marker {
+    fn decode(_bytes: &[u8], _constants: &ConstantPool) -> ClassFileParserResult<Self> {
+        Ok(Marker)
+    }
+
+    fn encode(&self, _constants: &mut dyn ConstantPoolBuilder) -> ClassFileParserResult<Vec<u8>> {
+        Ok(Vec::new())
+    }
+}
+
+/// 只会追加常量的常量池
+impl ConstantPoolBuilder for ConstantPool {
+    fn utf8_index(&mut self, text: &str) -> ClassFileParserResult<u16> {
+        self.constant_index(ConstantPoolEntry::Utf8(text.to_string()))
+    }
+
+    fn class_index(&mut self, class_name: &str) -> ClassFileParserResult<u16> {
+        let name_index = self.utf8_index(class_name)?;
+        self.constant_index(ConstantPoolEntry::ClassReference(name_index))
+    }
+
+    fn constant_index(&mut self, entry: ConstantPoolEntry) -> ClassFileParserResult<u16> {
+        self.add_entry(entry);
+        Ok(self.len() as u16)
+    }
+}
+
+fn attribute(name: &str, bytes: &[u8]) -> Attribute {
+    Attribute {
+        name: name.to_string(),
+        bytes: bytes.to_vec(),
+    }
+}
+
+/// 每个属性的内容从 0x100 * (index + 1) 开始
+fn located(attributes: Vec<Attribute>) -> LocatedAttributes {
+    let mut located = LocatedAttributes::default();
+    for (index, attribute) in attributes.into_iter().enumerate() {
+        located.push(attribute, 0x100 * (index + 1));
+    }
+    located
+}
+
+#[test]
+fn can_decode_registered_attributes() {
+    let mut constants = ConstantPool::default();
+    constants.add_entry(ConstantPoolEntry::Utf8("acme-javac".to_string()));
+
+    let mut registry = AttributeRegistry::new();
+    registry.register::<CompilerInfo>("AcmeCompiler").register::<Marker>("AcmeMarker");
+    assert!(registry.is_registered("AcmeMarker"));
+    assert!(!registry.is_registered("SourceFile"));
+
+    let raw_attributes = located(vec![
+        attribute("SourceFile", &[0, 1]),
+        attribute("AcmeMarker", &[]),
+        attribute("AcmeCompiler", &[0, 3, 0, 1]),
+    ]);
+    let custom_attributes = registry.decode_all(&raw_attributes, &constants).unwrap();
+    assert_eq!(vec!["AcmeMarker", "AcmeCompiler"], custom_attributes.names().collect::<Vec<_>>());
+    assert_eq!(
+        Some(&CompilerInfo {
+            version: 3,
+            compiler: "acme-javac".to_string(),
+        }),
+        custom_attributes.get::<CompilerInfo>("AcmeCompiler")
+    );
+    // 类型不匹配
+    assert_eq!(None, custom_attributes.get::<Marker>("AcmeCompiler"));
+    assert!(!custom_attributes.contains("SourceFile"));
+
+    let error = registry.decode_all(&located(vec![attribute("AcmeCompiler", &[0, 3])]), &constants);
+    let segment = PathSegment::Attribute("AcmeCompiler".to_string());
+    assert_eq!(
+        Err(ClassFileParserError::UnexpectedEndOfData.locate(0x100, segment)),
+        error.map(|_| ())
+    );
+}
+
+#[test]
+fn can_modify_and_encode_attributes() {
+    let mut constants = ConstantPool::default();
+    constants.add_entry(ConstantPoolEntry::Utf8("acme-javac".to_string()));
+    let mut registry = AttributeRegistry::new();
+    registry.register::<CompilerInfo>("AcmeCompiler");
+    let original = registry
+        .decode_all(&located(vec![attribute("AcmeCompiler", &[0, 3, 0, 1])]), &constants)
+        .unwrap();
+    let mut custom_attributes = registry
+        .decode_all(&located(vec![attribute("AcmeCompiler", &[0, 3, 0, 1])]), &constants)
+        .unwrap();
+    assert_eq!(original, custom_attributes);
+
+    custom_attributes.get_mut::<CompilerInfo>("AcmeCompiler").unwrap().version = 4;
+    assert_ne!(original, custom_attributes);
+    custom_attributes.insert("AcmeMarker", Marker);
+    assert_eq!(2, custom_attributes.len());
+    assert_eq!(
+        "{\"AcmeCompiler\": CompilerInfo { version: 4, compiler: \"acme-javac\" }, \"AcmeMarker\": Marker}",
+        custom_attributes.to_string()
+    );
+
+    // 与标准属性同名的属性不会被编码, 新的常量追加到常量池的末尾
+    let encoded = custom_attributes.encode_all(&mut constants, &["AcmeMarker"]).unwrap();
+    assert_eq!(vec![("AcmeCompiler", attribute("AcmeCompiler", &[0, 4, 0, 2]))], encoded);
+    assert_eq!(2, constants.len());
+
+    assert!(custom_attributes.remove("AcmeMarker"));
+    assert!(!custom_attributes.remove("AcmeMarker"));
+    assert_eq!(1, custom_attributes.len());
+}
@@ -0,0 +1,110 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::fmt::Formatter;
+
+use crate::error::location::PathSegment;
+use crate::error::{ClassFileParserError, ClassFileParserResult};
+
+/// 宽松模式读取时发现的问题, error 带有出错的位置
+#[derive(Debug, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub error: ClassFileParserError,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// 接受了不合法的数据或者跳过了无法解码的属性, 原始属性仍然保留, 例如未知的 flag bits
+    Warning,
+    /// 丢失了数据, 例如跳过了整个方法, 或者数据提前结束只读取了一部分
+    Error,
+}
+
+/// 读取过程中的错误处理: 严格模式下直接返回错误, 宽松模式下记录诊断信息并继续读取
+#[derive(Debug, Default)]
+pub(crate) struct Recovery {
+    /// 宽松模式下收集的诊断信息, 严格模式下为 None
+    diagnostics: Option<RefCell<Vec<Diagnostic>>>,
+    /// 正在读取的结构的路径, 被恢复的错误没有经过外层的结构, 需要用它补全路径
+    context: RefCell<Vec<PathSegment>>,
+}
+
+impl Recovery {
+    pub(crate) fn strict() -> Recovery {
+        Recovery::default()
+    }
+
+    pub(crate) fn lenient() -> Recovery {
+        Recovery {
+            diagnostics: Some(RefCell::default()),
+            context: RefCell::default(),
+        }
+    }
+
+    pub(crate) fn is_lenient(&self) -> bool {
+        self.diagnostics.is_some()
+    }
+
+    /// 开始读取 segment 对应的结构, 与 [Recovery::leave] 成对调用
+    pub(crate) fn enter(&self, segment: PathSegment) {
+        self.context.borrow_mut().push(segment);
+    }
+
+    pub(crate) fn leave(&self) {
+        self.context.borrow_mut().pop();
+    }
+
+    /// 严格模式下返回 err; 宽松模式下记录 err 并返回 Ok, 调用者使用默认值继续读取。
+    /// 没有位置的 err 使用 offset 以及当前正在读取的结构作为位置
+    pub(crate) fn report(
+        &self,
+        severity: Severity,
+        err: ClassFileParserError,
+        offset: usize,
+    ) -> ClassFileParserResult<()> {
+        let Some(diagnostics) = &self.diagnostics else {
+            return Err(err);
+        };
+        let mut context = self.context.borrow().clone();
+        let mut error = match (err.offset(), context.pop()) {
+            (None, Some(segment)) => err.locate(offset, segment),
+            (None, None) => err.locate(offset, PathSegment::Field("class")),
+            (Some(_), segment) => {
+                context.extend(segment);
+                err
+            }
+        };
+        for segment in context.into_iter().rev() {
+            error = error.locate(offset, segment);
+        }
+        diagnostics.borrow_mut().push(Diagnostic { severity, error });
+        Ok(())
+    }
+
+    /// 与 [Recovery::report] 相同, 宽松模式下出错时返回 None
+    pub(crate) fn recover<T>(
+        &self,
+        severity: Severity,
+        result: ClassFileParserResult<T>,
+        offset: usize,
+    ) -> ClassFileParserResult<Option<T>> {
+        match result {
+            Ok(value) => Ok(Some(value)),
+            Err(err) => self.report(severity, err, offset).map(|_| None),
+        }
+    }
+
+    pub(crate) fn into_diagnostics(self) -> Vec<Diagnostic> {
+        self.diagnostics.map(RefCell::into_inner).unwrap_or_default()
+    }
+}
+
+/// 与编译器的输出类似, 例如 `warning: invalid field flags: 0x8000 at offset 0x1a2 (fields[0].access_flags)`
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.severity {
+            Severity::Warning => write!(f, "warning: {}", self.error),
+            Severity::Error => write!(f, "error: {}", self.error),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use std::fmt::Write;
+
+/// 每一行输出的字节数
+const BYTES_PER_ROW: usize = 16;
+/// 行首偏移 `00000010` 与后面两个空格的宽度
+const OFFSET_WIDTH: usize = 10;
+
+/// 以 `hexdump -C` 的格式输出 offset 所在的行以及前后各一行, 并在 offset 处的字节下面画出 `^^`。
+/// offset 等于 data 的长度时, 即数据提前结束时, `^^` 指向最后一个字节之后的位置
+pub fn hex_excerpt(data: &[u8], offset: usize) -> String {
+    let offset = offset.min(data.len());
+    let row = offset / BYTES_PER_ROW;
+    let mut excerpt = String::new();
+    for row_index in row.saturating_sub(1)..=row + 1 {
+        let start = row_index * BYTES_PER_ROW;
+        if start >= data.len() && row_index != row {
+            break;
+        }
+        let bytes = &data[start..data.len().min(start + BYTES_PER_ROW)];
+        if !excerpt.is_empty() {
+            excerpt.push('\n');
+        }
+        write_row(&mut excerpt, start, bytes);
+        if row_index == row {
+            let column = offset - start;
+            excerpt.push('\n');
+            excerpt.push_str(&" ".repeat(column_of(column)));
+            excerpt.push_str("^^");
+        }
+    }
+    excerpt
+}
+
+/// 第 column 个字节在行中的位置, 第 8 个字节之前有一个额外的空格
+fn column_of(column: usize) -> usize {
+    OFFSET_WIDTH + column * 3 + usize::from(column >= BYTES_PER_ROW / 2)
+}
+
+fn write_row(excerpt: &mut String, start: usize, bytes: &[u8]) {
+    // 写入 String 不会失败
+    let _ = write!(excerpt, "{start:08x}  ");
+    for column in 0..BYTES_PER_ROW {
+        if column == BYTES_PER_ROW / 2 {
+            excerpt.push(' ');
+        }
+        match bytes.get(column) {
+            Some(byte) => {
+                let _ = write!(excerpt, "{byte:02x} ");
+            }
+            None => excerpt.push_str("   "),
+        }
+    }
+    excerpt.push_str(" |");
+    for byte in bytes {
+        excerpt.push(if byte.is_ascii_graphic() || *byte == b' ' { char::from(*byte) } else { '.' });
+    }
+    excerpt.push('|');
+}
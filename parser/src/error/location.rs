@@ -0,0 +1,101 @@
+use std::fmt;
+use std::fmt::Formatter;
+
+use crate::error::excerpt::hex_excerpt;
+use crate::error::ClassFileParserError;
+
+/// 出错的结构在 class 文件中的路径, 例如 `methods[3].attributes[Code].exception_table[1]`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ErrorPath(Vec<PathSegment>);
+
+/// 路径中的一段, 与 JVMS 中 ClassFile 结构的字段名称相同
+/// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.1
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    /// 结构中的一项, 例如 `magic`
+    Field(&'static str),
+    /// 表中的第 index 项, 例如 `methods[3]`, 常量池使用常量的索引
+    Item(&'static str, usize),
+    /// 名为 name 的属性, 例如 `attributes[Code]`
+    Attribute(String),
+}
+
+impl ErrorPath {
+    pub(crate) fn new(segments: Vec<PathSegment>) -> ErrorPath {
+        ErrorPath(segments)
+    }
+
+    pub fn segments(&self) -> &[PathSegment] {
+        &self.0
+    }
+}
+
+impl ClassFileParserError {
+    /// 记录错误发生的位置, 从内向外调用: 已经有位置的错误保留更精确的内层偏移, 只在路径的前面加上 segment
+    pub(crate) fn locate(self, offset: usize, segment: PathSegment) -> ClassFileParserError {
+        match self {
+            ClassFileParserError::Located {
+                offset,
+                mut path,
+                error,
+            } => {
+                path.0.insert(0, segment);
+                ClassFileParserError::Located { offset, path, error }
+            }
+            error => ClassFileParserError::Located {
+                offset,
+                path: ErrorPath(vec![segment]),
+                error: Box::new(error),
+            },
+        }
+    }
+
+    /// 出错的位置在输入中的字节偏移, 只有 [crate::read_buffer] 返回的错误才有
+    pub fn offset(&self) -> Option<usize> {
+        match self {
+            ClassFileParserError::Located { offset, .. } => Some(*offset),
+            _ => None,
+        }
+    }
+
+    pub fn path(&self) -> Option<&ErrorPath> {
+        match self {
+            ClassFileParserError::Located { path, .. } => Some(path),
+            _ => None,
+        }
+    }
+
+    /// 数据提前结束, 之后的结构都无法读取
+    pub fn is_truncated(&self) -> bool {
+        matches!(self.inner(), ClassFileParserError::UnexpectedEndOfData)
+    }
+
+    /// 去掉位置信息之后的错误, 例如 [ClassFileParserError::UnexpectedEndOfData]
+    pub fn inner(&self) -> &ClassFileParserError {
+        match self {
+            ClassFileParserError::Located { error, .. } => error.inner(),
+            error => error,
+        }
+    }
+
+    /// 输出出错位置附近的字节, data 是读取时的输入, 没有位置信息时为 None
+    pub fn hex_excerpt(&self, data: &[u8]) -> Option<String> {
+        self.offset().map(|offset| hex_excerpt(data, offset))
+    }
+}
+
+impl fmt::Display for ErrorPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (index, segment) in self.0.iter().enumerate() {
+            if index > 0 {
+                f.write_str(".")?;
+            }
+            match segment {
+                PathSegment::Field(name) => f.write_str(name)?,
+                PathSegment::Item(name, index) => write!(f, "{name}[{index}]")?,
+                PathSegment::Attribute(name) => write!(f, "attributes[{name}]")?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,5 +1,13 @@
 use thiserror::Error;
 
+use crate::error::location::ErrorPath;
+
+pub mod diagnostic;
+pub mod excerpt;
+pub mod location;
+#[cfg(test)]
+mod test;
+
 /// 解析文件 Result
 pub type ClassFileParserResult<T> = std::result::Result<T, ClassFileParserError>;
 
@@ -37,4 +45,38 @@ pub enum ClassFileParserError {
     UnsupportedVersion(u16, u16),
     #[error("Error while parsing a given type descriptor in the file={0}")]
     InvalidTypeDescriptor(String),
-}
\ No newline at end of file
+    #[error("invalid generic signature {signature:?} at position {position}: expected {expected}")]
+    InvalidSignature {
+        signature: String,
+        /// 出错的字节位置, 从 0 开始
+        position: usize,
+        /// 期望出现的内容, 例如 an identifier
+        expected: String,
+    },
+    #[error("invalid SMAP at line {line}: {message}")]
+    InvalidSourceMap {
+        /// 出错的行, 从 1 开始
+        line: usize,
+        message: String,
+    },
+    /// [crate::read_buffer] 返回的错误都带有位置, 使用 [ClassFileParserError::inner] 得到原始的错误
+    #[error("{error} at offset {offset:#x} ({path})")]
+    Located {
+        /// 出错的值在输入中的字节偏移, 属性内容中的错误没有更精确的位置时指向属性内容的开始
+        offset: usize,
+        path: ErrorPath,
+        error: Box<ClassFileParserError>,
+    },
+}
+
+/// 从 [std::io::Read]、文件或者内存映射读取 class 文件的 Result
+pub type ClassFileReadResult<T> = std::result::Result<T, ClassFileReadError>;
+
+/// 读取 class 文件时的错误, 区分无法读取输入与输入不是合法的 class 文件两种情况
+#[derive(Error, Debug)]
+pub enum ClassFileReadError {
+    #[error("cannot read class file: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("malformed class file: {0}")]
+    Format(#[from] ClassFileParserError),
+}
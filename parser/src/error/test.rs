@@ -0,0 +1,100 @@
+use crate::error::diagnostic::{Diagnostic, Recovery, Severity};
+use crate::error::excerpt::hex_excerpt;
+use crate::error::location::PathSegment;
+use crate::error::ClassFileParserError;
+
+#[test]
+fn can_locate_nested_errors() {
+    let err = ClassFileParserError::WrongConstantPoolIndexError(42)
+        .locate(0x1234, PathSegment::Item("exception_table", 1))
+        // 外层的偏移不会覆盖内层更精确的偏移
+        .locate(0x1200, PathSegment::Attribute("Code".to_string()))
+        .locate(0x1100, PathSegment::Item("methods", 3));
+
+    assert_eq!(Some(0x1234), err.offset());
+    assert_eq!(
+        "methods[3].attributes[Code].exception_table[1]",
+        err.path().unwrap().to_string()
+    );
+    assert_eq!(&ClassFileParserError::WrongConstantPoolIndexError(42), err.inner());
+    assert_eq!(
+        "The index=42 of constant pool is error! at offset 0x1234 (methods[3].attributes[Code].exception_table[1])",
+        err.to_string()
+    );
+}
+
+#[test]
+fn errors_without_location() {
+    let err = ClassFileParserError::UnexpectedEndOfData;
+    assert_eq!(None, err.offset());
+    assert_eq!(None, err.path());
+    assert_eq!(None, err.hex_excerpt(&[]));
+    assert_eq!(&err, err.inner());
+}
+
+#[test]
+fn can_render_hex_excerpt() {
+    let data: Vec<u8> = (0x30..0x58).collect();
+    assert_eq!(
+        "00000000  30 31 32 33 34 35 36 37  38 39 3a 3b 3c 3d 3e 3f  |0123456789:;<=>?|\n\
+         00000010  40 41 42 43 44 45 46 47  48 49 4a 4b 4c 4d 4e 4f  |@ABCDEFGHIJKLMNO|\n\
+         \x20                                        ^^\n\
+         00000020  50 51 52 53 54 55 56 57                           |PQRSTUVW|",
+        hex_excerpt(&data, 0x1a)
+    );
+}
+
+#[test]
+fn can_render_hex_excerpt_at_end_of_data() {
+    let data = [0xca, 0xfe, 0x00, 0x01];
+    assert_eq!(
+        "00000000  ca fe 00 01                                       |....|\n\
+         \x20                     ^^",
+        hex_excerpt(&data, data.len())
+    );
+    // 数据的长度正好是一行时, `^^` 在下一个空行中
+    let data = [0u8; 16];
+    assert_eq!(
+        "00000000  00 00 00 00 00 00 00 00  00 00 00 00 00 00 00 00  |................|\n\
+         00000010                                                    ||\n\
+         \x20         ^^",
+        hex_excerpt(&data, 16)
+    );
+}
+
+#[test]
+fn strict_recovery_returns_errors() {
+    let recovery = Recovery::strict();
+    assert!(!recovery.is_lenient());
+    assert_eq!(
+        Err(ClassFileParserError::UnexpectedEndOfData),
+        recovery.recover::<()>(Severity::Warning, Err(ClassFileParserError::UnexpectedEndOfData), 0x10)
+    );
+    assert_eq!(Ok(Some(1)), recovery.recover(Severity::Warning, Ok(1), 0x10));
+    assert!(recovery.into_diagnostics().is_empty());
+}
+
+#[test]
+fn lenient_recovery_collects_located_diagnostics() {
+    let recovery = Recovery::lenient();
+    recovery.enter(PathSegment::Item("methods", 2));
+    recovery.enter(PathSegment::Field("access_flags"));
+    // 没有位置的错误使用当前正在读取的结构
+    assert_eq!(Ok(()), recovery.report(Severity::Warning, ClassFileParserError::WrongConstantPoolIndexError(7), 0x40));
+    recovery.leave();
+    // 已经定位的错误只补全外层的路径
+    let located = ClassFileParserError::UnexpectedEndOfData.locate(0x52, PathSegment::Attribute("Code".to_string()));
+    assert_eq!(Ok(None::<()>), recovery.recover(Severity::Error, Err(located), 0x60));
+    recovery.leave();
+
+    let diagnostics = recovery.into_diagnostics();
+    assert_eq!(2, diagnostics.len());
+    assert_eq!(
+        "warning: The index=7 of constant pool is error! at offset 0x40 (methods[2].access_flags)",
+        diagnostics[0].to_string()
+    );
+    let Diagnostic { severity, error } = &diagnostics[1];
+    assert_eq!(Severity::Error, *severity);
+    assert_eq!(Some(0x52), error.offset());
+    assert_eq!("methods[2].attributes[Code]", error.path().unwrap().to_string());
+}
@@ -1,6 +1,11 @@
 use std::{fmt, fmt::Formatter};
 
+use crate::annotation::annotations::Annotations;
+use crate::annotation::type_annotations::TypeAnnotations;
+use crate::custom_attribute::CustomAttributes;
 use crate::flags::FieldFlags;
+use crate::signature::field_signature::FieldSignature;
+use crate::utils::attribute::Attribute;
 use crate::utils::types::Type;
 
 /// 对类中的字段进行建模
@@ -11,6 +16,16 @@ pub struct ClassFileField {
     /// final 修饰的字段，将会有一个 attribute 进行修饰
     pub constant_value: Option<FieldConstantValue>,
     pub deprecated: bool,
+    /// 泛型字段的 Signature 属性, 写回 class 文件时使用原始属性
+    pub signature: Option<FieldSignature>,
+    /// 字段上的注解, 写回 class 文件时使用原始属性
+    pub annotations: Annotations,
+    /// 字段类型上的类型注解, 例如 `List<@Nullable String>`
+    pub type_annotations: TypeAnnotations,
+    /// 已经注册的自定义属性, 写回时重新编码
+    pub custom_attributes: CustomAttributes,
+    /// 字段的原始属性, 包括已经解析到上面字段中的 ConstantValue 与 Deprecated
+    pub attributes: Vec<Attribute>,
 }
 
 impl fmt::Display for ClassFileField {
@@ -23,7 +38,20 @@ impl fmt::Display for ClassFileField {
             self.type_descriptor,
             self.constant_value,
             if self.deprecated { "(deprecated)" } else { "" }
-        )
+        )?;
+        if let Some(signature) = &self.signature {
+            write!(f, " signature: {signature}")?;
+        }
+        for annotation in self.annotations.iter() {
+            write!(f, " {annotation}")?;
+        }
+        for type_annotation in self.type_annotations.iter() {
+            write!(f, " [{type_annotation}]")?;
+        }
+        if !self.custom_attributes.is_empty() {
+            write!(f, " custom attributes: {}", self.custom_attributes)?;
+        }
+        Ok(())
     }
 }
 
@@ -7,6 +7,7 @@ use crate::utils::types::{BaseType, Type};
 fn test_filed() {
     let object_filed_type = Type::Object("StringNum".to_string());
     let base_filed_type = Type::Base(BaseType::Boolean);
+    assert_eq!("Boolean", base_filed_type.to_string());
 
     let flag = FieldFlags::PUBLIC | FieldFlags::STATIC;
     let filed = ClassFileField {
@@ -15,6 +16,11 @@ fn test_filed() {
         type_descriptor: object_filed_type,
         constant_value: None,
         deprecated: true,
+        signature: None,
+        annotations: Default::default(),
+        type_annotations: Default::default(),
+        custom_attributes: Default::default(),
+        attributes: Vec::new(),
     };
     println!("{}", filed);
 }
@@ -2,10 +2,12 @@
 mod test;
 
 use std::fmt::{Display, Formatter};
-use bitflags::{bitflags, Flags};
-/// flags, 主要是类、字段、方法的访问权限等
+use bitflags::bitflags;
+
+// flags, 主要是类、字段、方法的访问权限等
 bitflags! {
     /// 类 flags
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     pub struct ClassAccessFlags: u16 {
         const PUBLIC = 0x0001;
         const FINAL = 0x0010;
@@ -15,26 +17,35 @@ bitflags! {
         const SYNTHETIC = 0x1000;
         const ANNOTATION = 0x2000;
         const ENUM = 0x4000;
+        /// module-info.class
+        const MODULE = 0x8000;
     }
 
     /// 字段 flags
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     pub struct FieldFlags: u16 {
         const PUBLIC = 0x0001;
         const PRIVATE = 0x0002;
-        const PROTECTED = 0x004;
+        const PROTECTED = 0x0004;
         const STATIC = 0x0008;
         const FINAL = 0x0010;
         const VOLATILE = 0x0040;
         const TRANSIENT = 0x0080;
         const SYNTHETIC = 0x1000;
         const ENUM = 0x4000;
+
+        /// 访问权限, 最多只能设置其中一个
+        const ACCESS = Self::PUBLIC.bits() | Self::PRIVATE.bits() | Self::PROTECTED.bits();
+        /// 接口中的字段必须是 public static final
+        const INTERFACE_FIELD = Self::PUBLIC.bits() | Self::STATIC.bits() | Self::FINAL.bits();
     }
 
     /// 方法 flags
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
     pub struct MethodFlags: u16 {
         const PUBLIC = 0x0001;
         const PRIVATE = 0x0002;
-        const PROTECTED = 0x004;
+        const PROTECTED = 0x0004;
         const STATIC = 0x0008;
         const FINAL = 0x0010;
         const SYNCHRONIZED = 0x0020;
@@ -44,42 +55,223 @@ bitflags! {
         const ABSTRACT = 0x0400;
         const STRICT = 0x0800;
         const SYNTHETIC = 0x1000;
+
+        /// 访问权限, 最多只能设置其中一个
+        const ACCESS = Self::PUBLIC.bits() | Self::PRIVATE.bits() | Self::PROTECTED.bits();
+        /// 不能与 abstract 同时设置的 flags
+        /// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.6
+        const NOT_ABSTRACT = Self::PRIVATE.bits()
+            | Self::STATIC.bits()
+            | Self::FINAL.bits()
+            | Self::SYNCHRONIZED.bits()
+            | Self::NATIVE.bits()
+            | Self::STRICT.bits();
     }
-}
 
-impl Default for ClassAccessFlags {
-        fn default() -> ClassAccessFlags {
-                ClassAccessFlags::empty()
-        }
-}
+    /// 内部类 flags, 即内部类在源码中声明的修饰符, 来自 InnerClasses 属性
+    /// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.6
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct InnerClassAccessFlags: u16 {
+        const PUBLIC = 0x0001;
+        const PRIVATE = 0x0002;
+        const PROTECTED = 0x0004;
+        const STATIC = 0x0008;
+        const FINAL = 0x0010;
+        const INTERFACE = 0x0200;
+        const ABSTRACT = 0x0400;
+        const SYNTHETIC = 0x1000;
+        const ANNOTATION = 0x2000;
+        const ENUM = 0x4000;
+    }
 
-impl Display for ClassAccessFlags {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+    /// 方法参数 flags, 来自 MethodParameters 属性
+    /// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.24
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct MethodParameterFlags: u16 {
+        const FINAL = 0x0010;
+        const SYNTHETIC = 0x1000;
+        /// 语言规范要求隐式声明的参数, 例如内部类构造器的外部类实例
+        const MANDATED = 0x8000;
     }
-}
 
-impl Default for FieldFlags {
-    fn default() -> Self {
-        FieldFlags::empty()
+    /// 模块 flags, 来自 module-info.class 的 Module 属性
+    /// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.25
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ModuleFlags: u16 {
+        /// `open module`, 所有的包都可以在运行时被反射访问
+        const OPEN = 0x0020;
+        const SYNTHETIC = 0x1000;
+        const MANDATED = 0x8000;
     }
-}
 
-impl Display for FieldFlags {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+    /// requires 语句的 flags
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ModuleRequiresFlags: u16 {
+        const TRANSITIVE = 0x0020;
+        /// `requires static`, 只在编译时需要
+        const STATIC_PHASE = 0x0040;
+        const SYNTHETIC = 0x1000;
+        /// 隐式声明的依赖, 例如每个模块都依赖的 java.base
+        const MANDATED = 0x8000;
     }
-}
 
-impl Default for MethodFlags {
-    fn default() -> Self {
-        MethodFlags::empty()
+    /// exports 与 opens 语句的 flags, 两者的取值相同
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct ModuleExportsFlags: u16 {
+        const SYNTHETIC = 0x1000;
+        const MANDATED = 0x8000;
     }
 }
 
-impl Display for MethodFlags {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+/// (flag 的值, JVMS 中的名称, Java 源码中的修饰符)
+/// 同一个值在不同的地方含义不同, 例如 0x0040 对于字段是 volatile, 对于方法是 bridge, 所以每种 flags 各有一张表
+type FlagInfo = (u16, &'static str, Option<&'static str>);
+
+/// 按照 Java 源码中修饰符的习惯顺序排列, 例如 `public abstract class`
+const CLASS_FLAGS: [FlagInfo; 9] = [
+    (0x0001, "ACC_PUBLIC", Some("public")),
+    (0x0400, "ACC_ABSTRACT", Some("abstract")),
+    (0x0010, "ACC_FINAL", Some("final")),
+    (0x0020, "ACC_SUPER", None),
+    (0x0200, "ACC_INTERFACE", None),
+    (0x1000, "ACC_SYNTHETIC", None),
+    (0x2000, "ACC_ANNOTATION", None),
+    (0x4000, "ACC_ENUM", None),
+    (0x8000, "ACC_MODULE", None),
+];
+
+const FIELD_FLAGS: [FlagInfo; 9] = [
+    (0x0001, "ACC_PUBLIC", Some("public")),
+    (0x0002, "ACC_PRIVATE", Some("private")),
+    (0x0004, "ACC_PROTECTED", Some("protected")),
+    (0x0008, "ACC_STATIC", Some("static")),
+    (0x0010, "ACC_FINAL", Some("final")),
+    (0x0080, "ACC_TRANSIENT", Some("transient")),
+    (0x0040, "ACC_VOLATILE", Some("volatile")),
+    (0x1000, "ACC_SYNTHETIC", None),
+    (0x4000, "ACC_ENUM", None),
+];
+
+const METHOD_FLAGS: [FlagInfo; 12] = [
+    (0x0001, "ACC_PUBLIC", Some("public")),
+    (0x0002, "ACC_PRIVATE", Some("private")),
+    (0x0004, "ACC_PROTECTED", Some("protected")),
+    (0x0400, "ACC_ABSTRACT", Some("abstract")),
+    (0x0008, "ACC_STATIC", Some("static")),
+    (0x0010, "ACC_FINAL", Some("final")),
+    (0x0020, "ACC_SYNCHRONIZED", Some("synchronized")),
+    (0x0100, "ACC_NATIVE", Some("native")),
+    (0x0800, "ACC_STRICT", Some("strictfp")),
+    (0x0040, "ACC_BRIDGE", None),
+    (0x0080, "ACC_VARARGS", None),
+    (0x1000, "ACC_SYNTHETIC", None),
+];
+
+const INNER_CLASS_FLAGS: [FlagInfo; 10] = [
+    (0x0001, "ACC_PUBLIC", Some("public")),
+    (0x0002, "ACC_PRIVATE", Some("private")),
+    (0x0004, "ACC_PROTECTED", Some("protected")),
+    (0x0400, "ACC_ABSTRACT", Some("abstract")),
+    (0x0008, "ACC_STATIC", Some("static")),
+    (0x0010, "ACC_FINAL", Some("final")),
+    (0x0200, "ACC_INTERFACE", None),
+    (0x1000, "ACC_SYNTHETIC", None),
+    (0x2000, "ACC_ANNOTATION", None),
+    (0x4000, "ACC_ENUM", None),
+];
+
+const METHOD_PARAMETER_FLAGS: [FlagInfo; 3] = [
+    (0x0010, "ACC_FINAL", Some("final")),
+    (0x1000, "ACC_SYNTHETIC", None),
+    (0x8000, "ACC_MANDATED", None),
+];
+
+const MODULE_FLAGS: [FlagInfo; 3] = [
+    (0x0020, "ACC_OPEN", Some("open")),
+    (0x1000, "ACC_SYNTHETIC", None),
+    (0x8000, "ACC_MANDATED", None),
+];
+
+const MODULE_REQUIRES_FLAGS: [FlagInfo; 4] = [
+    (0x0020, "ACC_TRANSITIVE", Some("transitive")),
+    (0x0040, "ACC_STATIC_PHASE", Some("static")),
+    (0x1000, "ACC_SYNTHETIC", None),
+    (0x8000, "ACC_MANDATED", None),
+];
+
+const MODULE_EXPORTS_FLAGS: [FlagInfo; 2] = [
+    (0x1000, "ACC_SYNTHETIC", None),
+    (0x8000, "ACC_MANDATED", None),
+];
+
+fn modifiers(bits: u16, table: &[FlagInfo]) -> Vec<&'static str> {
+    table
+        .iter()
+        .filter(|(flag, _, _)| bits & flag != 0)
+        .filter_map(|(_, _, modifier)| *modifier)
+        .collect()
+}
+
+/// JVMS 中的名称按照值从小到大排列, 与 javap 一致
+fn jvms_names(bits: u16, table: &[FlagInfo]) -> Vec<&'static str> {
+    let mut flags: Vec<&FlagInfo> = table.iter().filter(|(flag, _, _)| bits & flag != 0).collect();
+    flags.sort_by_key(|(flag, _, _)| *flag);
+    flags.iter().map(|(_, name, _)| *name).collect()
+}
+
+/// 接口隐含了 abstract, 与 javap 一样不会输出它。类与内部类的 ACC_INTERFACE 与 ACC_ABSTRACT 取值相同
+fn without_implied_abstract(bits: u16) -> u16 {
+    if bits & ClassAccessFlags::INTERFACE.bits() != 0 {
+        bits & !ClassAccessFlags::ABSTRACT.bits()
+    } else {
+        bits
     }
 }
 
+/// 根据 flags 的表生成 `modifiers`、`jvms_names` 以及 Default 与 Display。
+/// 可选的 modifier_bits 在输出修饰符之前去掉隐含的 flags, 例如接口的 abstract
+macro_rules! impl_flag_names {
+    ($flags:ident, $table:ident) => {
+        impl_flag_names!($flags, $table, std::convert::identity);
+    };
+    ($flags:ident, $table:ident, $modifier_bits:path) => {
+        impl $flags {
+            /// Java 源码中的修饰符, 例如 `public static final`
+            pub fn modifiers(&self) -> Vec<&'static str> {
+                modifiers($modifier_bits(self.bits()), &$table)
+            }
+
+            /// JVMS 中的名称, 例如 `ACC_PUBLIC`
+            pub fn jvms_names(&self) -> Vec<&'static str> {
+                jvms_names(self.bits(), &$table)
+            }
+        }
+
+        impl Default for $flags {
+            fn default() -> Self {
+                $flags::empty()
+            }
+        }
+
+        /// 默认输出 Java 修饰符, 例如 `public static final`;
+        /// `{:#}` 输出 JVMS 中的名称, 例如 `ACC_PUBLIC, ACC_SUPER`
+        impl Display for $flags {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                if f.alternate() {
+                    write!(f, "{}", self.jvms_names().join(", "))
+                } else {
+                    write!(f, "{}", self.modifiers().join(" "))
+                }
+            }
+        }
+    };
+}
+
+impl_flag_names!(ClassAccessFlags, CLASS_FLAGS, without_implied_abstract);
+impl_flag_names!(FieldFlags, FIELD_FLAGS);
+impl_flag_names!(MethodFlags, METHOD_FLAGS);
+impl_flag_names!(InnerClassAccessFlags, INNER_CLASS_FLAGS, without_implied_abstract);
+impl_flag_names!(MethodParameterFlags, METHOD_PARAMETER_FLAGS);
+impl_flag_names!(ModuleFlags, MODULE_FLAGS);
+impl_flag_names!(ModuleRequiresFlags, MODULE_REQUIRES_FLAGS);
+impl_flag_names!(ModuleExportsFlags, MODULE_EXPORTS_FLAGS);
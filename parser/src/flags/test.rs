@@ -1,5 +1,6 @@
-use bitflags::Flags;
-use crate::flags::{ClassAccessFlags, FieldFlags, MethodFlags};
+use crate::flags::{
+    ClassAccessFlags, FieldFlags, InnerClassAccessFlags, MethodFlags, ModuleFlags, ModuleRequiresFlags,
+};
 
 #[test]
 fn test_flags() {
@@ -8,5 +9,47 @@ fn test_flags() {
     assert_eq!(format!("{:?}", class_flag.0), "PUBLIC | INTERFACE");
 
     let filed_flag = FieldFlags::PUBLIC | FieldFlags::FINAL;
-    print!("{}", filed_flag.0);
-}
\ No newline at end of file
+    print!("{}", filed_flag);
+}
+
+#[test]
+fn can_display_flags() {
+    let class_flag = ClassAccessFlags::PUBLIC | ClassAccessFlags::SUPER | ClassAccessFlags::ABSTRACT;
+    assert_eq!("public abstract", class_flag.to_string());
+    assert_eq!("ACC_PUBLIC, ACC_SUPER, ACC_ABSTRACT", format!("{:#}", class_flag));
+    // 接口隐含了 abstract
+    let interface_flag = ClassAccessFlags::PUBLIC | ClassAccessFlags::INTERFACE | ClassAccessFlags::ABSTRACT;
+    assert_eq!("public", interface_flag.to_string());
+    assert_eq!("ACC_MODULE", format!("{:#}", ClassAccessFlags::from_bits(0x8000).unwrap()));
+
+    let field_flag = FieldFlags::PUBLIC | FieldFlags::STATIC | FieldFlags::FINAL;
+    assert_eq!("public static final", field_flag.to_string());
+    assert_eq!(FieldFlags::INTERFACE_FIELD.bits(), field_flag.bits());
+    assert_eq!("", FieldFlags::empty().to_string());
+}
+
+#[test]
+fn interprets_overlapping_flags_by_owner() {
+    // 0x0040 与 0x0080 对于字段是 volatile 与 transient, 对于方法是 bridge 与 varargs
+    let field_flag = FieldFlags::from_bits(0x00c2).unwrap();
+    assert_eq!("private transient volatile", field_flag.to_string());
+    assert_eq!("ACC_PRIVATE, ACC_VOLATILE, ACC_TRANSIENT", format!("{:#}", field_flag));
+
+    let method_flag = MethodFlags::from_bits(0x10c1).unwrap();
+    assert_eq!("public", method_flag.to_string());
+    assert_eq!("ACC_PUBLIC, ACC_BRIDGE, ACC_VARARGS, ACC_SYNTHETIC", format!("{:#}", method_flag));
+    assert_eq!(MethodFlags::PUBLIC.bits(), (method_flag & MethodFlags::ACCESS).bits());
+    assert!(!MethodFlags::ABSTRACT.intersects(MethodFlags::NOT_ABSTRACT));
+
+    // 内部类可以是 private static 的, 这在类 flags 中不存在
+    let inner_class_flag = InnerClassAccessFlags::from_bits(0x060a).unwrap();
+    assert_eq!("private static", inner_class_flag.to_string());
+    assert_eq!("ACC_PRIVATE, ACC_STATIC, ACC_INTERFACE, ACC_ABSTRACT", format!("{:#}", inner_class_flag));
+
+    // 0x0020 对于类是 ACC_SUPER, 对于模块是 open, 对于 requires 是 transitive
+    assert_eq!("open", ModuleFlags::from_bits(0x0020).unwrap().to_string());
+    let requires_flag = ModuleRequiresFlags::from_bits(0x0060).unwrap();
+    assert_eq!("transitive static", requires_flag.to_string());
+    assert_eq!("ACC_TRANSITIVE, ACC_STATIC_PHASE", format!("{:#}", requires_flag));
+    assert_eq!("ACC_MANDATED", format!("{:#}", ModuleRequiresFlags::MANDATED));
+}
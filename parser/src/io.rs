@@ -0,0 +1,42 @@
+use std::fs::File;
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::class::ClassFile;
+use crate::class_parser::ClassFileReader;
+use crate::error::ClassFileParserResult;
+use crate::view::ClassFileView;
+
+/// 以内存映射的方式打开的 class 文件, 不需要把文件复制到内存中,
+/// 适合配合 [ClassFileView] 只读取其中的一部分
+pub struct MappedClassFile {
+    mmap: Mmap,
+}
+
+impl MappedClassFile {
+    /// # Safety
+    ///
+    /// 调用者必须保证在 MappedClassFile 存在期间文件不会被修改或者截断。
+    /// 映射的内容通过 `&[u8]` 读取, 文件被其他进程修改是未定义行为, 被截断时读取会触发 SIGBUS,
+    /// 解析时对长度与索引的检查无法避免这些问题
+    pub unsafe fn open(path: impl AsRef<Path>) -> std::io::Result<MappedClassFile> {
+        let file = File::open(path)?;
+        // SAFETY: 由调用者保证文件在映射期间不会被修改或者截断
+        let mmap = unsafe { Mmap::map(&file)? };
+        Ok(MappedClassFile { mmap })
+    }
+
+    /// 文件的内容
+    pub fn bytes(&self) -> &[u8] {
+        &self.mmap
+    }
+
+    pub fn view(&self) -> ClassFileParserResult<ClassFileView<'_>> {
+        ClassFileView::parse(self.bytes())
+    }
+
+    pub fn read(&self) -> ClassFileParserResult<ClassFile> {
+        ClassFileReader::new(self.bytes()).read()
+    }
+}
@@ -1,19 +1,75 @@
+use std::io::Read;
+use std::path::Path;
+
 use crate::class::ClassFile;
-use crate::class_parser::ClassFileReader;
-use crate::error::ClassFileParserResult;
+use crate::class_parser::{ClassFileReader, ParseReport};
+use crate::class_writer::ClassFileWriter;
+use crate::custom_attribute::AttributeRegistry;
+use crate::error::{ClassFileParserResult, ClassFileReadResult};
+use crate::io::MappedClassFile;
 
-mod version;
-mod error;
+pub mod version;
+pub mod error;
 pub mod log;
-mod constant_pool;
-mod flags;
-mod field;
-mod method;
-mod utils;
+pub mod constant_pool;
+pub mod flags;
+pub mod field;
+pub mod method;
+pub mod utils;
 pub mod class;
+pub mod class_attributes;
 pub mod class_parser;
+pub mod class_writer;
+pub mod signature;
+pub mod annotation;
+pub mod custom_attribute;
+pub mod view;
+pub mod io;
+pub mod validation;
 
-/// 将数据读取为一个 Class 文件的抽象
+/// 将数据读取为一个 Class 文件的抽象, 出错时错误带有出错的字节偏移与结构路径,
+/// 见 [error::ClassFileParserError::offset] 与 [error::ClassFileParserError::hex_excerpt]
 pub fn read_buffer(buf: &[u8]) -> ClassFileParserResult<ClassFile>{
     ClassFileReader::new(buf).read()
-}
\ No newline at end of file
+}
+
+/// 与 [read_buffer] 相同, 另外使用 registry 中注册的解码器解码自定义属性
+pub fn read_buffer_with(buf: &[u8], registry: &AttributeRegistry) -> ClassFileParserResult<ClassFile> {
+    ClassFileReader::with_registry(buf, registry).read()
+}
+
+/// 从任意的 [Read] 读取, 例如 jar 中压缩的 entry, 输入会先被完整地读入内存。
+/// 读取失败时返回 [error::ClassFileReadError::Io], 内容不是合法的 class 文件时返回 [error::ClassFileReadError::Format]
+pub fn read_from(mut reader: impl Read) -> ClassFileReadResult<ClassFile> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+    Ok(read_buffer(&bytes)?)
+}
+
+/// 读取 path 处的 class 文件, 错误与 [read_from] 相同
+pub fn read_file(path: impl AsRef<Path>) -> ClassFileReadResult<ClassFile> {
+    let bytes = std::fs::read(path)?;
+    Ok(read_buffer(&bytes)?)
+}
+
+/// 与 [read_file] 相同, 但是使用内存映射读取文件, 见 [MappedClassFile]
+///
+/// # Safety
+///
+/// 与 [MappedClassFile::open] 相同, 调用者必须保证读取期间文件不会被修改或者截断
+pub unsafe fn read_file_mapped(path: impl AsRef<Path>) -> ClassFileReadResult<ClassFile> {
+    // SAFETY: 由调用者保证
+    let mapped = unsafe { MappedClassFile::open(path)? };
+    Ok(mapped.read()?)
+}
+
+/// 宽松模式读取: 接受未知的 flag bits 与版本, 跳过无法解码的属性以及无法读取的字段和方法,
+/// 返回读取到的部分内容以及所有的 warning 与 error, 见 [error::diagnostic::Severity]
+pub fn read_buffer_lenient(buf: &[u8]) -> ParseReport {
+    ClassFileReader::lenient(buf, None).read_lenient()
+}
+
+/// 将 Class 文件的抽象写回字节流, 未经修改的 ClassFile 会得到与原文件相同的字节
+pub fn write_buffer(class_file: &ClassFile) -> ClassFileParserResult<Vec<u8>> {
+    ClassFileWriter::new(class_file).write()
+}
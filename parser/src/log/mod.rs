@@ -1,8 +1,7 @@
 #[cfg(test)]
 mod test;
-use std::io::Write;
 use env_logger::Target;
-use log::{debug, info};
+use log::debug;
 
 pub enum LogLevel {
     INFO,
@@ -25,7 +24,8 @@ pub fn init_log(level: LogLevel) {
     // });
 
     builder.target(Target::Stdout);
-    builder.init();
+    // 多个测试会在同一个进程中重复初始化, 这里忽略重复初始化的错误
+    let _ = builder.try_init();
     debug!("Logger has init as info level");
 }
 
@@ -1,5 +1,5 @@
 use log::{debug, error, info, trace};
-use crate::log::{init_log, LogLevel, update_log_level};
+use crate::log::{init_log, LogLevel};
 
 #[test]
 fn test_log() {
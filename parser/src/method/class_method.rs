@@ -1,11 +1,22 @@
 use std::{fmt, fmt::Formatter};
 
+use itertools::Itertools;
+
+use crate::annotation::annotations::{Annotations, ElementValue};
+use crate::annotation::type_annotations::TypeAnnotations;
+use crate::custom_attribute::CustomAttributes;
 use crate::flags::MethodFlags;
 use crate::method::descriptor::MethodDescriptor;
 use crate::method::exception_table::ExceptionTable;
 use crate::method::line_number_table::LineNumberTable;
+use crate::method::local_variable_table::LocalVariableTable;
+use crate::method::method_parameters::MethodParameter;
+use crate::method::stack_map_table::StackMapTable;
+use crate::signature::method_signature::MethodSignature;
 use crate::utils::attribute::Attribute;
 use crate::utils::instruction::Instruction;
+use crate::utils::line_number::LineNumber;
+use crate::utils::pc::ProgramCounter;
 use crate::utils::types::{BaseType, Type};
 
 /// Models a method in a class
@@ -26,6 +37,20 @@ pub struct ClassFileMethod {
     pub deprecated: bool,
     /// exceptions 是每一个 method 都必须有的属性
     pub thrown_exceptions: Vec<String>,
+    /// 泛型方法的 Signature 属性, 写回 class 文件时使用原始属性
+    pub signature: Option<MethodSignature>,
+    /// 方法上的注解, 写回 class 文件时使用原始属性
+    pub annotations: Annotations,
+    /// 返回值、参数、throws 子句以及类型形参上的类型注解, 方法体中的类型注解在 [ClassFileMethodCode] 中
+    pub type_annotations: TypeAnnotations,
+    /// 每个参数上的注解, 没有参数注解时为空
+    pub parameter_annotations: Vec<Annotations>,
+    /// 注解类型中元素的默认值, 来自 AnnotationDefault 属性
+    pub annotation_default: Option<ElementValue>,
+    /// MethodParameters 属性中的参数名与 flags, 没有这个属性时为空
+    pub parameters: Vec<MethodParameter>,
+    /// 已经注册的自定义属性, 写回时重新编码
+    pub custom_attributes: CustomAttributes,
 }
 
 impl fmt::Display for ClassFileMethod {
@@ -39,6 +64,29 @@ impl fmt::Display for ClassFileMethod {
             if self.deprecated { " (deprecated)" } else { "" },
             self.thrown_exceptions,
         )?;
+        if let Some(signature) = &self.signature {
+            writeln!(f, "  signature: {signature}")?;
+        }
+        for annotation in self.annotations.iter() {
+            writeln!(f, "  annotation: {annotation}")?;
+        }
+        for type_annotation in self.type_annotations.iter() {
+            writeln!(f, "  type annotation: {type_annotation}")?;
+        }
+        for (index, annotations) in self.parameter_annotations.iter().enumerate() {
+            for annotation in annotations.iter() {
+                writeln!(f, "  parameter {index} annotation: {annotation}")?;
+            }
+        }
+        if !self.parameters.is_empty() {
+            writeln!(f, "  parameters: {}", self.parameters.iter().join(", "))?;
+        }
+        if let Some(default_value) = &self.annotation_default {
+            writeln!(f, "  default: {default_value}")?;
+        }
+        if !self.custom_attributes.is_empty() {
+            writeln!(f, "  custom attributes: {}", self.custom_attributes)?;
+        }
         if let Some(code) = &self.code {
             writeln!(f, "  code: {code}")?;
         }
@@ -59,6 +107,33 @@ impl ClassFileMethod {
         self.parsed_type_descriptor.return_type.is_none()
     }
 
+    /// 第 index 个参数在源码中的名称, 优先使用 MethodParameters 属性, 其次使用局部变量表,
+    /// 两者都没有时为 None
+    pub fn parameter_name(&self, index: usize) -> Option<&str> {
+        if let Some(parameter) = self.parameters.get(index) {
+            return parameter.name.as_deref();
+        }
+        if index >= self.parsed_type_descriptor.parameters.len() {
+            return None;
+        }
+        let local_variable_table = self.code.as_ref()?.local_variable_table.as_ref()?;
+        // 实例方法的 slot 0 是 this, long 与 double 占据两个 slot
+        let slot: usize = usize::from(!self.is_static())
+            + self
+                .parsed_type_descriptor
+                .parameters
+                .iter()
+                .take(index)
+                .map(|parameter| match parameter {
+                    Type::Base(BaseType::Long) | Type::Base(BaseType::Double) => 2,
+                    _ => 1,
+                })
+                .sum::<usize>();
+        local_variable_table
+            .lookup(u16::try_from(slot).ok()?, ProgramCounter(0))
+            .map(|variable| variable.name.as_str())
+    }
+
     pub fn returns(&self, expected_type: Type) -> bool {
         match self.parsed_type_descriptor.return_type {
             Some(Type::Base(BaseType::Int))
@@ -84,12 +159,29 @@ pub struct ClassFileMethodCode {
     pub code: Vec<u8>,
     pub exception_table: ExceptionTable,
     pub line_number_table: Option<LineNumberTable>,
+    /// 只有使用 `javac -g` 编译时才有局部变量表
+    pub local_variable_table: Option<LocalVariableTable>,
+    /// 类型检查使用的 StackMapTable, 写回 class 文件时会重新编码
+    pub stack_map_table: Option<StackMapTable>,
+    /// 局部变量、catch 参数以及 new、instanceof、类型转换等表达式中的类型注解
+    pub type_annotations: TypeAnnotations,
+    /// Code 中已经注册的自定义属性, 写回时重新编码
+    pub custom_attributes: CustomAttributes,
 
     /// Generic unmapped attributes of the code
     // TODO: replace with some proper struct
     pub attributes: Vec<Attribute>,
 }
 
+impl ClassFileMethodCode {
+    /// pc 处的指令所在的行, 没有 LineNumberTable 或者 pc 在第一项之前时为 None
+    pub fn line_number_of(&self, pc: ProgramCounter) -> Option<LineNumber> {
+        let line_number_table = self.line_number_table.as_ref()?;
+        let first_entry = line_number_table.entries().first()?;
+        (first_entry.program_counter <= pc).then(|| line_number_table.lookup_pc(pc))
+    }
+}
+
 impl fmt::Display for ClassFileMethodCode {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         writeln!(
@@ -106,6 +198,32 @@ impl fmt::Display for ClassFileMethodCode {
         } else {
             writeln!(f, "    unparseable code: {:?}", self.code)?;
         }
+        if let Some(stack_map_table) = &self.stack_map_table {
+            writeln!(f, "  stack map frames:")?;
+            for frame in stack_map_table.frames() {
+                writeln!(f, "    {frame}")?;
+            }
+        }
+        for type_annotation in self.type_annotations.iter() {
+            writeln!(f, "  type annotation: {type_annotation}")?;
+        }
+        if !self.custom_attributes.is_empty() {
+            writeln!(f, "  custom attributes: {}", self.custom_attributes)?;
+        }
+        if let Some(local_variable_table) = &self.local_variable_table {
+            writeln!(f, "  local variables:")?;
+            for variable in local_variable_table.entries() {
+                let type_text = match &variable.signature {
+                    Some(signature) => signature.to_string(),
+                    None => variable.type_descriptor.descriptor(),
+                };
+                writeln!(
+                    f,
+                    "    slot {} [{}, {}): {} {}",
+                    variable.slot, variable.range.start, variable.range.end, variable.name, type_text
+                )?;
+            }
+        }
         Ok(())
     }
 }
@@ -1,5 +1,5 @@
 use std::fmt;
-use std::fmt::{Formatter, Write};
+use std::fmt::Formatter;
 use std::str::Chars;
 use itertools::Itertools;
 use crate::error::ClassFileParserError::InvalidMethodTypeDescriptor;
@@ -13,6 +13,10 @@ impl ExceptionTable {
         Self { entries }
     }
 
+    pub fn entries(&self) -> &[ExceptionTableEntry] {
+        &self.entries
+    }
+
     pub fn lookup(&self, pc: ProgramCounter) -> Vec<&ExceptionTableEntry> {
         self.entries
             .iter()
@@ -20,6 +20,10 @@ impl LineNumberTable {
         }
     }
 
+    pub fn entries(&self) -> &[LineNumberTableEntry] {
+        &self.entries
+    }
+
     pub fn lookup_pc(&self, pc: ProgramCounter) -> LineNumber {
         let best_matching_entry_index = match self
             .entries
@@ -41,7 +45,7 @@ pub struct LineNumberTableEntry {
 
 impl PartialOrd for LineNumberTableEntry {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.program_counter.partial_cmp(&other.program_counter)
+        Some(self.cmp(other))
     }
 }
 
@@ -0,0 +1,51 @@
+use std::ops::Range;
+
+use crate::signature::field_signature::FieldSignature;
+use crate::utils::pc::ProgramCounter;
+use crate::utils::types::Type;
+
+/// 由 LocalVariableTable 与 LocalVariableTypeTable 两个属性合并得到的局部变量表,
+/// 可以查询某个位置上某个 slot 中保存的是哪个局部变量。
+/// 同一个 slot 在不同的作用域中可以被不同的局部变量复用。
+/// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.13
+#[derive(Debug, Default, PartialEq)]
+pub struct LocalVariableTable {
+    entries: Vec<LocalVariable>,
+}
+
+/// 一个局部变量
+#[derive(Debug, PartialEq, Clone)]
+pub struct LocalVariable {
+    /// 局部变量有值的地址范围
+    pub range: Range<ProgramCounter>,
+    /// 局部变量表中的索引, long 与 double 同时占据 slot + 1
+    pub slot: u16,
+    pub name: String,
+    pub type_descriptor: Type,
+    /// 来自 LocalVariableTypeTable, 只有泛型类型的局部变量才有
+    pub signature: Option<FieldSignature>,
+}
+
+impl LocalVariableTable {
+    pub fn new(entries: Vec<LocalVariable>) -> Self {
+        Self { entries }
+    }
+
+    pub fn entries(&self) -> &[LocalVariable] {
+        &self.entries
+    }
+
+    /// 查询 pc 处 slot 中保存的局部变量
+    pub fn lookup(&self, slot: u16, pc: ProgramCounter) -> Option<&LocalVariable> {
+        self.entries
+            .iter()
+            .find(|entry| entry.slot == slot && entry.range.contains(&pc))
+    }
+
+    /// pc 处所有有值的局部变量, 按照 slot 排序
+    pub fn variables_at(&self, pc: ProgramCounter) -> Vec<&LocalVariable> {
+        let mut variables: Vec<&LocalVariable> = self.entries.iter().filter(|entry| entry.range.contains(&pc)).collect();
+        variables.sort_by_key(|entry| entry.slot);
+        variables
+    }
+}
@@ -0,0 +1,49 @@
+use std::fmt;
+use std::fmt::Formatter;
+
+use crate::constant_pool::constant_pool::ConstantPool;
+use crate::error::ClassFileParserResult;
+use crate::flags::MethodParameterFlags;
+use crate::utils::buffer::Buffer;
+
+/// MethodParameters 属性中的一个参数, 使用 `javac -parameters` 编译时才会生成,
+/// record 的规范构造器总是有这个属性
+/// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.24
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodParameter {
+    /// 源码中的参数名, 编译器生成的参数可能没有名称
+    pub name: Option<String>,
+    pub flags: MethodParameterFlags,
+}
+
+impl MethodParameter {
+    /// 读取 MethodParameters 属性的内容
+    pub(crate) fn read_all(bytes: &[u8], constants: &ConstantPool) -> ClassFileParserResult<Vec<MethodParameter>> {
+        let mut buf = Buffer::new(bytes);
+        let parameters_count = buf.read_u8()?;
+        (0..parameters_count)
+            .map(|_| {
+                let name_index = buf.read_u16()?;
+                let name = if name_index == 0 { None } else { Some(constants.text_of(name_index)?) };
+                // 未知的 flag bits 由 ClassFileReader 检查, 宽松模式下保留它们
+                let flags = MethodParameterFlags::from_bits_retain(buf.read_u16()?);
+                Ok(MethodParameter { name, flags })
+            })
+            .collect()
+    }
+
+    /// 源码中没有出现的参数, 包括编译器生成的与语言规范隐式声明的参数
+    pub fn is_implicit(&self) -> bool {
+        self.flags
+            .intersects(MethodParameterFlags::SYNTHETIC | MethodParameterFlags::MANDATED)
+    }
+}
+
+impl fmt::Display for MethodParameter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for modifier in self.flags.modifiers() {
+            write!(f, "{modifier} ")?;
+        }
+        f.write_str(self.name.as_deref().unwrap_or("<unnamed>"))
+    }
+}
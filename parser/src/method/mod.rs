@@ -1,6 +1,9 @@
-pub(crate) mod class_method;
-pub(crate) mod descriptor;
+pub mod class_method;
+pub mod descriptor;
 #[cfg(test)]
 mod test;
-pub(crate) mod exception_table;
-pub(crate) mod line_number_table;
\ No newline at end of file
+pub mod exception_table;
+pub mod line_number_table;
+pub mod local_variable_table;
+pub mod method_parameters;
+pub mod stack_map_table;
\ No newline at end of file
@@ -0,0 +1,317 @@
+use std::fmt;
+use std::fmt::Formatter;
+
+use itertools::Itertools;
+
+use crate::constant_pool::constant_pool::ConstantPool;
+use crate::error::{ClassFileParserError, ClassFileParserResult};
+use crate::utils::buffer::{Buffer, BufferWriter};
+use crate::utils::pc::ProgramCounter;
+
+/// Code 属性中的 StackMapTable 属性, 记录了类型检查时各个位置的局部变量与操作数栈的类型
+/// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.4
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct StackMapTable {
+    frames: Vec<StackMapFrame>,
+}
+
+/// StackMapTable 中的一个 frame, 位置已经由 offset_delta 换算为绝对地址
+#[derive(Debug, PartialEq, Clone)]
+pub struct StackMapFrame {
+    pub program_counter: ProgramCounter,
+    pub frame_type: StackMapFrameType,
+}
+
+/// frame 的种类, 相对于上一个 frame 描述局部变量与操作数栈的变化
+#[derive(Debug, PartialEq, Clone)]
+pub enum StackMapFrameType {
+    /// frame_type 0-63, 局部变量不变, 操作数栈为空
+    Same,
+    /// frame_type 64-127, 局部变量不变, 操作数栈中只有一个元素
+    SameLocals1StackItem(VerificationType),
+    /// frame_type 247
+    SameLocals1StackItemExtended(VerificationType),
+    /// frame_type 248-250, 去掉最后的 1 到 3 个局部变量, 操作数栈为空
+    Chop(u8),
+    /// frame_type 251
+    SameExtended,
+    /// frame_type 252-254, 增加 1 到 3 个局部变量, 操作数栈为空
+    Append(Vec<VerificationType>),
+    /// frame_type 255, 完整的局部变量与操作数栈
+    Full {
+        locals: Vec<VerificationType>,
+        stack: Vec<VerificationType>,
+    },
+}
+
+/// verification_type_info, long 与 double 只占一项
+#[derive(Debug, PartialEq, Clone)]
+pub enum VerificationType {
+    Top,
+    Integer,
+    Float,
+    Double,
+    Long,
+    Null,
+    UninitializedThis,
+    /// 类名, 例如 `java/lang/String` 或者数组 `[I`
+    Object(String),
+    /// 还没有调用构造器的对象, 保存创建它的 new 指令的地址
+    Uninitialized(ProgramCounter),
+}
+
+impl StackMapTable {
+    pub fn new(frames: Vec<StackMapFrame>) -> Self {
+        Self { frames }
+    }
+
+    pub fn frames(&self) -> &[StackMapFrame] {
+        &self.frames
+    }
+
+    /// 查找恰好位于 pc 处的 frame
+    pub fn lookup(&self, pc: ProgramCounter) -> Option<&StackMapFrame> {
+        self.frames
+            .binary_search_by(|frame| frame.program_counter.cmp(&pc))
+            .ok()
+            .map(|index| &self.frames[index])
+    }
+
+    /// 读取 StackMapTable 属性的内容
+    pub(crate) fn read(bytes: &[u8], constants: &ConstantPool) -> ClassFileParserResult<StackMapTable> {
+        let mut buf = Buffer::new(bytes);
+        let number_of_entries = buf.read_u16()?;
+        let mut frames: Vec<StackMapFrame> = Vec::with_capacity(number_of_entries as usize);
+        for _ in 0..number_of_entries {
+            let frame_type = buf.read_u8()?;
+            let (offset_delta, frame_type) = match frame_type {
+                0..=63 => (frame_type as u16, StackMapFrameType::Same),
+                64..=127 => (
+                    frame_type as u16 - 64,
+                    StackMapFrameType::SameLocals1StackItem(VerificationType::read(&mut buf, constants)?),
+                ),
+                247 => (
+                    buf.read_u16()?,
+                    StackMapFrameType::SameLocals1StackItemExtended(VerificationType::read(&mut buf, constants)?),
+                ),
+                248..=250 => (buf.read_u16()?, StackMapFrameType::Chop(251 - frame_type)),
+                251 => (buf.read_u16()?, StackMapFrameType::SameExtended),
+                252..=254 => {
+                    let offset_delta = buf.read_u16()?;
+                    let locals = VerificationType::read_all(&mut buf, constants, frame_type as u16 - 251)?;
+                    (offset_delta, StackMapFrameType::Append(locals))
+                }
+                255 => {
+                    let offset_delta = buf.read_u16()?;
+                    let number_of_locals = buf.read_u16()?;
+                    let locals = VerificationType::read_all(&mut buf, constants, number_of_locals)?;
+                    let number_of_stack_items = buf.read_u16()?;
+                    let stack = VerificationType::read_all(&mut buf, constants, number_of_stack_items)?;
+                    (offset_delta, StackMapFrameType::Full { locals, stack })
+                }
+                _ => {
+                    return Err(ClassFileParserError::InvalidClassData {
+                        name: format!("reserved stack map frame type {frame_type}"),
+                        is_invalidate_constant_pool_idx: false,
+                    })
+                }
+            };
+            // 第一个 frame 的地址就是 offset_delta, 之后的 frame 都是上一个的地址 + offset_delta + 1
+            let program_counter = match frames.last() {
+                None => Some(offset_delta),
+                Some(previous) => previous.program_counter.0.checked_add(offset_delta).and_then(|pc| pc.checked_add(1)),
+            }
+            .ok_or_else(|| ClassFileParserError::InvalidClassData {
+                name: format!("stack map frame offset_delta {offset_delta} overflows the code"),
+                is_invalidate_constant_pool_idx: false,
+            })?;
+            frames.push(StackMapFrame {
+                program_counter: ProgramCounter(program_counter),
+                frame_type,
+            });
+        }
+        Ok(StackMapTable::new(frames))
+    }
+
+    /// 写出 StackMapTable 属性的内容, 是 [StackMapTable::read] 的逆操作。
+    /// class_index 用于获取类名在常量池中的索引。
+    /// offset_delta 放不进 Same 与 SameLocals1StackItem 时, 自动使用它们的 extended 形式
+    pub(crate) fn write(
+        &self,
+        class_index: &mut dyn FnMut(&str) -> ClassFileParserResult<u16>,
+    ) -> ClassFileParserResult<Vec<u8>> {
+        let mut buffer = BufferWriter::new();
+        buffer.write_u16(length_u16(self.frames.len(), "stack map frames")?);
+        let mut previous: Option<ProgramCounter> = None;
+        for frame in self.frames.iter() {
+            let offset_delta = match previous {
+                None => Some(frame.program_counter.0),
+                Some(previous) => frame
+                    .program_counter
+                    .0
+                    .checked_sub(previous.0)
+                    .and_then(|delta| delta.checked_sub(1)),
+            }
+            .ok_or_else(|| ClassFileParserError::InvalidClassData {
+                name: format!("stack map frames are not sorted at {}", frame.program_counter),
+                is_invalidate_constant_pool_idx: false,
+            })?;
+            previous = Some(frame.program_counter);
+
+            match &frame.frame_type {
+                StackMapFrameType::Same if offset_delta < 64 => buffer.write_u8(offset_delta as u8),
+                StackMapFrameType::Same | StackMapFrameType::SameExtended => {
+                    buffer.write_u8(251);
+                    buffer.write_u16(offset_delta);
+                }
+                StackMapFrameType::SameLocals1StackItem(stack_item) if offset_delta < 64 => {
+                    buffer.write_u8(64 + offset_delta as u8);
+                    stack_item.write(&mut buffer, class_index)?;
+                }
+                StackMapFrameType::SameLocals1StackItem(stack_item)
+                | StackMapFrameType::SameLocals1StackItemExtended(stack_item) => {
+                    buffer.write_u8(247);
+                    buffer.write_u16(offset_delta);
+                    stack_item.write(&mut buffer, class_index)?;
+                }
+                StackMapFrameType::Chop(count @ 1..=3) => {
+                    buffer.write_u8(251 - count);
+                    buffer.write_u16(offset_delta);
+                }
+                StackMapFrameType::Append(locals) if (1..=3).contains(&locals.len()) => {
+                    buffer.write_u8(251 + locals.len() as u8);
+                    buffer.write_u16(offset_delta);
+                    for local in locals {
+                        local.write(&mut buffer, class_index)?;
+                    }
+                }
+                StackMapFrameType::Full { locals, stack } => {
+                    buffer.write_u8(255);
+                    buffer.write_u16(offset_delta);
+                    for items in [locals, stack] {
+                        buffer.write_u16(length_u16(items.len(), "verification types")?);
+                        for item in items {
+                            item.write(&mut buffer, class_index)?;
+                        }
+                    }
+                }
+                frame_type => {
+                    return Err(ClassFileParserError::InvalidClassData {
+                        name: format!("stack map frame {frame_type:?} must chop or append 1 to 3 locals"),
+                        is_invalidate_constant_pool_idx: false,
+                    })
+                }
+            }
+        }
+        Ok(buffer.into_bytes())
+    }
+}
+
+fn length_u16(len: usize, what: &str) -> ClassFileParserResult<u16> {
+    u16::try_from(len).map_err(|_| ClassFileParserError::InvalidClassData {
+        name: format!("too many {what} to be written: {len}"),
+        is_invalidate_constant_pool_idx: false,
+    })
+}
+
+impl VerificationType {
+    fn read(buf: &mut Buffer, constants: &ConstantPool) -> ClassFileParserResult<VerificationType> {
+        let tag = buf.read_u8()?;
+        Ok(match tag {
+            0 => VerificationType::Top,
+            1 => VerificationType::Integer,
+            2 => VerificationType::Float,
+            3 => VerificationType::Double,
+            4 => VerificationType::Long,
+            5 => VerificationType::Null,
+            6 => VerificationType::UninitializedThis,
+            7 => VerificationType::Object(constants.text_of(buf.read_u16()?)?),
+            8 => VerificationType::Uninitialized(ProgramCounter(buf.read_u16()?)),
+            _ => {
+                return Err(ClassFileParserError::InvalidClassData {
+                    name: format!("invalid verification type tag {tag}"),
+                    is_invalidate_constant_pool_idx: false,
+                })
+            }
+        })
+    }
+
+    fn read_all(
+        buf: &mut Buffer,
+        constants: &ConstantPool,
+        count: u16,
+    ) -> ClassFileParserResult<Vec<VerificationType>> {
+        (0..count).map(|_| VerificationType::read(buf, constants)).collect()
+    }
+
+    fn write(
+        &self,
+        buffer: &mut BufferWriter,
+        class_index: &mut dyn FnMut(&str) -> ClassFileParserResult<u16>,
+    ) -> ClassFileParserResult<()> {
+        match self {
+            VerificationType::Top => buffer.write_u8(0),
+            VerificationType::Integer => buffer.write_u8(1),
+            VerificationType::Float => buffer.write_u8(2),
+            VerificationType::Double => buffer.write_u8(3),
+            VerificationType::Long => buffer.write_u8(4),
+            VerificationType::Null => buffer.write_u8(5),
+            VerificationType::UninitializedThis => buffer.write_u8(6),
+            VerificationType::Object(class_name) => {
+                buffer.write_u8(7);
+                buffer.write_u16(class_index(class_name)?);
+            }
+            VerificationType::Uninitialized(offset) => {
+                buffer.write_u8(8);
+                buffer.write_u16(offset.0);
+            }
+        }
+        Ok(())
+    }
+
+    /// long 与 double 在局部变量表中占两个 slot
+    pub fn is_category2(&self) -> bool {
+        matches!(self, VerificationType::Long | VerificationType::Double)
+    }
+}
+
+/// 与 javap 的输出一致, 例如 `int`、`class java/lang/String`、`uninitialized 0`
+impl fmt::Display for VerificationType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            VerificationType::Top => write!(f, "top"),
+            VerificationType::Integer => write!(f, "int"),
+            VerificationType::Float => write!(f, "float"),
+            VerificationType::Double => write!(f, "double"),
+            VerificationType::Long => write!(f, "long"),
+            VerificationType::Null => write!(f, "null"),
+            VerificationType::UninitializedThis => write!(f, "uninitialized_this"),
+            VerificationType::Object(class_name) => write!(f, "class {class_name}"),
+            VerificationType::Uninitialized(offset) => write!(f, "uninitialized {offset}"),
+        }
+    }
+}
+
+impl fmt::Display for StackMapFrame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.program_counter)?;
+        match &self.frame_type {
+            StackMapFrameType::Same => write!(f, "same"),
+            StackMapFrameType::SameExtended => write!(f, "same_frame_extended"),
+            StackMapFrameType::SameLocals1StackItem(stack_item) => {
+                write!(f, "same_locals_1_stack_item stack = [ {stack_item} ]")
+            }
+            StackMapFrameType::SameLocals1StackItemExtended(stack_item) => {
+                write!(f, "same_locals_1_stack_item_frame_extended stack = [ {stack_item} ]")
+            }
+            StackMapFrameType::Chop(count) => write!(f, "chop {count}"),
+            StackMapFrameType::Append(locals) => write!(f, "append locals = [ {} ]", locals.iter().join(", ")),
+            StackMapFrameType::Full { locals, stack } => write!(
+                f,
+                "full_frame locals = [ {} ] stack = [ {} ]",
+                locals.iter().join(", "),
+                stack.iter().join(", ")
+            ),
+        }
+    }
+}
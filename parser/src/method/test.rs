@@ -1,7 +1,12 @@
+use crate::constant_pool::constant_pool::{ConstantPool, ConstantPoolEntry};
 use crate::error::ClassFileParserError;
+use crate::flags::MethodParameterFlags;
 use crate::method::descriptor::MethodDescriptor;
 use crate::method::exception_table::{ExceptionTable, ExceptionTableEntry};
 use crate::method::line_number_table::{LineNumberTable, LineNumberTableEntry};
+use crate::method::local_variable_table::{LocalVariable, LocalVariableTable};
+use crate::method::method_parameters::MethodParameter;
+use crate::method::stack_map_table::{StackMapFrame, StackMapFrameType, StackMapTable, VerificationType};
 use crate::utils::line_number::LineNumber;
 use crate::utils::pc::ProgramCounter;
 use crate::utils::types::{BaseType, Type};
@@ -141,4 +146,155 @@ fn can_lookup_line_number() {
     assert_eq!(LineNumber(5), table.lookup_pc(ProgramCounter(12)));
     assert_eq!(LineNumber(6), table.lookup_pc(ProgramCounter(20)));
     assert_eq!(LineNumber(6), table.lookup_pc(ProgramCounter(21)));
-}
\ No newline at end of file
+}
+
+fn stack_map_constants() -> ConstantPool {
+    let mut constants = ConstantPool::default();
+    constants.add_entry(ConstantPoolEntry::Utf8("java/lang/String".to_string())); // 1
+    constants.add_entry(ConstantPoolEntry::ClassReference(1)); // 2
+    constants
+}
+
+#[test]
+fn can_read_and_write_stack_map_table() {
+    let bytes = [
+        0, 5, // number_of_entries
+        3, // same, pc 3
+        64 + 2, 7, 0, 2, // same_locals_1_stack_item, pc 6
+        252, 0, 4, 1, // append int, pc 11
+        250, 0, 0, // chop 1, pc 12
+        255, 0, 100, 0, 2, 4, 8, 0, 3, 0, 1, 5, // full_frame, pc 113
+    ];
+    let constants = stack_map_constants();
+    let table = StackMapTable::read(&bytes, &constants).unwrap();
+    assert_eq!(
+        &[
+            StackMapFrame {
+                program_counter: ProgramCounter(3),
+                frame_type: StackMapFrameType::Same,
+            },
+            StackMapFrame {
+                program_counter: ProgramCounter(6),
+                frame_type: StackMapFrameType::SameLocals1StackItem(VerificationType::Object(
+                    "java/lang/String".to_string()
+                )),
+            },
+            StackMapFrame {
+                program_counter: ProgramCounter(11),
+                frame_type: StackMapFrameType::Append(vec![VerificationType::Integer]),
+            },
+            StackMapFrame {
+                program_counter: ProgramCounter(12),
+                frame_type: StackMapFrameType::Chop(1),
+            },
+            StackMapFrame {
+                program_counter: ProgramCounter(113),
+                frame_type: StackMapFrameType::Full {
+                    locals: vec![VerificationType::Long, VerificationType::Uninitialized(ProgramCounter(3))],
+                    stack: vec![VerificationType::Null],
+                },
+            },
+        ],
+        table.frames()
+    );
+    assert_eq!(Some(&table.frames()[2]), table.lookup(ProgramCounter(11)));
+    assert_eq!(None, table.lookup(ProgramCounter(10)));
+
+    let written = table.write(&mut |class_name| {
+        assert_eq!("java/lang/String", class_name);
+        Ok(2)
+    });
+    assert_eq!(Ok(bytes.to_vec()), written);
+}
+
+#[test]
+fn writes_extended_stack_map_frames_for_large_offsets() {
+    let table = StackMapTable::new(vec![
+        StackMapFrame {
+            program_counter: ProgramCounter(100),
+            frame_type: StackMapFrameType::Same,
+        },
+        StackMapFrame {
+            program_counter: ProgramCounter(200),
+            frame_type: StackMapFrameType::SameLocals1StackItem(VerificationType::Float),
+        },
+    ]);
+    let written = table.write(&mut |_| unreachable!()).unwrap();
+    assert_eq!(vec![0, 2, 251, 0, 100, 247, 0, 99, 2], written);
+
+    let unsorted = StackMapTable::new(vec![table.frames()[1].clone(), table.frames()[0].clone()]);
+    assert!(unsorted.write(&mut |_| unreachable!()).is_err());
+}
+
+#[test]
+fn cannot_read_invalid_stack_map_table() {
+    let constants = stack_map_constants();
+    // 128-246 是保留的 frame_type
+    assert!(StackMapTable::read(&[0, 1, 128], &constants).is_err());
+    // verification type 的 tag 最大为 8
+    assert!(StackMapTable::read(&[0, 1, 64, 9], &constants).is_err());
+    assert_eq!(
+        Err(ClassFileParserError::UnexpectedEndOfData),
+        StackMapTable::read(&[0, 1, 255, 0], &constants)
+    );
+}
+
+fn local_variable(start: u16, end: u16, slot: u16, name: &str, type_descriptor: Type) -> LocalVariable {
+    LocalVariable {
+        range: ProgramCounter(start)..ProgramCounter(end),
+        slot,
+        name: name.to_string(),
+        type_descriptor,
+        signature: None,
+    }
+}
+
+#[test]
+fn can_lookup_local_variables() {
+    // 两个作用域不重叠的局部变量复用了 slot 1
+    let table = LocalVariableTable::new(vec![
+        local_variable(0, 20, 0, "this", Type::Object("Foo".to_string())),
+        local_variable(2, 8, 1, "a", Type::Base(BaseType::Int)),
+        local_variable(10, 16, 1, "b", Type::Object("java/lang/String".to_string())),
+    ]);
+
+    assert_eq!("a", table.lookup(1, ProgramCounter(2)).unwrap().name);
+    assert_eq!("b", table.lookup(1, ProgramCounter(15)).unwrap().name);
+    // 范围不包括结束地址
+    assert_eq!(None, table.lookup(1, ProgramCounter(8)));
+    assert_eq!(None, table.lookup(2, ProgramCounter(2)));
+
+    let names: Vec<&str> = table.variables_at(ProgramCounter(12)).iter().map(|v| v.name.as_str()).collect();
+    assert_eq!(vec!["this", "b"], names);
+    assert_eq!(1, table.variables_at(ProgramCounter(9)).len());
+}
+
+#[test]
+fn can_read_method_parameters() {
+    let mut constants = ConstantPool::default();
+    constants.add_entry(ConstantPoolEntry::Utf8("count".to_string()));
+    // final int count, 以及一个没有名称的编译器生成的参数
+    let bytes = [2, 0, 1, 0, 0x10, 0, 0, 0x10, 0];
+    let parameters = MethodParameter::read_all(&bytes, &constants).unwrap();
+    assert_eq!(
+        vec![
+            MethodParameter {
+                name: Some("count".to_string()),
+                flags: MethodParameterFlags::FINAL,
+            },
+            MethodParameter {
+                name: None,
+                flags: MethodParameterFlags::SYNTHETIC,
+            },
+        ],
+        parameters
+    );
+    assert!(!parameters[0].is_implicit());
+    assert!(parameters[1].is_implicit());
+    assert_eq!("final count", parameters[0].to_string());
+
+    // 未知的 flag bits 由 ClassFileReader 检查, 这里原样保留
+    let parameters = MethodParameter::read_all(&[1, 0, 1, 0, 0x01], &constants).unwrap();
+    assert_eq!(0x0001, parameters[0].flags.bits());
+    assert_eq!(Err(ClassFileParserError::UnexpectedEndOfData), MethodParameter::read_all(&[1, 0, 1], &constants));
+}
@@ -0,0 +1,49 @@
+use std::fmt;
+use std::fmt::Formatter;
+
+use itertools::Itertools;
+
+use crate::error::ClassFileParserResult;
+use crate::signature::generic_type::{fmt_type_parameters, ClassTypeSignature, TypeParameter};
+use crate::signature::reader::SignatureReader;
+
+/// 类的泛型签名, 例如 `class Foo<T extends Number> extends Bar<T> implements Comparable<Foo<T>>`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassSignature {
+    pub type_parameters: Vec<TypeParameter>,
+    pub superclass: ClassTypeSignature,
+    pub interfaces: Vec<ClassTypeSignature>,
+}
+
+impl ClassSignature {
+    /// 解析类的签名, 例如 `<T:Ljava/lang/Number;>Ljava/lang/Object;Ljava/lang/Comparable<TT;>;`
+    /// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.9.1
+    pub fn parse(signature: &str) -> ClassFileParserResult<ClassSignature> {
+        let mut reader = SignatureReader::new(signature);
+        let type_parameters = reader.read_type_parameters()?;
+        let superclass = reader.read_class_type()?;
+        let mut interfaces = Vec::new();
+        while !reader.is_at_end() {
+            interfaces.push(reader.read_class_type()?);
+        }
+        Ok(ClassSignature {
+            type_parameters,
+            superclass,
+            interfaces,
+        })
+    }
+}
+
+impl fmt::Display for ClassSignature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fmt_type_parameters(f, &self.type_parameters)?;
+        if !self.type_parameters.is_empty() {
+            f.write_str(" ")?;
+        }
+        write!(f, "extends {}", self.superclass)?;
+        if !self.interfaces.is_empty() {
+            write!(f, " implements {}", self.interfaces.iter().join(", "))?;
+        }
+        Ok(())
+    }
+}
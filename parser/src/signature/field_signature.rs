@@ -0,0 +1,35 @@
+use std::fmt;
+use std::fmt::Formatter;
+
+use crate::error::ClassFileParserResult;
+use crate::signature::generic_type::{GenericType, TypeParameter};
+use crate::signature::reader::SignatureReader;
+use crate::utils::types::Type;
+
+/// 字段的泛型签名, 是 [Type] 的泛型版本, 只能是引用类型
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldSignature {
+    pub field_type: GenericType,
+}
+
+impl FieldSignature {
+    /// 解析字段的签名, 例如 `Ljava/util/List<Ljava/lang/String;>;`
+    /// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.9.1
+    pub fn parse(signature: &str) -> ClassFileParserResult<FieldSignature> {
+        let mut reader = SignatureReader::new(signature);
+        let field_type = reader.read_reference_type()?;
+        reader.expect_end()?;
+        Ok(FieldSignature { field_type })
+    }
+
+    /// 类型擦除, 字段中的类型变量来自类的类型形参
+    pub fn erasure(&self, class_type_parameters: &[TypeParameter]) -> Type {
+        self.field_type.erasure(class_type_parameters)
+    }
+}
+
+impl fmt::Display for FieldSignature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.field_type)
+    }
+}
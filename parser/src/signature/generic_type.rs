@@ -0,0 +1,183 @@
+use std::fmt;
+use std::fmt::Formatter;
+
+use itertools::Itertools;
+
+use crate::utils::types::{BaseType, Type};
+
+/// 带有泛型信息的类型, 对应 JVMS 中的 JavaTypeSignature, 是 [Type] 的泛型版本
+/// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.9.1
+#[derive(Debug, Clone, PartialEq)]
+pub enum GenericType {
+    /// Primitive types
+    Base(BaseType),
+
+    /// 类或者接口, 可能带有类型参数, 例如 `java.util.List<java.lang.String>`
+    Class(ClassTypeSignature),
+
+    /// 类型变量, 例如 `T`
+    TypeVariable(String),
+
+    /// Array
+    Array(Box<GenericType>),
+}
+
+/// 类或者接口类型, 例如 `Ljava/util/Map<TK;TV;>.Entry;`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassTypeSignature {
+    /// 带有包名的类名, 例如 `java/util/Map`
+    pub name: String,
+    pub type_arguments: Vec<TypeArgument>,
+    /// 内部类的后缀, 例如 `Outer<T>.Inner<U>` 中的 `Inner<U>`
+    pub inner_classes: Vec<InnerClassTypeSignature>,
+}
+
+/// [ClassTypeSignature] 中 `.` 之后的内部类
+#[derive(Debug, Clone, PartialEq)]
+pub struct InnerClassTypeSignature {
+    /// 不带外部类的简单类名
+    pub name: String,
+    pub type_arguments: Vec<TypeArgument>,
+}
+
+/// 类型实参, 例如 `List<? extends Number>` 中的 `? extends Number`
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypeArgument {
+    /// `?`
+    Wildcard,
+    /// `? extends T`
+    Extends(GenericType),
+    /// `? super T`
+    Super(GenericType),
+    /// 确定的类型, 例如 `List<String>` 中的 `String`
+    Exact(GenericType),
+}
+
+/// 类型形参, 例如 `<K extends Comparable<K> & Serializable>` 中的 `K`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeParameter {
+    pub name: String,
+    /// 类的上界, 只有接口上界时为 None
+    pub class_bound: Option<GenericType>,
+    pub interface_bounds: Vec<GenericType>,
+}
+
+impl GenericType {
+    /// 类型擦除, 得到对应的 [Type]; 类型变量被擦除为它的第一个上界,
+    /// 在 type_parameters 中找不到的类型变量被擦除为 `java/lang/Object`
+    pub fn erasure(&self, type_parameters: &[TypeParameter]) -> Type {
+        match self {
+            GenericType::Base(base) => Type::Base(base.clone()),
+            GenericType::Class(class) => Type::Object(class.erasure()),
+            GenericType::TypeVariable(name) => type_parameters
+                .iter()
+                .find(|parameter| &parameter.name == name)
+                .and_then(|parameter| parameter.first_bound())
+                // 类型变量的上界不能是它自己, 这里去掉它避免无限递归
+                .map(|bound| {
+                    let others: Vec<TypeParameter> = type_parameters
+                        .iter()
+                        .filter(|parameter| &parameter.name != name)
+                        .cloned()
+                        .collect();
+                    bound.erasure(&others)
+                })
+                .unwrap_or_else(|| Type::Object("java/lang/Object".to_string())),
+            GenericType::Array(component_type) => {
+                Type::Array(Box::new(component_type.erasure(type_parameters)))
+            }
+        }
+    }
+}
+
+impl ClassTypeSignature {
+    /// 擦除类型参数之后的类名, 内部类使用 `$` 连接, 例如 `java/util/Map$Entry`
+    pub fn erasure(&self) -> String {
+        let mut name = self.name.clone();
+        for inner_class in self.inner_classes.iter() {
+            name.push('$');
+            name.push_str(&inner_class.name);
+        }
+        name
+    }
+}
+
+impl TypeParameter {
+    pub fn first_bound(&self) -> Option<&GenericType> {
+        self.class_bound.as_ref().or(self.interface_bounds.first())
+    }
+}
+
+impl fmt::Display for GenericType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            GenericType::Base(base) => f.write_str(base.java_name()),
+            GenericType::Class(class) => write!(f, "{class}"),
+            GenericType::TypeVariable(name) => f.write_str(name),
+            GenericType::Array(component_type) => write!(f, "{component_type}[]"),
+        }
+    }
+}
+
+fn fmt_type_arguments(f: &mut Formatter<'_>, type_arguments: &[TypeArgument]) -> fmt::Result {
+    if type_arguments.is_empty() {
+        Ok(())
+    } else {
+        write!(f, "<{}>", type_arguments.iter().join(", "))
+    }
+}
+
+impl fmt::Display for ClassTypeSignature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name.replace('/', "."))?;
+        fmt_type_arguments(f, &self.type_arguments)?;
+        for inner_class in self.inner_classes.iter() {
+            write!(f, ".{}", inner_class.name)?;
+            fmt_type_arguments(f, &inner_class.type_arguments)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for TypeArgument {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            TypeArgument::Wildcard => f.write_str("?"),
+            TypeArgument::Extends(bound) => write!(f, "? extends {bound}"),
+            TypeArgument::Super(bound) => write!(f, "? super {bound}"),
+            TypeArgument::Exact(argument) => write!(f, "{argument}"),
+        }
+    }
+}
+
+impl fmt::Display for TypeParameter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)?;
+        let bounds: Vec<&GenericType> = self
+            .class_bound
+            .iter()
+            .chain(self.interface_bounds.iter())
+            .collect();
+        // 源码中省略的 `extends Object` 会被 javac 写入签名, 这里同样省略
+        let is_object = |bound: &GenericType| {
+            matches!(bound, GenericType::Class(class)
+                if class.name == "java/lang/Object" && class.type_arguments.is_empty() && class.inner_classes.is_empty())
+        };
+        if bounds.len() == 1 && is_object(bounds[0]) {
+            return Ok(());
+        }
+        if !bounds.is_empty() {
+            write!(f, " extends {}", bounds.iter().join(" & "))?;
+        }
+        Ok(())
+    }
+}
+
+/// 以 `<A, B extends C>` 的形式输出类型形参, 没有类型形参时什么都不输出
+pub(crate) fn fmt_type_parameters(f: &mut Formatter<'_>, type_parameters: &[TypeParameter]) -> fmt::Result {
+    if type_parameters.is_empty() {
+        Ok(())
+    } else {
+        write!(f, "<{}>", type_parameters.iter().join(", "))
+    }
+}
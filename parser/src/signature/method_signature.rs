@@ -0,0 +1,90 @@
+use std::fmt;
+use std::fmt::Formatter;
+
+use itertools::Itertools;
+
+use crate::error::ClassFileParserResult;
+use crate::method::descriptor::MethodDescriptor;
+use crate::signature::generic_type::{fmt_type_parameters, GenericType, TypeParameter};
+use crate::signature::reader::SignatureReader;
+
+/// 方法的泛型签名, 是 [MethodDescriptor] 的泛型版本, 额外包含了类型形参以及 throws 子句
+#[derive(Debug, Clone, PartialEq)]
+pub struct MethodSignature {
+    pub type_parameters: Vec<TypeParameter>,
+    pub parameters: Vec<GenericType>,
+    /// None 表示 void
+    pub return_type: Option<GenericType>,
+    /// 抛出的异常, 只能是类或者类型变量
+    pub thrown_exceptions: Vec<GenericType>,
+}
+
+impl MethodSignature {
+    /// 解析方法的签名, 例如 `<T:Ljava/lang/Exception;>(Ljava/util/List<TT;>;)V^TT;`
+    /// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.9.1
+    pub fn parse(signature: &str) -> ClassFileParserResult<MethodSignature> {
+        let mut reader = SignatureReader::new(signature);
+        let type_parameters = reader.read_type_parameters()?;
+        reader.expect('(')?;
+        let mut parameters = Vec::new();
+        while !reader.consume(')') {
+            parameters.push(reader.read_java_type()?);
+        }
+        let return_type = if reader.consume('V') {
+            None
+        } else {
+            Some(reader.read_java_type()?)
+        };
+        let mut thrown_exceptions = Vec::new();
+        while reader.consume('^') {
+            thrown_exceptions.push(reader.read_throws_type()?);
+        }
+        reader.expect_end()?;
+        Ok(MethodSignature {
+            type_parameters,
+            parameters,
+            return_type,
+            thrown_exceptions,
+        })
+    }
+
+    /// 类型擦除, 得到对应的 [MethodDescriptor];
+    /// 方法中的类型变量可能来自类的类型形参, 因此需要传入类的类型形参
+    pub fn erasure(&self, class_type_parameters: &[TypeParameter]) -> MethodDescriptor {
+        let type_parameters: Vec<TypeParameter> = self
+            .type_parameters
+            .iter()
+            .chain(class_type_parameters.iter())
+            .cloned()
+            .collect();
+        MethodDescriptor {
+            parameters: self
+                .parameters
+                .iter()
+                .map(|parameter| parameter.erasure(&type_parameters))
+                .collect(),
+            return_type: self
+                .return_type
+                .as_ref()
+                .map(|return_type| return_type.erasure(&type_parameters)),
+        }
+    }
+}
+
+impl fmt::Display for MethodSignature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        fmt_type_parameters(f, &self.type_parameters)?;
+        if !self.type_parameters.is_empty() {
+            f.write_str(" ")?;
+        }
+        match &self.return_type {
+            Some(return_type) => write!(f, "{return_type}")?,
+            None => f.write_str("void")?,
+        }
+        write!(f, " ({})", self.parameters.iter().join(", "))?;
+        if !self.thrown_exceptions.is_empty() {
+            write!(f, " throws {}", self.thrown_exceptions.iter().join(", "))?;
+        }
+        Ok(())
+    }
+}
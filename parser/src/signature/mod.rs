@@ -0,0 +1,7 @@
+pub mod class_signature;
+pub mod field_signature;
+pub mod generic_type;
+pub mod method_signature;
+pub(crate) mod reader;
+#[cfg(test)]
+mod test;
@@ -0,0 +1,251 @@
+use crate::error::{ClassFileParserError, ClassFileParserResult};
+use crate::signature::generic_type::{
+    ClassTypeSignature, GenericType, InnerClassTypeSignature, TypeArgument, TypeParameter,
+};
+use crate::utils::types::BaseType;
+
+/// JVMS 4.3.2: 数组类型最多有 255 维
+const MAX_ARRAY_DIMENSIONS: usize = 255;
+
+/// 类型实参与数组是递归读取和输出的, 限制两者合计的嵌套层数, 避免恶意构造的签名耗尽栈空间
+const MAX_NESTING_DEPTH: usize = 255;
+
+/// 按照 JVMS 4.7.9.1 的文法读取泛型签名, 每个 read 方法对应文法中的一个符号
+/// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.9.1
+pub(crate) struct SignatureReader<'a> {
+    signature: &'a str,
+    /// 当前读取到的字节位置
+    position: usize,
+    /// 当前位置外层的类型实参与数组维度的层数
+    depth: usize,
+}
+
+impl<'a> SignatureReader<'a> {
+    pub(crate) fn new(signature: &'a str) -> Self {
+        Self {
+            signature,
+            position: 0,
+            depth: 0,
+        }
+    }
+
+    /// 在当前位置出错, expected 描述了这里应该出现的内容
+    fn error(&self, expected: &str) -> ClassFileParserError {
+        ClassFileParserError::InvalidSignature {
+            signature: self.signature.to_string(),
+            position: self.position,
+            expected: expected.to_string(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.signature[self.position..].chars().next()
+    }
+
+    fn advance(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    pub(crate) fn is_at_end(&self) -> bool {
+        self.position >= self.signature.len()
+    }
+
+    /// 如果下一个字符是 expected 则读取它并返回 true
+    pub(crate) fn consume(&mut self, expected: char) -> bool {
+        if self.peek() == Some(expected) {
+            self.position += expected.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub(crate) fn expect(&mut self, expected: char) -> ClassFileParserResult<()> {
+        if self.consume(expected) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("`{expected}`")))
+        }
+    }
+
+    pub(crate) fn expect_end(&self) -> ClassFileParserResult<()> {
+        if self.is_at_end() {
+            Ok(())
+        } else {
+            Err(self.error("the end of the signature"))
+        }
+    }
+
+    /// 进入一层类型实参或者数组维度
+    fn enter(&mut self) -> ClassFileParserResult<()> {
+        if self.depth == MAX_NESTING_DEPTH {
+            return Err(self.error(&format!("at most {MAX_NESTING_DEPTH} nested types")));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Identifier: 不包含 `. ; [ / < > :` 的非空字符串
+    fn read_identifier(&mut self) -> ClassFileParserResult<String> {
+        let start = self.position;
+        while let Some(c) = self.peek() {
+            if matches!(c, '.' | ';' | '[' | '/' | '<' | '>' | ':') {
+                break;
+            }
+            self.advance();
+        }
+        if start == self.position {
+            Err(self.error("an identifier"))
+        } else {
+            Ok(self.signature[start..self.position].to_string())
+        }
+    }
+
+    /// TypeParameters: `<` TypeParameter {TypeParameter} `>`, 可以省略
+    pub(crate) fn read_type_parameters(&mut self) -> ClassFileParserResult<Vec<TypeParameter>> {
+        let mut type_parameters = Vec::new();
+        if self.consume('<') {
+            loop {
+                type_parameters.push(self.read_type_parameter()?);
+                if self.consume('>') {
+                    break;
+                }
+            }
+        }
+        Ok(type_parameters)
+    }
+
+    /// TypeParameter: Identifier `:` [ReferenceTypeSignature] {`:` ReferenceTypeSignature}
+    fn read_type_parameter(&mut self) -> ClassFileParserResult<TypeParameter> {
+        let name = self.read_identifier()?;
+        self.expect(':')?;
+        let class_bound = match self.peek() {
+            Some('L' | 'T' | '[') => Some(self.read_reference_type()?),
+            _ => None,
+        };
+        let mut interface_bounds = Vec::new();
+        while self.consume(':') {
+            interface_bounds.push(self.read_reference_type()?);
+        }
+        Ok(TypeParameter {
+            name,
+            class_bound,
+            interface_bounds,
+        })
+    }
+
+    /// JavaTypeSignature: ReferenceTypeSignature 或者 BaseType
+    pub(crate) fn read_java_type(&mut self) -> ClassFileParserResult<GenericType> {
+        let base_type = match self.peek() {
+            Some('B') => BaseType::Byte,
+            Some('C') => BaseType::Char,
+            Some('D') => BaseType::Double,
+            Some('F') => BaseType::Float,
+            Some('I') => BaseType::Int,
+            Some('J') => BaseType::Long,
+            Some('S') => BaseType::Short,
+            Some('Z') => BaseType::Boolean,
+            Some('L' | 'T' | '[') => return self.read_reference_type(),
+            _ => return Err(self.error("a type signature")),
+        };
+        self.advance();
+        Ok(GenericType::Base(base_type))
+    }
+
+    /// ReferenceTypeSignature: ClassTypeSignature, TypeVariableSignature 或者 ArrayTypeSignature
+    pub(crate) fn read_reference_type(&mut self) -> ClassFileParserResult<GenericType> {
+        match self.peek() {
+            Some('L') => Ok(GenericType::Class(self.read_class_type()?)),
+            Some('T') => self.read_type_variable(),
+            Some('[') => {
+                let mut dimensions = 0;
+                while self.peek() == Some('[') {
+                    if dimensions == MAX_ARRAY_DIMENSIONS {
+                        return Err(self.error(&format!("at most {MAX_ARRAY_DIMENSIONS} array dimensions")));
+                    }
+                    self.enter()?;
+                    self.advance();
+                    dimensions += 1;
+                }
+                let mut array_type = self.read_java_type()?;
+                self.depth -= dimensions;
+                for _ in 0..dimensions {
+                    array_type = GenericType::Array(Box::new(array_type));
+                }
+                Ok(array_type)
+            }
+            _ => Err(self.error("a reference type signature")),
+        }
+    }
+
+    /// ThrowsSignature 中 `^` 之后的部分: ClassTypeSignature 或者 TypeVariableSignature
+    pub(crate) fn read_throws_type(&mut self) -> ClassFileParserResult<GenericType> {
+        match self.peek() {
+            Some('L') => Ok(GenericType::Class(self.read_class_type()?)),
+            Some('T') => self.read_type_variable(),
+            _ => Err(self.error("a class type or type variable signature")),
+        }
+    }
+
+    /// TypeVariableSignature: `T` Identifier `;`
+    fn read_type_variable(&mut self) -> ClassFileParserResult<GenericType> {
+        self.expect('T')?;
+        let name = self.read_identifier()?;
+        self.expect(';')?;
+        Ok(GenericType::TypeVariable(name))
+    }
+
+    /// ClassTypeSignature: `L` [PackageSpecifier] SimpleClassTypeSignature {`.` SimpleClassTypeSignature} `;`
+    pub(crate) fn read_class_type(&mut self) -> ClassFileParserResult<ClassTypeSignature> {
+        self.expect('L')?;
+        let mut name = self.read_identifier()?;
+        while self.consume('/') {
+            name.push('/');
+            name.push_str(&self.read_identifier()?);
+        }
+        let type_arguments = self.read_type_arguments()?;
+        let mut inner_classes = Vec::new();
+        while self.consume('.') {
+            let name = self.read_identifier()?;
+            let type_arguments = self.read_type_arguments()?;
+            inner_classes.push(InnerClassTypeSignature {
+                name,
+                type_arguments,
+            });
+        }
+        self.expect(';')?;
+        Ok(ClassTypeSignature {
+            name,
+            type_arguments,
+            inner_classes,
+        })
+    }
+
+    /// TypeArguments: `<` TypeArgument {TypeArgument} `>`, 可以省略
+    fn read_type_arguments(&mut self) -> ClassFileParserResult<Vec<TypeArgument>> {
+        let mut type_arguments = Vec::new();
+        if self.peek() == Some('<') {
+            self.enter()?;
+            self.advance();
+            loop {
+                let type_argument = if self.consume('*') {
+                    TypeArgument::Wildcard
+                } else if self.consume('+') {
+                    TypeArgument::Extends(self.read_reference_type()?)
+                } else if self.consume('-') {
+                    TypeArgument::Super(self.read_reference_type()?)
+                } else {
+                    TypeArgument::Exact(self.read_reference_type()?)
+                };
+                type_arguments.push(type_argument);
+                if self.consume('>') {
+                    break;
+                }
+            }
+            self.depth -= 1;
+        }
+        Ok(type_arguments)
+    }
+}
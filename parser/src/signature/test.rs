@@ -0,0 +1,225 @@
+use crate::error::ClassFileParserError;
+use crate::method::descriptor::MethodDescriptor;
+use crate::signature::class_signature::ClassSignature;
+use crate::signature::field_signature::FieldSignature;
+use crate::signature::generic_type::{ClassTypeSignature, GenericType, InnerClassTypeSignature, TypeArgument};
+use crate::signature::method_signature::MethodSignature;
+use crate::utils::types::{BaseType, Type};
+
+fn class_type(name: &str, type_arguments: Vec<TypeArgument>) -> ClassTypeSignature {
+    ClassTypeSignature {
+        name: name.to_string(),
+        type_arguments,
+        inner_classes: Vec::new(),
+    }
+}
+
+#[test]
+fn can_parse_parameterized_field() {
+    assert_eq!(
+        Ok(FieldSignature {
+            field_type: GenericType::Class(class_type(
+                "java/util/List",
+                vec![TypeArgument::Exact(GenericType::Class(class_type("java/lang/String", vec![])))]
+            ))
+        }),
+        FieldSignature::parse("Ljava/util/List<Ljava/lang/String;>;")
+    );
+}
+
+#[test]
+fn can_parse_wildcards() {
+    let signature = FieldSignature::parse("Ljava/util/Map<*+TK;-[I>;").unwrap();
+    let GenericType::Class(class) = &signature.field_type else {
+        panic!("expected a class type");
+    };
+    assert_eq!(
+        vec![
+            TypeArgument::Wildcard,
+            TypeArgument::Extends(GenericType::TypeVariable("K".to_string())),
+            TypeArgument::Super(GenericType::Array(Box::new(GenericType::Base(BaseType::Int)))),
+        ],
+        class.type_arguments
+    );
+    assert_eq!("java.util.Map<?, ? extends K, ? super int[]>", signature.to_string());
+}
+
+#[test]
+fn can_parse_inner_classes() {
+    let signature = FieldSignature::parse("Lrjvm/Outer<TT;>.Inner.Nested<Ljava/lang/Integer;>;").unwrap();
+    let GenericType::Class(class) = &signature.field_type else {
+        panic!("expected a class type");
+    };
+    assert_eq!(
+        vec![
+            InnerClassTypeSignature {
+                name: "Inner".to_string(),
+                type_arguments: vec![],
+            },
+            InnerClassTypeSignature {
+                name: "Nested".to_string(),
+                type_arguments: vec![TypeArgument::Exact(GenericType::Class(class_type(
+                    "java/lang/Integer",
+                    vec![]
+                )))],
+            },
+        ],
+        class.inner_classes
+    );
+    assert_eq!("rjvm/Outer$Inner$Nested", class.erasure());
+    assert_eq!("rjvm.Outer<T>.Inner.Nested<java.lang.Integer>", signature.to_string());
+}
+
+#[test]
+fn can_parse_class_signature() {
+    let signature = ClassSignature::parse(
+        "<K::Ljava/lang/Comparable<TK;>;:Ljava/io/Serializable;V:Ljava/lang/Object;>\
+         Ljava/util/AbstractMap<TK;TV;>;Ljava/lang/Iterable<TV;>;",
+    )
+    .unwrap();
+    assert_eq!(2, signature.type_parameters.len());
+    assert_eq!(None, signature.type_parameters[0].class_bound);
+    assert_eq!(2, signature.type_parameters[0].interface_bounds.len());
+    assert_eq!(1, signature.interfaces.len());
+    assert_eq!(
+        "<K extends java.lang.Comparable<K> & java.io.Serializable, V> \
+         extends java.util.AbstractMap<K, V> implements java.lang.Iterable<V>",
+        signature.to_string()
+    );
+}
+
+#[test]
+fn can_parse_method_signature() {
+    let signature =
+        MethodSignature::parse("<T:Ljava/lang/Exception;>(Ljava/util/List<-TK;>;[I[TT;)TV;^TT;^Ljava/io/IOException;")
+            .unwrap();
+    assert_eq!(3, signature.parameters.len());
+    assert_eq!(Some(GenericType::TypeVariable("V".to_string())), signature.return_type);
+    assert_eq!(2, signature.thrown_exceptions.len());
+    assert_eq!(
+        "<T extends java.lang.Exception> V (java.util.List<? super K>, int[], T[]) throws T, java.io.IOException",
+        signature.to_string()
+    );
+    assert_eq!("void ()", MethodSignature::parse("()V").unwrap().to_string());
+}
+
+#[test]
+fn can_erase_method_signature() {
+    let class_signature = ClassSignature::parse("<K::Ljava/lang/Comparable<TK;>;V:Ljava/lang/Object;>Ljava/lang/Object;").unwrap();
+    let signature = MethodSignature::parse("<T:Ljava/lang/Exception;>(Ljava/util/List<-TK;>;[I[TT;)TV;").unwrap();
+    assert_eq!(
+        MethodDescriptor::parse("(Ljava/util/List;[I[Ljava/lang/Exception;)Ljava/lang/Object;").unwrap(),
+        signature.erasure(&class_signature.type_parameters)
+    );
+    assert_eq!(
+        Type::Object("java/lang/Comparable".to_string()),
+        FieldSignature::parse("TK;").unwrap().erasure(&class_signature.type_parameters)
+    );
+    assert_eq!(
+        Type::Object("java/lang/Object".to_string()),
+        FieldSignature::parse("TUnknown;").unwrap().erasure(&[])
+    );
+}
+
+fn invalid(signature: &str, position: usize, expected: &str) -> ClassFileParserError {
+    ClassFileParserError::InvalidSignature {
+        signature: signature.to_string(),
+        position,
+        expected: expected.to_string(),
+    }
+}
+
+#[test]
+fn cannot_parse_invalid_signatures() {
+    for (signature, position, expected) in [
+        ("", 0, "a reference type signature"),
+        ("I", 0, "a reference type signature"),
+        ("Ljava/util/List", 15, "`;`"),
+        ("Ljava/util/List<>;", 16, "a reference type signature"),
+        ("TT", 2, "`;`"),
+        ("Ljava/lang/Object;Ljava/lang/Object;", 18, "the end of the signature"),
+    ] {
+        assert_eq!(Err(invalid(signature, position, expected)), FieldSignature::parse(signature));
+    }
+    for (signature, position, expected) in [
+        ("(I", 2, "a type signature"),
+        ("()", 2, "a type signature"),
+        ("()VV", 3, "the end of the signature"),
+        ("<>()V", 1, "an identifier"),
+        ("(V)V", 1, "a type signature"),
+        ("()V^I", 4, "a class type or type variable signature"),
+    ] {
+        assert_eq!(Err(invalid(signature, position, expected)), MethodSignature::parse(signature));
+    }
+    for (signature, position, expected) in [("<T>Ljava/lang/Object;", 2, "`:`"), ("Ljava/lang/Object;I", 18, "`L`")] {
+        assert_eq!(Err(invalid(signature, position, expected)), ClassSignature::parse(signature));
+    }
+}
+
+#[test]
+fn cannot_parse_too_deeply_nested_signatures() {
+    let array = format!("{}TT;", "[".repeat(255));
+    let FieldSignature { field_type } = FieldSignature::parse(&array).unwrap();
+    assert!(matches!(field_type, GenericType::Array(_)));
+    let array = format!("{}TT;", "[".repeat(300));
+    assert_eq!(
+        Err(invalid(&array, 255, "at most 255 array dimensions")),
+        FieldSignature::parse(&array)
+    );
+
+    // List<List<...<T>...>>
+    let nested = |depth: usize| format!("{}TT;{};", "Ljava/util/List<".repeat(depth), ">;".repeat(depth - 1) + ">");
+    assert!(FieldSignature::parse(&nested(255)).is_ok());
+    let signature = nested(300);
+    let position = "Ljava/util/List<".len() * 255 + "Ljava/util/List".len();
+    assert_eq!(
+        Err(invalid(&signature, position, "at most 255 nested types")),
+        FieldSignature::parse(&signature)
+    );
+    // 类型实参与数组维度合计的嵌套层数也有限制: List<T[][]...[]>
+    let signature = format!("Ljava/util/List<{}TT;>;", "[".repeat(255));
+    assert_eq!(
+        Err(invalid(&signature, "Ljava/util/List<".len() + 254, "at most 255 nested types")),
+        FieldSignature::parse(&signature)
+    );
+}
+
+#[test]
+fn invalid_signature_position_is_a_byte_offset() {
+    // 反射输出中的类名可以包含非 ASCII 字符
+    let signature = "Lcom/example/Café<TT;>";
+    assert_eq!(
+        Err(invalid(signature, signature.len(), "`;`")),
+        FieldSignature::parse(signature)
+    );
+    assert_eq!(
+        "invalid generic signature \"TT\" at position 2: expected `;`",
+        FieldSignature::parse("TT").unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn can_parse_signatures_from_reflection() {
+    // java.util.stream.Collectors#toMap 与 java.lang.Enum 的签名
+    let signature = MethodSignature::parse(
+        "<T:Ljava/lang/Object;K:Ljava/lang/Object;U:Ljava/lang/Object;>(Ljava/util/function/Function<-TT;+TK;>;\
+         Ljava/util/function/Function<-TT;+TU;>;)Ljava/util/stream/Collector<TT;*Ljava/util/Map<TK;TU;>;>;",
+    )
+    .unwrap();
+    assert_eq!(
+        "<T, K, U> java.util.stream.Collector<T, ?, java.util.Map<K, U>> \
+         (java.util.function.Function<? super T, ? extends K>, java.util.function.Function<? super T, ? extends U>)",
+        signature.to_string()
+    );
+    let signature = ClassSignature::parse(
+        "<E:Ljava/lang/Enum<TE;>;>Ljava/lang/Object;Ljava/lang/constant/Constable;\
+         Ljava/lang/Comparable<TE;>;Ljava/io/Serializable;",
+    )
+    .unwrap();
+    assert_eq!(
+        "<E extends java.lang.Enum<E>> extends java.lang.Object \
+         implements java.lang.constant.Constable, java.lang.Comparable<E>, java.io.Serializable",
+        signature.to_string()
+    );
+}
+
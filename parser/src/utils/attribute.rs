@@ -1,8 +1,16 @@
 use std::fmt;
 use std::fmt::Formatter;
+use std::ops::Deref;
+
+use result::OptionResultExt;
+
+use crate::error::location::PathSegment;
+use crate::error::{ClassFileParserError, ClassFileParserResult};
+use crate::utils::base_type_convert::ToUsizeSafe;
+use crate::utils::buffer::Buffer;
 
 /// An attribute in the class file, which can belong to a class, field, method, or code block.
-#[derive(Debug, Default, PartialEq)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct Attribute {
     pub name: String,
     pub bytes: Vec<u8>,
@@ -12,4 +20,65 @@ impl fmt::Display for Attribute {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "{} (data = {} bytes)", self.name, self.bytes.len())
     }
-}
\ No newline at end of file
+}
+/// 读取到的原始属性以及每个属性的内容在输入中的偏移, 用于定位解码属性时出现的错误
+#[derive(Debug, Default)]
+pub(crate) struct LocatedAttributes {
+    attributes: Vec<Attribute>,
+    offsets: Vec<usize>,
+}
+
+impl LocatedAttributes {
+    pub(crate) fn push(&mut self, attribute: Attribute, offset: usize) {
+        self.attributes.push(attribute);
+        self.offsets.push(offset);
+    }
+
+    /// (属性, 属性的内容在输入中的偏移)
+    pub(crate) fn with_offsets(&self) -> impl Iterator<Item = (&Attribute, usize)> {
+        self.attributes.iter().zip(self.offsets.iter().copied())
+    }
+
+    /// 解码名为 name 的第一个属性, 出错时在路径中记录属性的名称。
+    /// decode 的第二个参数是属性内容的偏移, 用于读取属性中嵌套的结构
+    pub(crate) fn decode_with_offset<T>(
+        &self,
+        name: &str,
+        decode: impl FnOnce(&[u8], usize) -> ClassFileParserResult<T>,
+    ) -> ClassFileParserResult<Option<T>> {
+        self.with_offsets()
+            .find(|(attribute, _)| attribute.name == name)
+            .map(|(attribute, offset)| {
+                decode(&attribute.bytes, offset).map_err(|err| locate(err, attribute, offset))
+            })
+            .invert()
+    }
+
+    pub(crate) fn into_attributes(self) -> Vec<Attribute> {
+        self.attributes
+    }
+}
+
+impl Deref for LocatedAttributes {
+    type Target = [Attribute];
+
+    fn deref(&self) -> &[Attribute] {
+        &self.attributes
+    }
+}
+
+/// 解码 attribute 时出现的错误, 没有更精确的位置时指向属性内容的开始
+pub(crate) fn locate(err: ClassFileParserError, attribute: &Attribute, offset: usize) -> ClassFileParserError {
+    err.locate(offset, PathSegment::Attribute(attribute.name.clone()))
+}
+
+/// 按照声明的长度跳过 attributes_count 以及之后的所有属性, 不解析属性的名称, 返回属性的个数
+pub(crate) fn skip_attributes(buffer: &mut Buffer) -> ClassFileParserResult<u16> {
+    let attributes_count = buffer.read_u16()?;
+    for _ in 0..attributes_count {
+        buffer.read_u16()?;
+        let len = buffer.read_u32()?;
+        buffer.read_bytes(len.into_usize_safe())?;
+    }
+    Ok(attributes_count)
+}
@@ -1,23 +1,50 @@
-use cesu8::from_java_cesu8;
+use cesu8::{from_java_cesu8, to_java_cesu8};
 use crate::error::{ClassFileParserError, ClassFileParserResult};
 
 /// A buffer reader, used to marshall data from a generic byte array
 pub struct Buffer<'a> {
     buffer: &'a [u8],
     position: usize,
+    /// buffer 的第一个字节在整个输入中的偏移, 属性的内容使用单独的 Buffer 读取
+    offset: usize,
+    /// 最近一次读取开始的位置, 读取失败时同样会更新
+    last_read: usize,
 }
 
 
 impl<'a> Buffer<'a> {
     pub fn new(data: &'a [u8]) -> Self {
+        Self::with_offset(data, 0)
+    }
+
+    /// data 是整个输入中从 offset 开始的一段, 例如属性的内容
+    pub fn with_offset(data: &'a [u8], offset: usize) -> Self {
         Buffer {
             buffer: data,
             position: 0,
+            offset,
+            last_read: 0,
         }
     }
 
+    /// 下一个要读取的字节在整个输入中的偏移
+    pub fn offset(&self) -> usize {
+        self.offset + self.position
+    }
+
+    /// 最近一次读取的值在整个输入中的偏移, 读取或者校验失败时就是出错的值的位置
+    pub fn last_read_offset(&self) -> usize {
+        self.offset + self.last_read
+    }
+
+    /// 回到之前读取过的位置 offset, offset 是整个输入中的偏移
+    pub fn seek(&mut self, offset: usize) {
+        self.position = offset.saturating_sub(self.offset).min(self.buffer.len());
+    }
+
     /// 移动位置指针到 size 字节之后, 并读取 size 字节的数据
     fn advance(&mut self, size: usize) -> ClassFileParserResult<&'a [u8]> {
+        self.last_read = self.position;
         if self.position + size > self.buffer.len() {
             Err(ClassFileParserError::UnexpectedEndOfData)
         } else {
@@ -78,9 +105,77 @@ impl<'a> Buffer<'a> {
     }
 }
 
+/// 与 [Buffer] 相对应的写入器, 以大端序写入数据
+#[derive(Default)]
+pub struct BufferWriter {
+    buffer: Vec<u8>,
+}
+
+impl BufferWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    pub fn write_u8(&mut self, value: u8) {
+        self.buffer.push(value);
+    }
+
+    pub fn write_u16(&mut self, value: u16) {
+        self.buffer.extend_from_slice(&value.to_be_bytes());
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        self.buffer.extend_from_slice(&value.to_be_bytes());
+    }
+
+    pub fn write_i32(&mut self, value: i32) {
+        self.buffer.extend_from_slice(&value.to_be_bytes());
+    }
+
+    pub fn write_i64(&mut self, value: i64) {
+        self.buffer.extend_from_slice(&value.to_be_bytes());
+    }
+
+    pub fn write_f32(&mut self, value: f32) {
+        self.buffer.extend_from_slice(&value.to_be_bytes());
+    }
+
+    pub fn write_f64(&mut self, value: f64) {
+        self.buffer.extend_from_slice(&value.to_be_bytes());
+    }
+
+    /// 以 Java 的 modified UTF-8 写入字符串, 前面带有两个字节的长度
+    pub fn write_utf8(&mut self, value: &str) -> ClassFileParserResult<()> {
+        let bytes = to_java_cesu8(value);
+        let len = u16::try_from(bytes.len()).map_err(|_| ClassFileParserError::InvalidClassData {
+            name: format!("string of {} bytes is too long for the constant pool", bytes.len()),
+            is_invalidate_constant_pool_idx: false,
+        })?;
+        self.write_u16(len);
+        self.write_bytes(&bytes);
+        Ok(())
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.buffer
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::utils::buffer::Buffer;
+    use crate::utils::buffer::{Buffer, BufferWriter};
 
     #[test]
     fn buffer_works() {
@@ -93,4 +188,51 @@ mod tests {
 
         assert!(buffer.read_u32().is_err());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn buffer_tracks_offsets() {
+        let data = vec![0x00, 0x01, 0x02];
+        let mut buffer = Buffer::with_offset(&data, 0x10);
+        assert_eq!(0x10, buffer.offset());
+
+        assert_eq!(0x0001, buffer.read_u16().unwrap());
+        assert_eq!(0x10, buffer.last_read_offset());
+        assert_eq!(0x12, buffer.offset());
+
+        // 读取失败时 last_read_offset 指向读取开始的位置
+        assert!(buffer.read_u16().is_err());
+        assert_eq!(0x12, buffer.last_read_offset());
+        assert_eq!(0x12, buffer.offset());
+    }
+
+    #[test]
+    fn buffer_can_seek_back() {
+        let data = vec![0x00, 0x01, 0x02, 0x03];
+        let mut buffer = Buffer::with_offset(&data, 0x10);
+        assert_eq!(0x0001, buffer.read_u16().unwrap());
+        assert_eq!(0x0203, buffer.read_u16().unwrap());
+
+        buffer.seek(0x11);
+        assert_eq!(0x11, buffer.offset());
+        assert_eq!(0x0102, buffer.read_u16().unwrap());
+    }
+
+    #[test]
+    fn buffer_writer_works() {
+        let mut writer = BufferWriter::new();
+        writer.write_u8(1);
+        writer.write_u16(0x0203);
+        writer.write_i32(-2);
+        writer.write_utf8("a\0").unwrap();
+
+        let data = writer.into_bytes();
+        let mut buffer = Buffer::new(&data);
+        assert_eq!(1, buffer.read_u8().unwrap());
+        assert_eq!(0x0203, buffer.read_u16().unwrap());
+        assert_eq!(-2, buffer.read_i32().unwrap());
+        // Java 中的 \0 被编码为两个字节
+        assert_eq!(3, buffer.read_u16().unwrap());
+        assert_eq!("a\0", buffer.read_utf8(3).unwrap());
+        assert!(!buffer.has_more_data());
+    }
+}
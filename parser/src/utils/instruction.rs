@@ -1,10 +1,11 @@
 use crate::error::{ClassFileParserError, ClassFileParserResult};
-use crate::error::ClassFileParserError::ClassFileVersionError;
 
 /// Represents a Java bytecode instruction.
+/// 跳转类指令中的 u16 均为已经计算好的绝对地址, 而不是字节码中的相对偏移量
 //noinspection SpellCheckingInspection
 #[allow(non_camel_case_types)]
-#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[derive(Clone, Debug, Eq, PartialEq, strum_macros::IntoStaticStr)]
+#[strum(serialize_all = "lowercase")]
 pub enum Instruction {
     Aaload,
     Aastore,
@@ -91,7 +92,7 @@ pub enum Instruction {
     Getfield(u16),
     Getstatic(u16),
     Goto(u16),
-    Goto_w,
+    Goto_w(u16),
     I2b,
     I2c,
     I2d,
@@ -154,7 +155,7 @@ pub enum Instruction {
     Iushr,
     Ixor,
     Jsr(u16),
-    Jsr_w,
+    Jsr_w(u16),
     L2d,
     L2f,
     L2i,
@@ -176,7 +177,7 @@ pub enum Instruction {
     Lload_3,
     Lmul,
     Lneg,
-    Lookupswitch,
+    Lookupswitch(LookupSwitch),
     Lor,
     Lrem,
     Lreturn,
@@ -206,8 +207,8 @@ pub enum Instruction {
     Sastore,
     Sipush(i16),
     Swap,
-    Tableswitch,
-    Wide,
+    Tableswitch(TableSwitch),
+    Wide(WideInstruction),
 }
 
 /// Possible arguments of instruction `newarray`
@@ -223,7 +224,60 @@ pub enum NewArrayType {
     Long,
 }
 
+/// Arguments of instruction `tableswitch`, a jump table indexed by `low..=high`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TableSwitch {
+    /// 操作码之后用于 4 字节对齐的填充字节数, 取值 0 到 3
+    pub padding: u8,
+    pub default: u16,
+    pub low: i32,
+    pub high: i32,
+    /// 跳转地址, 第 i 个元素对应匹配值 low + i
+    pub offsets: Vec<u16>,
+}
+
+/// Arguments of instruction `lookupswitch`, a list of (match, jump address) pairs
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LookupSwitch {
+    /// 操作码之后用于 4 字节对齐的填充字节数, 取值 0 到 3
+    pub padding: u8,
+    pub default: u16,
+    /// 按照 match 值升序排列
+    pub match_offsets: Vec<(i32, u16)>,
+}
+
+/// Instructions that can be modified by `wide`, using a two bytes local variable index
+//noinspection SpellCheckingInspection
+#[derive(Clone, Copy, Debug, Eq, PartialEq, strum_macros::IntoStaticStr)]
+#[strum(serialize_all = "lowercase")]
+pub enum WideInstruction {
+    Iload(u16),
+    Lload(u16),
+    Fload(u16),
+    Dload(u16),
+    Aload(u16),
+    Istore(u16),
+    Lstore(u16),
+    Fstore(u16),
+    Dstore(u16),
+    Astore(u16),
+    Ret(u16),
+    Iinc(u16, i16),
+}
+
+impl WideInstruction {
+    /// 被 wide 修饰的指令的助记符, 例如 `iinc`
+    pub fn mnemonic(&self) -> &'static str {
+        self.into()
+    }
+}
+
 impl Instruction {
+    /// 指令的助记符, 与 javap 的输出一致, 例如 `iload_1`
+    pub fn mnemonic(&self) -> &'static str {
+        self.into()
+    }
+
     /// Reads one instruction from the bytecode, and returns it along
     /// with the address of the start of the next instruction
     pub fn parse(raw_code: &[u8], address: usize) -> ClassFileParserResult<(Self, usize)> {
@@ -315,7 +369,7 @@ impl Instruction {
             0xb4 => Instruction::Getfield(Self::read_u16(raw_code, &mut address)?),
             0xb2 => Instruction::Getstatic(Self::read_u16(raw_code, &mut address)?),
             0xa7 => Instruction::Goto(Self::read_offset(raw_code, &mut address)?),
-            0xc8 => todo!("OpCode::Goto_w"),
+            0xc8 => Instruction::Goto_w(Self::read_offset_w(raw_code, &mut address)?),
             0x91 => Instruction::I2b,
             0x92 => Instruction::I2c,
             0x87 => Instruction::I2d,
@@ -396,7 +450,7 @@ impl Instruction {
             0x7c => Instruction::Iushr,
             0x82 => Instruction::Ixor,
             0xa8 => Instruction::Jsr(Self::read_offset(raw_code, &mut address)?),
-            0xc9 => todo!("OpCode::Jsr_w"),
+            0xc9 => Instruction::Jsr_w(Self::read_offset_w(raw_code, &mut address)?),
             0x8a => Instruction::L2d,
             0x89 => Instruction::L2f,
             0x88 => Instruction::L2i,
@@ -418,7 +472,7 @@ impl Instruction {
             0x21 => Instruction::Lload_3,
             0x69 => Instruction::Lmul,
             0x75 => Instruction::Lneg,
-            0xab => todo!("OpCode::Lookupswitch"),
+            0xab => Instruction::Lookupswitch(Self::read_lookup_switch(raw_code, &mut address)?),
             0x81 => Instruction::Lor,
             0x71 => Instruction::Lrem,
             0xad => Instruction::Lreturn,
@@ -469,8 +523,8 @@ impl Instruction {
             0x56 => Instruction::Sastore,
             0x11 => Instruction::Sipush(Self::read_i16(raw_code, &mut address)?),
             0x5f => Instruction::Swap,
-            0xaa => todo!("OpCode::Tableswitch"),
-            0xc4 => todo!("OpCode::Wide"),
+            0xaa => Instruction::Tableswitch(Self::read_table_switch(raw_code, &mut address)?),
+            0xc4 => Instruction::Wide(Self::read_wide(raw_code, &mut address)?),
             _ => {
                 return Err(
                     ClassFileParserError::InvalidInstructionData(format!(
@@ -483,7 +537,7 @@ impl Instruction {
     }
 
     /// Parses all instructions in the given raw code.
-    pub(crate) fn parse_instructions(
+    pub fn parse_instructions(
         raw_code: &[u8],
     ) -> ClassFileParserResult<Vec<(usize, Instruction)>> {
         let mut instructions: Vec<(usize, Self)> = Vec::new();
@@ -517,7 +571,7 @@ impl Instruction {
 
     fn read_i8(raw_code: &[u8], address: &mut usize) -> ClassFileParserResult<i8> {
         let value = Self::read_u8(raw_code, address)?;
-        Ok(unsafe { std::mem::transmute(value) })
+        Ok(value as i8)
     }
 
     fn read_u16(raw_code: &[u8], address: &mut usize) -> ClassFileParserResult<u16> {
@@ -528,7 +582,13 @@ impl Instruction {
 
     fn read_i16(raw_code: &[u8], address: &mut usize) -> ClassFileParserResult<i16> {
         let value = Self::read_u16(raw_code, address)?;
-        Ok(unsafe { std::mem::transmute(value) })
+        Ok(value as i16)
+    }
+
+    fn read_i32(raw_code: &[u8], address: &mut usize) -> ClassFileParserResult<i32> {
+        let high = Self::read_u16(raw_code, address)? as u32;
+        let low = Self::read_u16(raw_code, address)? as u32;
+        Ok(((high << 16) | low) as i32)
     }
 
     fn read_offset(raw_code: &[u8], address: &mut usize) -> ClassFileParserResult<u16> {
@@ -539,4 +599,225 @@ impl Instruction {
             ClassFileParserError::InvalidOffsetError(*address)
         })
     }
+
+    fn read_offset_w(raw_code: &[u8], address: &mut usize) -> ClassFileParserResult<u16> {
+        let instruction_address = *address - 1;
+        let offset = Self::read_i32(raw_code, address)?;
+        Self::jump_address(instruction_address, offset, *address)
+    }
+
+    /// 跳转地址必须落在 u16 的范围内, 因为方法字节码的长度不能超过 65535
+    fn jump_address(instruction_address: usize, offset: i32, address: usize) -> ClassFileParserResult<u16> {
+        let jump_address = (instruction_address as i64) + (offset as i64);
+        u16::try_from(jump_address).map_err(|_| {
+            ClassFileParserError::InvalidOffsetError(address)
+        })
+    }
+
+    /// 跳过 switch 指令操作码之后的填充字节, 使得后续的参数从 4 字节对齐的地址开始
+    fn skip_switch_padding(raw_code: &[u8], address: &mut usize) -> ClassFileParserResult<u8> {
+        let padding = (4 - *address % 4) % 4;
+        for _ in 0..padding {
+            Self::read_u8(raw_code, address)?;
+        }
+        Ok(padding as u8)
+    }
+
+    /// 检查剩余字节是否足够容纳 count 个 entry_size 大小的表项, 避免根据错误的数据分配内存
+    fn check_switch_table_size(raw_code: &[u8], address: usize, count: i64, entry_size: i64) -> ClassFileParserResult<usize> {
+        let remaining = raw_code.len().saturating_sub(address) as i64;
+        if count < 0 || count.saturating_mul(entry_size) > remaining {
+            Err(ClassFileParserError::CanNotFindArgumentsError(address))
+        } else {
+            Ok(count as usize)
+        }
+    }
+
+    fn read_table_switch(raw_code: &[u8], address: &mut usize) -> ClassFileParserResult<TableSwitch> {
+        let instruction_address = *address - 1;
+        let padding = Self::skip_switch_padding(raw_code, address)?;
+        let default_offset = Self::read_i32(raw_code, address)?;
+        let default = Self::jump_address(instruction_address, default_offset, *address)?;
+        let low = Self::read_i32(raw_code, address)?;
+        let high = Self::read_i32(raw_code, address)?;
+        if low > high {
+            return Err(ClassFileParserError::InvalidInstructionData(
+                format!("tableswitch: low={low} is greater than high={high}"), *address,
+            ));
+        }
+        let count = Self::check_switch_table_size(raw_code, *address, high as i64 - low as i64 + 1, 4)?;
+        let mut offsets = Vec::with_capacity(count);
+        for _ in 0..count {
+            let offset = Self::read_i32(raw_code, address)?;
+            offsets.push(Self::jump_address(instruction_address, offset, *address)?);
+        }
+        Ok(TableSwitch {
+            padding,
+            default,
+            low,
+            high,
+            offsets,
+        })
+    }
+
+    fn read_lookup_switch(raw_code: &[u8], address: &mut usize) -> ClassFileParserResult<LookupSwitch> {
+        let instruction_address = *address - 1;
+        let padding = Self::skip_switch_padding(raw_code, address)?;
+        let default_offset = Self::read_i32(raw_code, address)?;
+        let default = Self::jump_address(instruction_address, default_offset, *address)?;
+        let pairs = Self::read_i32(raw_code, address)?;
+        let count = Self::check_switch_table_size(raw_code, *address, pairs as i64, 8)?;
+        let mut match_offsets = Vec::with_capacity(count);
+        for _ in 0..count {
+            let value = Self::read_i32(raw_code, address)?;
+            let offset = Self::read_i32(raw_code, address)?;
+            match_offsets.push((value, Self::jump_address(instruction_address, offset, *address)?));
+        }
+        Ok(LookupSwitch {
+            padding,
+            default,
+            match_offsets,
+        })
+    }
+
+    fn read_wide(raw_code: &[u8], address: &mut usize) -> ClassFileParserResult<WideInstruction> {
+        let op_byte = Self::read_u8(raw_code, address)?;
+        let index = Self::read_u16(raw_code, address)?;
+        Ok(match op_byte {
+            0x15 => WideInstruction::Iload(index),
+            0x16 => WideInstruction::Lload(index),
+            0x17 => WideInstruction::Fload(index),
+            0x18 => WideInstruction::Dload(index),
+            0x19 => WideInstruction::Aload(index),
+            0x36 => WideInstruction::Istore(index),
+            0x37 => WideInstruction::Lstore(index),
+            0x38 => WideInstruction::Fstore(index),
+            0x39 => WideInstruction::Dstore(index),
+            0x3a => WideInstruction::Astore(index),
+            0xa9 => WideInstruction::Ret(index),
+            0x84 => WideInstruction::Iinc(index, Self::read_i16(raw_code, address)?),
+            _ => {
+                return Err(ClassFileParserError::InvalidInstructionData(
+                    format!("wide: {op_byte:#04x}"), *address,
+                ))
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::ClassFileParserError;
+    use crate::utils::instruction::{Instruction, LookupSwitch, TableSwitch, WideInstruction};
+
+    #[test]
+    fn can_parse_table_switch() {
+        let code = vec![
+            0x00, // nop, so that the padding is two bytes
+            0xaa, 0x00, 0x00, // tableswitch + padding
+            0x00, 0x00, 0x00, 0x20, // default
+            0x00, 0x00, 0x00, 0x01, // low
+            0x00, 0x00, 0x00, 0x02, // high
+            0x00, 0x00, 0x00, 0x10, // 1
+            0xff, 0xff, 0xff, 0xff, // 2
+        ];
+        assert_eq!(
+            Ok((
+                Instruction::Tableswitch(TableSwitch {
+                    padding: 2,
+                    default: 0x21,
+                    low: 1,
+                    high: 2,
+                    offsets: vec![0x11, 0x00],
+                }),
+                code.len()
+            )),
+            Instruction::parse(&code, 1)
+        );
+    }
+
+    #[test]
+    fn can_parse_lookup_switch() {
+        let code = vec![
+            0xab, 0x00, 0x00, 0x00, // lookupswitch + padding
+            0x00, 0x00, 0x00, 0x30, // default
+            0x00, 0x00, 0x00, 0x02, // npairs
+            0xff, 0xff, 0xff, 0xfe, 0x00, 0x00, 0x00, 0x10, // -2
+            0x00, 0x00, 0x01, 0x00, 0x00, 0x00, 0x00, 0x20, // 256
+        ];
+        assert_eq!(
+            Ok((
+                Instruction::Lookupswitch(LookupSwitch {
+                    padding: 3,
+                    default: 0x30,
+                    match_offsets: vec![(-2, 0x10), (256, 0x20)],
+                }),
+                code.len()
+            )),
+            Instruction::parse(&code, 0)
+        );
+    }
+
+    #[test]
+    fn cannot_parse_truncated_switch() {
+        let code = vec![
+            0xaa, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x20,
+            0x80, 0x00, 0x00, 0x00, // low = i32::MIN
+            0x7f, 0xff, 0xff, 0xff, // high = i32::MAX
+        ];
+        assert_eq!(
+            Err(ClassFileParserError::CanNotFindArgumentsError(16)),
+            Instruction::parse(&code, 0)
+        );
+
+        let code = vec![
+            0xab, 0x00, 0x00, 0x00,
+            0x00, 0x00, 0x00, 0x20,
+            0xff, 0xff, 0xff, 0xff, // npairs = -1
+        ];
+        assert!(Instruction::parse(&code, 0).is_err());
+        assert!(Instruction::parse(&[0xab, 0x00], 0).is_err());
+    }
+
+    #[test]
+    fn can_parse_wide() {
+        assert_eq!(
+            Ok((Instruction::Wide(WideInstruction::Iload(0x0102)), 4)),
+            Instruction::parse(&[0xc4, 0x15, 0x01, 0x02], 0)
+        );
+        assert_eq!(
+            Ok((Instruction::Wide(WideInstruction::Iinc(3, -200)), 6)),
+            Instruction::parse(&[0xc4, 0x84, 0x00, 0x03, 0xff, 0x38], 0)
+        );
+        assert!(matches!(
+            Instruction::parse(&[0xc4, 0x60, 0x00, 0x03], 0),
+            Err(ClassFileParserError::InvalidInstructionData(_, _))
+        ));
+    }
+
+    #[test]
+    fn can_parse_wide_jumps() {
+        assert_eq!(
+            Ok((Instruction::Goto_w(0x0105), 6)),
+            Instruction::parse(&[0x00, 0xc8, 0x00, 0x00, 0x01, 0x04], 1)
+        );
+        assert_eq!(
+            Ok((Instruction::Jsr_w(0), 5)),
+            Instruction::parse(&[0xc9, 0x00, 0x00, 0x00, 0x00], 0)
+        );
+        assert_eq!(
+            Err(ClassFileParserError::InvalidOffsetError(5)),
+            Instruction::parse(&[0xc8, 0xff, 0xff, 0xff, 0x00], 0)
+        );
+    }
+
+    #[test]
+    fn can_get_mnemonics() {
+        assert_eq!("iload_1", Instruction::Iload_1.mnemonic());
+        assert_eq!("dup2_x1", Instruction::Dup2_x1.mnemonic());
+        assert_eq!("goto_w", Instruction::Goto_w(3).mnemonic());
+        assert_eq!("invokevirtual", Instruction::Invokevirtual(7).mnemonic());
+        assert_eq!("iinc", WideInstruction::Iinc(1, 1000).mnemonic());
+    }
 }
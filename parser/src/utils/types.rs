@@ -42,7 +42,46 @@ pub enum BaseType {
     Boolean,
 }
 
+impl BaseType {
+    /// 基本类型在描述符中对应的字符
+    pub fn descriptor(&self) -> char {
+        match self {
+            BaseType::Byte => 'B',
+            BaseType::Char => 'C',
+            BaseType::Double => 'D',
+            BaseType::Float => 'F',
+            BaseType::Int => 'I',
+            BaseType::Long => 'J',
+            BaseType::Short => 'S',
+            BaseType::Boolean => 'Z',
+        }
+    }
+
+    /// 基本类型在 Java 源码中的关键字, 例如 `int`
+    pub fn java_name(&self) -> &'static str {
+        match self {
+            BaseType::Byte => "byte",
+            BaseType::Char => "char",
+            BaseType::Double => "double",
+            BaseType::Float => "float",
+            BaseType::Int => "int",
+            BaseType::Long => "long",
+            BaseType::Short => "short",
+            BaseType::Boolean => "boolean",
+        }
+    }
+}
+
 impl Type {
+    /// 将类型转换回描述符, 是 [Type::parse] 的逆操作, 例如 `[Ljava/lang/String;`
+    pub fn descriptor(&self) -> String {
+        match self {
+            Type::Base(base) => base.descriptor().to_string(),
+            Type::Object(class) => format!("L{class};"),
+            Type::Array(component_type) => format!("[{}", component_type.descriptor()),
+        }
+    }
+
     /// Parses a type descriptor as specified in the JVM specs:
     /// https://docs.oracle.com/javase/specs/jvms/se7/html/jvms-4.html#jvms-4.3.2
     pub fn parse(type_descriptor: &str) -> ClassFileParserResult<Type> {
@@ -0,0 +1,397 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::fmt::Formatter;
+
+use crate::class::ClassFile;
+use crate::constant_pool::constant_pool::{ConstantPoolEntry, ReferenceKind};
+use crate::error::location::{ErrorPath, PathSegment};
+use crate::field::class_filed::{ClassFileField, FieldConstantValue};
+use crate::flags::{ClassAccessFlags, FieldFlags, MethodFlags};
+use crate::method::class_method::ClassFileMethod;
+use crate::utils::types::{BaseType, Type};
+
+#[cfg(test)]
+mod test;
+
+/// 违反了 JVMS 中的一条格式约束
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    /// 约束所在的 JVMS 章节, 例如 `4.6`
+    pub section: &'static str,
+    /// 违反约束的结构, 例如 `methods[2].access_flags`
+    pub path: ErrorPath,
+    pub message: String,
+}
+
+impl Violation {
+    /// 约束在 JVMS 中的链接
+    pub fn url(&self) -> String {
+        let chapter = self.section.split('.').next().unwrap_or_default();
+        format!(
+            "https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-{chapter}.html#jvms-{}",
+            self.section
+        )
+    }
+}
+
+/// 例如 `methods[2].access_flags: abstract method must not be ACC_FINAL (JVMS §4.6)`
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} (JVMS §{})", self.path, self.message, self.section)
+    }
+}
+
+/// 检查 JVMS 4.8 中的格式约束, 返回所有违反的约束, 合法的类返回空的 Vec。
+/// 解析器只拒绝无法读取的输入, 例如 flags 的组合、常量之间的引用以及 Code 属性是否存在都在这里检查
+/// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.8
+pub fn validate(class_file: &ClassFile) -> Vec<Violation> {
+    let mut validator = Validator {
+        class_file,
+        violations: Vec::new(),
+    };
+    validator.check_constants();
+    validator.check_class();
+    validator.check_fields();
+    validator.check_methods();
+    validator.violations
+}
+
+struct Validator<'a> {
+    class_file: &'a ClassFile,
+    violations: Vec<Violation>,
+}
+
+impl Validator<'_> {
+    fn report(&mut self, section: &'static str, segments: Vec<PathSegment>, message: String) {
+        self.violations.push(Violation {
+            section,
+            path: ErrorPath::new(segments),
+            message,
+        });
+    }
+
+    fn is_interface(&self) -> bool {
+        self.class_file.flags.contains(ClassAccessFlags::INTERFACE)
+    }
+
+    fn major_version(&self) -> u16 {
+        self.class_file.version.major_version()
+    }
+
+    /// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.1
+    fn check_class(&mut self) {
+        let flags = self.class_file.flags;
+        let path = || vec![PathSegment::Field("access_flags")];
+        if flags.contains(ClassAccessFlags::MODULE) {
+            let others = flags.difference(ClassAccessFlags::MODULE);
+            if !others.is_empty() {
+                self.report("4.1", path(), format!("module must not have other flags but has {others:#}"));
+            }
+            return;
+        }
+        if flags.contains(ClassAccessFlags::INTERFACE) {
+            if !flags.contains(ClassAccessFlags::ABSTRACT) {
+                self.report("4.1", path(), "interface must be ACC_ABSTRACT".to_string());
+            }
+            let conflicting =
+                flags.intersection(ClassAccessFlags::FINAL | ClassAccessFlags::SUPER | ClassAccessFlags::ENUM);
+            if !conflicting.is_empty() {
+                self.report("4.1", path(), format!("interface must not be {conflicting:#}"));
+            }
+            if self.class_file.superclass.as_deref() != Some("java/lang/Object") {
+                self.report(
+                    "4.1",
+                    vec![PathSegment::Field("super_class")],
+                    "the superclass of an interface must be java/lang/Object".to_string(),
+                );
+            }
+        } else {
+            if flags.contains(ClassAccessFlags::ANNOTATION) {
+                self.report("4.1", path(), "ACC_ANNOTATION requires ACC_INTERFACE".to_string());
+            }
+            if flags.contains(ClassAccessFlags::FINAL | ClassAccessFlags::ABSTRACT) {
+                self.report("4.1", path(), "class must not be both ACC_FINAL and ACC_ABSTRACT".to_string());
+            }
+        }
+        if self.class_file.superclass.is_none() && self.class_file.name != "java/lang/Object" {
+            self.report(
+                "4.1",
+                vec![PathSegment::Field("super_class")],
+                format!("only java/lang/Object has no superclass, but {} has none", self.class_file.name),
+            );
+        }
+    }
+
+    /// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.5
+    fn check_fields(&mut self) {
+        let mut signatures = HashSet::new();
+        for (index, field) in self.class_file.fields.iter().enumerate() {
+            let field_path = || PathSegment::Item("fields", index);
+            let path = || vec![field_path(), PathSegment::Field("access_flags")];
+            let flags = field.flags;
+            if flags.intersection(FieldFlags::ACCESS).bits().count_ones() > 1 {
+                self.report("4.5", path(), format!("field has more than one access flag: {flags:#}"));
+            }
+            if flags.contains(FieldFlags::FINAL | FieldFlags::VOLATILE) {
+                self.report("4.5", path(), "field must not be both ACC_FINAL and ACC_VOLATILE".to_string());
+            }
+            if self.is_interface() && flags.difference(FieldFlags::SYNTHETIC) != FieldFlags::INTERFACE_FIELD {
+                self.report(
+                    "4.5",
+                    path(),
+                    format!("interface field must be exactly ACC_PUBLIC, ACC_STATIC and ACC_FINAL but is {flags:#}"),
+                );
+            }
+            let descriptor = field.type_descriptor.descriptor();
+            if !signatures.insert((field.name.as_str(), descriptor.clone())) {
+                self.report(
+                    "4.5",
+                    vec![field_path()],
+                    format!("duplicate field {} {descriptor}", field.name),
+                );
+            }
+            self.check_constant_value(index, field);
+        }
+    }
+
+    /// ConstantValue 的类型必须与字段的类型一致
+    /// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.2
+    fn check_constant_value(&mut self, index: usize, field: &ClassFileField) {
+        let Some(constant_value) = &field.constant_value else {
+            return;
+        };
+        let matches = match (&field.type_descriptor, constant_value) {
+            (Type::Base(BaseType::Long), FieldConstantValue::Long(_)) => true,
+            (Type::Base(BaseType::Float), FieldConstantValue::Float(_)) => true,
+            (Type::Base(BaseType::Double), FieldConstantValue::Double(_)) => true,
+            (
+                Type::Base(BaseType::Int | BaseType::Short | BaseType::Char | BaseType::Byte | BaseType::Boolean),
+                FieldConstantValue::Int(_),
+            ) => true,
+            (Type::Object(class_name), FieldConstantValue::String(_)) => class_name == "java/lang/String",
+            _ => false,
+        };
+        if !matches {
+            self.report(
+                "4.7.2",
+                vec![PathSegment::Item("fields", index), PathSegment::Attribute("ConstantValue".to_string())],
+                format!("{constant_value} constant cannot initialize a field of type {}", field.type_descriptor),
+            );
+        }
+    }
+
+    /// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.6
+    fn check_methods(&mut self) {
+        let mut signatures = HashSet::new();
+        for (index, method) in self.class_file.methods.iter().enumerate() {
+            if !signatures.insert((method.name.as_str(), method.type_descriptor.as_str())) {
+                self.report(
+                    "4.6",
+                    vec![PathSegment::Item("methods", index)],
+                    format!("duplicate method {}{}", method.name, method.type_descriptor),
+                );
+            }
+            self.check_method_flags(index, method);
+            self.check_code(index, method);
+        }
+    }
+
+    fn check_method_flags(&mut self, index: usize, method: &ClassFileMethod) {
+        let path = || vec![PathSegment::Item("methods", index), PathSegment::Field("access_flags")];
+        let flags = method.flags;
+        // 类初始化方法除了 ACC_STRICT 之外的 flags 都被忽略, 51.0 之后必须是 ACC_STATIC 并且没有参数
+        // https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-2.html#jvms-2.9.2
+        if method.name == "<clinit>" {
+            if self.major_version() < 51 {
+                return;
+            }
+            if !flags.contains(MethodFlags::STATIC) {
+                self.report("2.9.2", path(), "class initialization method must be ACC_STATIC".to_string());
+            }
+            if method.type_descriptor != "()V" {
+                self.report(
+                    "2.9.2",
+                    vec![PathSegment::Item("methods", index)],
+                    format!("class initialization method must be ()V but is {}", method.type_descriptor),
+                );
+            }
+            return;
+        }
+
+        if flags.intersection(MethodFlags::ACCESS).bits().count_ones() > 1 {
+            self.report("4.6", path(), format!("method has more than one access flag: {flags:#}"));
+        }
+        if self.is_interface() {
+            let (allowed, required) = if self.major_version() < 52 {
+                (
+                    MethodFlags::PUBLIC
+                        | MethodFlags::ABSTRACT
+                        | MethodFlags::VARARGS
+                        | MethodFlags::BRIDGE
+                        | MethodFlags::SYNTHETIC,
+                    MethodFlags::PUBLIC | MethodFlags::ABSTRACT,
+                )
+            } else {
+                (
+                    MethodFlags::all().difference(
+                        MethodFlags::PROTECTED | MethodFlags::FINAL | MethodFlags::SYNCHRONIZED | MethodFlags::NATIVE,
+                    ),
+                    MethodFlags::empty(),
+                )
+            };
+            let forbidden = flags.difference(allowed);
+            if !forbidden.is_empty() {
+                self.report("4.6", path(), format!("interface method must not be {forbidden:#}"));
+            }
+            if !flags.contains(required) {
+                self.report("4.6", path(), format!("interface method must be {:#}", required.difference(flags)));
+            }
+            if self.major_version() >= 52 && !flags.intersects(MethodFlags::PUBLIC | MethodFlags::PRIVATE) {
+                self.report("4.6", path(), "interface method must be ACC_PUBLIC or ACC_PRIVATE".to_string());
+            }
+        }
+        if flags.contains(MethodFlags::ABSTRACT) {
+            let mut not_abstract = MethodFlags::NOT_ABSTRACT;
+            // ACC_STRICT 只在 46.0 到 60.0 之间有意义
+            if !(46..61).contains(&self.major_version()) {
+                not_abstract.remove(MethodFlags::STRICT);
+            }
+            let conflicting = flags.intersection(not_abstract);
+            if !conflicting.is_empty() {
+                self.report("4.6", path(), format!("abstract method must not be {conflicting:#}"));
+            }
+        }
+        if method.name == "<init>" {
+            let allowed = MethodFlags::ACCESS | MethodFlags::VARARGS | MethodFlags::STRICT | MethodFlags::SYNTHETIC;
+            let forbidden = flags.difference(allowed);
+            if !forbidden.is_empty() {
+                self.report("4.6", path(), format!("instance initialization method must not be {forbidden:#}"));
+            }
+        }
+    }
+
+    /// native 与 abstract 方法不能有 Code 属性, 其他方法必须有且只有一个
+    /// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.3
+    fn check_code(&mut self, index: usize, method: &ClassFileMethod) {
+        // 在内存中创建的方法只有 code 而没有原始的 Code 属性, 读取的方法两者都有, 只算一次
+        let raw_code_attributes = method.attributes.iter().filter(|attribute| attribute.name == "Code").count();
+        let code_attributes = raw_code_attributes.max(usize::from(method.code.is_some()));
+        let path = || vec![PathSegment::Item("methods", index), PathSegment::Attribute("Code".to_string())];
+        let has_no_code = method.flags.intersects(MethodFlags::NATIVE | MethodFlags::ABSTRACT);
+        if has_no_code && code_attributes > 0 {
+            let kind = method.flags.intersection(MethodFlags::NATIVE | MethodFlags::ABSTRACT);
+            self.report("4.7.3", path(), format!("{kind:#} method {} must not have a Code attribute", method.name));
+        } else if !has_no_code && code_attributes != 1 {
+            self.report(
+                "4.7.3",
+                path(),
+                format!("method {} must have exactly one Code attribute but has {code_attributes}", method.name),
+            );
+        }
+    }
+
+    /// 常量之间的引用必须指向正确种类的常量
+    /// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.4
+    fn check_constants(&mut self) {
+        let constants = &self.class_file.constants;
+        for (index, entry) in constants.iter() {
+            let path = || vec![PathSegment::Item("constant_pool", usize::from(index))];
+            for (section, target, expected) in self.references_of(entry) {
+                let found = match constants.get_entry(target) {
+                    Ok(found) if expected.contains(&constant_kind(found)) => continue,
+                    Ok(found) => format!("#{target} is a CONSTANT_{}", constant_kind(found)),
+                    Err(_) => format!("#{target} is not a valid index"),
+                };
+                let expected = expected.iter().map(|kind| format!("CONSTANT_{kind}")).collect::<Vec<_>>().join(" or ");
+                let message = format!("CONSTANT_{} must reference a {expected} but {found}", constant_kind(entry));
+                self.report(section, path(), message);
+            }
+            // 只有 NewInvokeSpecial 可以引用构造器, 任何句柄都不能引用类初始化方法
+            if let ConstantPoolEntry::MethodHandle(kind, reference) = entry {
+                let Ok((_, name, _)) = constants.member_reference_of(*reference) else {
+                    continue;
+                };
+                let is_constructor = *kind == ReferenceKind::NewInvokeSpecial;
+                let is_method = !matches!(
+                    kind,
+                    ReferenceKind::GetField
+                        | ReferenceKind::GetStatic
+                        | ReferenceKind::PutField
+                        | ReferenceKind::PutStatic
+                );
+                if is_constructor && name != "<init>" {
+                    let message = format!("REF_newInvokeSpecial must reference <init> but references {name}");
+                    self.report("4.4.8", path(), message);
+                } else if is_method && !is_constructor && (name == "<init>" || name == "<clinit>") {
+                    self.report("4.4.8", path(), format!("{kind} must not reference {name}"));
+                }
+            }
+        }
+    }
+
+    /// entry 引用的所有常量: (JVMS 章节, 引用的索引, 允许的常量种类)
+    fn references_of(&self, entry: &ConstantPoolEntry) -> Vec<(&'static str, u16, &'static [&'static str])> {
+        match entry {
+            ConstantPoolEntry::ClassReference(name) => vec![("4.4.1", *name, &["Utf8"])],
+            ConstantPoolEntry::FieldReference(class, name_and_type)
+            | ConstantPoolEntry::MethodReference(class, name_and_type)
+            | ConstantPoolEntry::InterfaceMethodReference(class, name_and_type) => {
+                vec![("4.4.2", *class, &["Class"]), ("4.4.2", *name_and_type, &["NameAndType"])]
+            }
+            ConstantPoolEntry::StringReference(string) => vec![("4.4.3", *string, &["Utf8"])],
+            ConstantPoolEntry::NameAndTypeDescriptor(name, descriptor) => {
+                vec![("4.4.6", *name, &["Utf8"]), ("4.4.6", *descriptor, &["Utf8"])]
+            }
+            ConstantPoolEntry::MethodHandle(kind, reference) => {
+                let expected: &'static [&'static str] = match kind {
+                    ReferenceKind::GetField
+                    | ReferenceKind::GetStatic
+                    | ReferenceKind::PutField
+                    | ReferenceKind::PutStatic => &["Fieldref"],
+                    ReferenceKind::InvokeVirtual | ReferenceKind::NewInvokeSpecial => &["Methodref"],
+                    // 52.0 之后可以引用接口中的静态方法与私有方法
+                    ReferenceKind::InvokeStatic | ReferenceKind::InvokeSpecial if self.major_version() < 52 => {
+                        &["Methodref"]
+                    }
+                    ReferenceKind::InvokeStatic | ReferenceKind::InvokeSpecial => &["Methodref", "InterfaceMethodref"],
+                    ReferenceKind::InvokeInterface => &["InterfaceMethodref"],
+                };
+                vec![("4.4.8", *reference, expected)]
+            }
+            ConstantPoolEntry::MethodType(descriptor) => vec![("4.4.9", *descriptor, &["Utf8"])],
+            ConstantPoolEntry::Dynamic(_, name_and_type) | ConstantPoolEntry::InvokeDynamic(_, name_and_type) => {
+                vec![("4.4.10", *name_and_type, &["NameAndType"])]
+            }
+            ConstantPoolEntry::ModuleReference(name) => vec![("4.4.11", *name, &["Utf8"])],
+            ConstantPoolEntry::PackageReference(name) => vec![("4.4.12", *name, &["Utf8"])],
+            ConstantPoolEntry::Utf8(_)
+            | ConstantPoolEntry::Integer(_)
+            | ConstantPoolEntry::Float(_)
+            | ConstantPoolEntry::Long(_)
+            | ConstantPoolEntry::Double(_) => Vec::new(),
+        }
+    }
+}
+
+/// 常量在 JVMS 中的名称, 去掉了 `CONSTANT_` 前缀
+fn constant_kind(entry: &ConstantPoolEntry) -> &'static str {
+    match entry {
+        ConstantPoolEntry::Utf8(_) => "Utf8",
+        ConstantPoolEntry::Integer(_) => "Integer",
+        ConstantPoolEntry::Float(_) => "Float",
+        ConstantPoolEntry::Long(_) => "Long",
+        ConstantPoolEntry::Double(_) => "Double",
+        ConstantPoolEntry::ClassReference(_) => "Class",
+        ConstantPoolEntry::StringReference(_) => "String",
+        ConstantPoolEntry::FieldReference(_, _) => "Fieldref",
+        ConstantPoolEntry::MethodReference(_, _) => "Methodref",
+        ConstantPoolEntry::InterfaceMethodReference(_, _) => "InterfaceMethodref",
+        ConstantPoolEntry::NameAndTypeDescriptor(_, _) => "NameAndType",
+        ConstantPoolEntry::MethodHandle(_, _) => "MethodHandle",
+        ConstantPoolEntry::MethodType(_) => "MethodType",
+        ConstantPoolEntry::Dynamic(_, _) => "Dynamic",
+        ConstantPoolEntry::InvokeDynamic(_, _) => "InvokeDynamic",
+        ConstantPoolEntry::ModuleReference(_) => "Module",
+        ConstantPoolEntry::PackageReference(_) => "Package",
+    }
+}
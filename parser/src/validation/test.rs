@@ -0,0 +1,138 @@
+use crate::class::ClassFile;
+use crate::constant_pool::constant_pool::{ConstantPoolEntry, ReferenceKind};
+use crate::error::location::{ErrorPath, PathSegment};
+use crate::flags::{ClassAccessFlags, MethodFlags};
+use crate::method::class_method::{ClassFileMethod, ClassFileMethodCode};
+use crate::method::descriptor::MethodDescriptor;
+use crate::validation::{validate, Violation};
+use crate::version::ClassFileVersion;
+
+/// 没有字段与方法的 java/lang/Object, 常量池在测试中填充
+fn object() -> ClassFile {
+    ClassFile {
+        name: "java/lang/Object".to_string(),
+        flags: ClassAccessFlags::PUBLIC | ClassAccessFlags::SUPER,
+        ..Default::default()
+    }
+}
+
+/// 在内存中创建的方法, 只有 code 而没有原始的 Code 属性
+fn method(flags: MethodFlags, name: &str, type_descriptor: &str) -> ClassFileMethod {
+    ClassFileMethod {
+        flags,
+        name: name.to_string(),
+        type_descriptor: type_descriptor.to_string(),
+        parsed_type_descriptor: MethodDescriptor::parse(type_descriptor).unwrap(),
+        attributes: Vec::new(),
+        code: Some(ClassFileMethodCode {
+            max_stack: 1,
+            max_locals: 0,
+            code: vec![0x04, 0xac],
+            ..Default::default()
+        }),
+        deprecated: false,
+        thrown_exceptions: Vec::new(),
+        signature: None,
+        annotations: Default::default(),
+        type_annotations: Default::default(),
+        parameter_annotations: Vec::new(),
+        annotation_default: None,
+        parameters: Vec::new(),
+        custom_attributes: Default::default(),
+    }
+}
+
+#[test]
+fn violation_display_and_url() {
+    let violation = Violation {
+        section: "4.6",
+        path: ErrorPath::new(vec![PathSegment::Item("methods", 2), PathSegment::Field("access_flags")]),
+        message: "abstract method must not be ACC_FINAL".to_string(),
+    };
+    assert_eq!(
+        "methods[2].access_flags: abstract method must not be ACC_FINAL (JVMS §4.6)",
+        violation.to_string()
+    );
+    assert_eq!("https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.6", violation.url());
+
+    let violation = Violation {
+        section: "2.9.2",
+        ..violation
+    };
+    assert_eq!("https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-2.html#jvms-2.9.2", violation.url());
+}
+
+#[test]
+fn class_without_superclass() {
+    assert!(validate(&object()).is_empty());
+
+    let class_file = ClassFile {
+        name: "a/B".to_string(),
+        ..object()
+    };
+    let violations = validate(&class_file);
+    assert_eq!(1, violations.len());
+    assert_eq!("4.1", violations[0].section);
+    assert_eq!("super_class", violations[0].path.to_string());
+}
+
+#[test]
+fn constant_references_of_wrong_kind() {
+    let mut class_file = object();
+    class_file.constants.add_entry(ConstantPoolEntry::Utf8("a/B".to_string()));
+    class_file.constants.add_entry(ConstantPoolEntry::Integer(7));
+    class_file.constants.add_entry(ConstantPoolEntry::ClassReference(2));
+    class_file.constants.add_entry(ConstantPoolEntry::StringReference(9));
+    class_file.constants.add_entry(ConstantPoolEntry::MethodHandle(ReferenceKind::GetField, 1));
+    class_file.constants.add_entry(ConstantPoolEntry::ClassReference(1));
+
+    let violations = validate(&class_file);
+    let messages: Vec<String> = violations.iter().map(|violation| violation.to_string()).collect();
+    assert_eq!(
+        vec![
+            "constant_pool[3]: CONSTANT_Class must reference a CONSTANT_Utf8 but #2 is a CONSTANT_Integer \
+             (JVMS §4.4.1)",
+            "constant_pool[4]: CONSTANT_String must reference a CONSTANT_Utf8 but #9 is not a valid index \
+             (JVMS §4.4.3)",
+            "constant_pool[5]: CONSTANT_MethodHandle must reference a CONSTANT_Fieldref but #1 is a CONSTANT_Utf8 \
+             (JVMS §4.4.8)",
+        ],
+        messages
+    );
+}
+
+#[test]
+fn class_built_in_memory() {
+    let mut class_file = object();
+    class_file.methods.push(method(MethodFlags::PUBLIC | MethodFlags::STATIC, "one", "()I"));
+    assert!(validate(&class_file).is_empty());
+
+    class_file.methods.push(method(MethodFlags::PUBLIC | MethodFlags::ABSTRACT, "two", "()I"));
+    let violations = validate(&class_file);
+    assert_eq!(1, violations.len());
+    assert_eq!("4.7.3", violations[0].section);
+    assert_eq!("methods[1].attributes[Code]", violations[0].path.to_string());
+}
+
+#[test]
+fn class_initialization_flags_are_ignored_before_51() {
+    let mut class_file = ClassFile {
+        name: "a/I".to_string(),
+        superclass: Some("java/lang/Object".to_string()),
+        flags: ClassAccessFlags::INTERFACE | ClassAccessFlags::ABSTRACT,
+        version: ClassFileVersion::new_unchecked(50, 0),
+        ..Default::default()
+    };
+    class_file.methods.push(method(MethodFlags::STATIC, "<clinit>", "()V"));
+    assert!(validate(&class_file).is_empty());
+
+    // 51.0 之前即使没有 ACC_STATIC 也不会被当作普通的接口方法检查
+    class_file.methods[0].flags = MethodFlags::empty();
+    assert!(validate(&class_file).is_empty());
+
+    class_file.version = ClassFileVersion::new_unchecked(52, 0);
+    let violations = validate(&class_file);
+    assert_eq!(1, violations.len());
+    assert_eq!("2.9.2", violations[0].section);
+    assert_eq!("class initialization method must be ACC_STATIC", violations[0].message);
+}
@@ -16,18 +16,34 @@ impl ClassFileVersion {
             major_version,
             minor_version,
         };
-        return match ClassFileVersion::parse_to_sdk_version(&version) {
-            Ok(v) => { Ok(version) }
+        match ClassFileVersion::parse_to_sdk_version(&version) {
+            Ok(_) => Ok(version),
             Err(_) => {
                 Err(ClassFileParserError::ClassFileVersionError(major_version, minor_version))
             }
         }
     }
+
+    /// 不检查版本, 宽松模式下保留未知的版本
+    pub(crate) fn new_unchecked(major_version: u16, minor_version: u16) -> Self {
+        Self {
+            major_version,
+            minor_version,
+        }
+    }
+
+    pub fn major_version(&self) -> u16 {
+        self.major_version
+    }
+
+    pub fn minor_version(&self) -> u16 {
+        self.minor_version
+    }
 }
 
 impl std::fmt::Display for ClassFileVersion {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:?}", ClassFileVersion::parse_to_sdk_version(&self))
+        write!(f, "{:?}", ClassFileVersion::parse_to_sdk_version(self))
     }
 }
 
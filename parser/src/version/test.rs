@@ -25,7 +25,7 @@ fn test_transform() {
         minor_version: 0,
     };
     let _ = ClassFileVersion::parse_to_sdk_version(&wrong_version).map_err(|e| {
-        info!("Err: {}", e.to_string());
+        info!("Err: {}", e);
     });
     assert_eq!(
         ClassFileVersion::parse_to_sdk_version(&wrong_version),
@@ -0,0 +1,227 @@
+use std::borrow::Cow;
+
+use bitflags::Flags;
+
+use crate::error::location::PathSegment;
+use crate::error::ClassFileParserResult;
+use crate::utils::attribute::{skip_attributes, Attribute};
+use crate::utils::base_type_convert::ToUsizeSafe;
+use crate::utils::buffer::Buffer;
+use crate::view::ConstantPoolView;
+
+/// 依次读取字段或者方法, 结构已经在 [crate::view::ClassFileView::parse] 中检查过
+pub struct Members<'a> {
+    constants: &'a ConstantPoolView<'a>,
+    buffer: Buffer<'a>,
+    remaining: u16,
+}
+
+impl<'a> Members<'a> {
+    pub(crate) fn new(constants: &'a ConstantPoolView<'a>, mut buffer: Buffer<'a>) -> Members<'a> {
+        let remaining = buffer.read_u16().unwrap_or_default();
+        Members { constants, buffer, remaining }
+    }
+
+    fn read_member(&mut self) -> ClassFileParserResult<MemberView<'a>> {
+        let access_flags = self.buffer.read_u16()?;
+        let name_index = self.buffer.read_u16()?;
+        let descriptor_index = self.buffer.read_u16()?;
+        let attributes = self.buffer.offset();
+        skip_attributes(&mut self.buffer)?;
+        Ok(MemberView {
+            constants: self.constants,
+            access_flags,
+            name_index,
+            descriptor_index,
+            attributes,
+        })
+    }
+}
+
+impl<'a> Iterator for Members<'a> {
+    type Item = MemberView<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.remaining = self.remaining.checked_sub(1)?;
+        self.read_member().ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::from(self.remaining), Some(usize::from(self.remaining)))
+    }
+}
+
+impl ExactSizeIterator for Members<'_> {}
+
+/// 字段或者方法, 名称、描述符与属性在访问时才解码
+/// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.6
+pub struct MemberView<'a> {
+    constants: &'a ConstantPoolView<'a>,
+    access_flags: u16,
+    name_index: u16,
+    descriptor_index: u16,
+    /// attributes_count 在输入中的位置
+    attributes: usize,
+}
+
+impl<'a> MemberView<'a> {
+    /// 字段使用 [crate::flags::FieldFlags], 方法使用 [crate::flags::MethodFlags], 未知的 flag bits 会被保留
+    pub fn flags<F: Flags<Bits = u16>>(&self) -> F {
+        F::from_bits_retain(self.access_flags)
+    }
+
+    pub fn name(&self) -> ClassFileParserResult<Cow<'a, str>> {
+        self.constants.utf8(self.name_index)
+    }
+
+    /// 未解析的描述符, 例如 `(I)Ljava/lang/String;`
+    pub fn descriptor(&self) -> ClassFileParserResult<Cow<'a, str>> {
+        self.constants.utf8(self.descriptor_index)
+    }
+
+    pub fn attributes(&self) -> Attributes<'a> {
+        let data = self.constants.data;
+        Attributes::new(self.constants, Buffer::with_offset(&data[self.attributes..], self.attributes))
+    }
+
+    /// 名为 name 的第一个属性
+    pub fn attribute(&self, name: &str) -> ClassFileParserResult<Option<AttributeView<'a>>> {
+        find_attribute(self.attributes(), name)
+    }
+
+    /// 方法的字节码, 不会复制 code 数组。抽象方法与 native 方法以及字段没有 Code 属性
+    pub fn code(&self) -> ClassFileParserResult<Option<CodeView<'a>>> {
+        let Some(attribute) = self.attribute("Code")? else {
+            return Ok(None);
+        };
+        CodeView::read(self.constants, &attribute)
+            .map(Some)
+            .map_err(|err| err.locate(attribute.offset, PathSegment::Attribute("Code".to_string())))
+    }
+}
+
+/// Code 属性中的 code 数组以及 Code 属性自己的属性
+/// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.7.3
+pub struct CodeView<'a> {
+    constants: &'a ConstantPoolView<'a>,
+    pub max_stack: u16,
+    pub max_locals: u16,
+    pub code: &'a [u8],
+    /// exception_table_length 在输入中的位置
+    exception_table: usize,
+    /// attributes_count 在输入中的位置
+    attributes: usize,
+}
+
+impl<'a> CodeView<'a> {
+    fn read(constants: &'a ConstantPoolView<'a>, attribute: &AttributeView<'a>) -> ClassFileParserResult<CodeView<'a>> {
+        let mut buf = Buffer::with_offset(attribute.bytes, attribute.offset);
+        let max_stack = buf.read_u16()?;
+        let max_locals = buf.read_u16()?;
+        let code_length = buf.read_u32()?.into_usize_safe();
+        let code = buf.read_bytes(code_length)?;
+        let exception_table = buf.offset();
+        let exception_table_length = buf.read_u16()?;
+        buf.read_bytes(usize::from(exception_table_length) * 4 * std::mem::size_of::<u16>())?;
+        let attributes = buf.offset();
+        skip_attributes(&mut buf)?;
+        Ok(CodeView {
+            constants,
+            max_stack,
+            max_locals,
+            code,
+            exception_table,
+            attributes,
+        })
+    }
+
+    /// exception_table 的条目个数, 条目在需要时使用 [crate::method::exception_table::ExceptionTable] 读取
+    pub fn exception_table_length(&self) -> u16 {
+        let data = self.constants.data;
+        u16::from_be_bytes([data[self.exception_table], data[self.exception_table + 1]])
+    }
+
+    /// 例如 LineNumberTable 与 StackMapTable
+    pub fn attributes(&self) -> Attributes<'a> {
+        let data = self.constants.data;
+        Attributes::new(self.constants, Buffer::with_offset(&data[self.attributes..], self.attributes))
+    }
+
+    pub fn attribute(&self, name: &str) -> ClassFileParserResult<Option<AttributeView<'a>>> {
+        find_attribute(self.attributes(), name)
+    }
+}
+
+/// 依次读取属性, 结构已经在创建时检查过
+pub struct Attributes<'a> {
+    constants: &'a ConstantPoolView<'a>,
+    buffer: Buffer<'a>,
+    remaining: u16,
+}
+
+impl<'a> Attributes<'a> {
+    pub(crate) fn new(constants: &'a ConstantPoolView<'a>, mut buffer: Buffer<'a>) -> Attributes<'a> {
+        let remaining = buffer.read_u16().unwrap_or_default();
+        Attributes { constants, buffer, remaining }
+    }
+
+    fn read_attribute(&mut self) -> ClassFileParserResult<AttributeView<'a>> {
+        let name_index = self.buffer.read_u16()?;
+        let len = self.buffer.read_u32()?;
+        let offset = self.buffer.offset();
+        let bytes = self.buffer.read_bytes(len.into_usize_safe())?;
+        Ok(AttributeView {
+            constants: self.constants,
+            name_index,
+            bytes,
+            offset,
+        })
+    }
+}
+
+impl<'a> Iterator for Attributes<'a> {
+    type Item = AttributeView<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.remaining = self.remaining.checked_sub(1)?;
+        self.read_attribute().ok()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::from(self.remaining), Some(usize::from(self.remaining)))
+    }
+}
+
+impl ExactSizeIterator for Attributes<'_> {}
+
+/// 名称在访问时才解码的属性, bytes 引用输入中属性的内容
+pub struct AttributeView<'a> {
+    constants: &'a ConstantPoolView<'a>,
+    name_index: u16,
+    pub bytes: &'a [u8],
+    /// 属性的内容在输入中的偏移
+    pub offset: usize,
+}
+
+impl<'a> AttributeView<'a> {
+    pub fn name(&self) -> ClassFileParserResult<Cow<'a, str>> {
+        self.constants.utf8(self.name_index)
+    }
+
+    /// 复制为 [Attribute]
+    pub fn to_attribute(&self) -> ClassFileParserResult<Attribute> {
+        Ok(Attribute {
+            name: self.name()?.into_owned(),
+            bytes: Vec::from(self.bytes),
+        })
+    }
+}
+
+fn find_attribute<'a>(attributes: Attributes<'a>, name: &str) -> ClassFileParserResult<Option<AttributeView<'a>>> {
+    for attribute in attributes {
+        if attribute.name()? == name {
+            return Ok(Some(attribute));
+        }
+    }
+    Ok(None)
+}
@@ -0,0 +1,278 @@
+use std::borrow::Cow;
+
+use cesu8::from_java_cesu8;
+
+use crate::class::ClassFile;
+use crate::class_parser::ClassFileReader;
+use crate::constant_pool::constant_pool::{ConstantPool, ConstantPoolEntry};
+use crate::error::location::PathSegment;
+use crate::error::{ClassFileParserError, ClassFileParserResult};
+use crate::flags::ClassAccessFlags;
+use crate::utils::attribute::skip_attributes;
+use crate::utils::buffer::Buffer;
+use crate::version::ClassFileVersion;
+use crate::view::member::{Attributes, MemberView, Members};
+
+pub mod member;
+#[cfg(test)]
+mod test;
+
+/// 不复制输入的 class 文件视图。创建时只检查结构并记录常量与各个表的位置,
+/// 常量、名称与属性在访问时才解码, 需要完整的 [ClassFile] 时使用 [ClassFileView::to_class_file]
+pub struct ClassFileView<'a> {
+    data: &'a [u8],
+    version: ClassFileVersion,
+    constants: ConstantPoolView<'a>,
+    /// access_flags 的位置, 之后依次是 this_class、super_class 与 interfaces_count
+    header: usize,
+    fields: usize,
+    methods: usize,
+    attributes: usize,
+}
+
+impl<'a> ClassFileView<'a> {
+    /// 检查 data 的结构, 与 [crate::read_buffer] 相同, 出错时错误带有出错的字节偏移与结构路径。
+    /// 常量的内容以及属性的名称在这里不会被检查
+    pub fn parse(data: &'a [u8]) -> ClassFileParserResult<ClassFileView<'a>> {
+        let mut buffer = Buffer::new(data);
+        let check_magic_number = match buffer.read_u32() {
+            Ok(0xCAFEBABE) => Ok(()),
+            Ok(_) => Err(ClassFileParserError::InvalidClassData {
+                name: "invalid magic number".to_owned(),
+                is_invalidate_constant_pool_idx: false,
+            }),
+            Err(err) => Err(err),
+        };
+        check_magic_number.map_err(|err| err.locate(0, PathSegment::Field("magic")))?;
+        let version = Self::read_version(&mut buffer).map_err(|err| err.locate(4, PathSegment::Field("version")))?;
+        let constants = ConstantPoolView::parse(data, &mut buffer)?;
+
+        let header = buffer.offset();
+        let skip_header = |buffer: &mut Buffer| {
+            buffer.read_bytes(3 * std::mem::size_of::<u16>())?;
+            let interfaces_count = buffer.read_u16()?;
+            buffer.read_bytes(usize::from(interfaces_count) * std::mem::size_of::<u16>())?;
+            Ok(())
+        };
+        skip_header(&mut buffer).map_err(|err| Self::locate(err, &buffer, PathSegment::Field("interfaces")))?;
+        let fields = buffer.offset();
+        Self::skip_members(&mut buffer, "fields")?;
+        let methods = buffer.offset();
+        Self::skip_members(&mut buffer, "methods")?;
+        let attributes = buffer.offset();
+        skip_attributes(&mut buffer).map_err(|err| Self::locate(err, &buffer, PathSegment::Field("attributes")))?;
+
+        Ok(ClassFileView {
+            data,
+            version,
+            constants,
+            header,
+            fields,
+            methods,
+            attributes,
+        })
+    }
+
+    fn read_version(buffer: &mut Buffer) -> ClassFileParserResult<ClassFileVersion> {
+        let minor_version = buffer.read_u16()?;
+        let major_version = buffer.read_u16()?;
+        ClassFileVersion::new(major_version, minor_version)
+    }
+
+    fn locate(err: ClassFileParserError, buffer: &Buffer, segment: PathSegment) -> ClassFileParserError {
+        err.locate(buffer.last_read_offset(), segment)
+    }
+
+    /// 字段与方法的结构相同: access_flags、name_index、descriptor_index 以及属性
+    fn skip_members(buffer: &mut Buffer, name: &'static str) -> ClassFileParserResult<()> {
+        let count = buffer
+            .read_u16()
+            .map_err(|err| Self::locate(err, buffer, PathSegment::Field(name)))?;
+        for index in 0..count {
+            let mut skip_member = || {
+                buffer.read_bytes(3 * std::mem::size_of::<u16>())?;
+                skip_attributes(buffer)
+            };
+            skip_member().map_err(|err| Self::locate(err, buffer, PathSegment::Item(name, usize::from(index))))?;
+        }
+        Ok(())
+    }
+
+    /// 读取 header 之后第 index 个 u16, 结构已经在创建时检查过
+    fn header_u16(&self, index: usize) -> u16 {
+        let offset = self.header + index * std::mem::size_of::<u16>();
+        u16::from_be_bytes([self.data[offset], self.data[offset + 1]])
+    }
+
+    /// 视图所引用的输入
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+
+    pub fn version(&self) -> ClassFileVersion {
+        self.version
+    }
+
+    /// 未知的 flag bits 会被保留
+    pub fn flags(&self) -> ClassAccessFlags {
+        ClassAccessFlags::from_bits_retain(self.header_u16(0))
+    }
+
+    pub fn constants(&self) -> &ConstantPoolView<'a> {
+        &self.constants
+    }
+
+    /// 类的内部名称, 例如 `java/lang/String`
+    pub fn name(&self) -> ClassFileParserResult<Cow<'a, str>> {
+        self.constants.class_name(self.header_u16(1))
+    }
+
+    /// java/lang/Object 与 module-info 没有父类
+    pub fn superclass(&self) -> ClassFileParserResult<Option<Cow<'a, str>>> {
+        match self.header_u16(2) {
+            0 => Ok(None),
+            index => self.constants.class_name(index).map(Some),
+        }
+    }
+
+    pub fn interfaces(&self) -> impl Iterator<Item = ClassFileParserResult<Cow<'a, str>>> + '_ {
+        (0..usize::from(self.header_u16(3))).map(|index| self.constants.class_name(self.header_u16(4 + index)))
+    }
+
+    pub fn fields(&self) -> Members<'_> {
+        Members::new(&self.constants, Buffer::with_offset(&self.data[self.fields..], self.fields))
+    }
+
+    pub fn methods(&self) -> Members<'_> {
+        Members::new(&self.constants, Buffer::with_offset(&self.data[self.methods..], self.methods))
+    }
+
+    /// 类的属性, 例如 SourceFile
+    pub fn attributes(&self) -> Attributes<'_> {
+        Attributes::new(&self.constants, Buffer::with_offset(&self.data[self.attributes..], self.attributes))
+    }
+
+    /// 按照方法的名称与描述符查找, 例如 `("main", "([Ljava/lang/String;)V")`
+    pub fn method(&self, name: &str, descriptor: &str) -> ClassFileParserResult<Option<MemberView<'_>>> {
+        for method in self.methods() {
+            if method.name()? == name && method.descriptor()? == descriptor {
+                return Ok(Some(method));
+            }
+        }
+        Ok(None)
+    }
+
+    /// 解码所有的内容, 与 [crate::read_buffer] 的结果相同
+    pub fn to_class_file(&self) -> ClassFileParserResult<ClassFile> {
+        ClassFileReader::new(self.data).read()
+    }
+}
+
+/// 只记录每个常量位置的常量池, 常量在访问时才解码
+pub struct ConstantPoolView<'a> {
+    data: &'a [u8],
+    /// 每个 slot 的常量在输入中的位置, long 和 double 的第二个 slot 为 None
+    offsets: Vec<Option<usize>>,
+}
+
+impl<'a> ConstantPoolView<'a> {
+    fn parse(data: &'a [u8], buffer: &mut Buffer) -> ClassFileParserResult<ConstantPoolView<'a>> {
+        let constants_count = buffer
+            .read_u16()
+            .map_err(|err| err.locate(buffer.last_read_offset(), PathSegment::Field("constant_pool_count")))?;
+        let mut offsets = Vec::with_capacity(usize::from(constants_count));
+        while offsets.len() + 1 < usize::from(constants_count) {
+            let offset = buffer.offset();
+            let segment = PathSegment::Item("constant_pool", offsets.len() + 1);
+            let tag = Self::skip_constant(buffer).map_err(|err| err.locate(buffer.last_read_offset(), segment))?;
+            offsets.push(Some(offset));
+            // long and double constants take up two slots in the pool
+            if matches!(tag, 5 | 6) {
+                offsets.push(None);
+            }
+        }
+        Ok(ConstantPoolView { data, offsets })
+    }
+
+    /// 跳过一个常量, 返回常量的 tag
+    /// https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.4
+    fn skip_constant(buffer: &mut Buffer) -> ClassFileParserResult<u8> {
+        let tag = buffer.read_u8()?;
+        let size = match tag {
+            1 => usize::from(buffer.read_u16()?),
+            7 | 8 | 16 | 19 | 20 => 2,
+            15 => 3,
+            3 | 4 | 9 | 10 | 11 | 12 | 17 | 18 => 4,
+            5 | 6 => 8,
+            _ => {
+                return Err(ClassFileParserError::InvalidClassData {
+                    name: format!("Unknown constant type: 0x{tag:X}"),
+                    is_invalidate_constant_pool_idx: false,
+                })
+            }
+        };
+        buffer.read_bytes(size)?;
+        Ok(tag)
+    }
+
+    /// 常量池占据的 slot 数量, 与 [ConstantPool::len] 相同
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// 常量在输入中的位置, 索引规则与 [ConstantPool::get_entry] 相同
+    fn offset_of(&self, index: u16) -> ClassFileParserResult<usize> {
+        match usize::from(index).checked_sub(1).and_then(|slot| self.offsets.get(slot)) {
+            Some(Some(offset)) => Ok(*offset),
+            Some(None) => Err(ClassFileParserError::ConstantPoolIndexToPhantomEntryError(index)),
+            None => Err(ClassFileParserError::WrongConstantPoolIndexError(index)),
+        }
+    }
+
+    /// 读取 tag 为 expected 的常量的内容
+    fn content_of(&self, index: u16, expected: u8, kind: &str) -> ClassFileParserResult<Buffer<'a>> {
+        let offset = self.offset_of(index)?;
+        if self.data[offset] != expected {
+            return Err(ClassFileParserError::InvalidClassData {
+                name: format!("expected a {kind} constant at index {index} but found {:?}", self.entry(index)?),
+                is_invalidate_constant_pool_idx: true,
+            });
+        }
+        Ok(Buffer::with_offset(&self.data[offset + 1..], offset + 1))
+    }
+
+    /// 解码单个常量
+    pub fn entry(&self, index: u16) -> ClassFileParserResult<ConstantPoolEntry> {
+        ClassFileReader::read_constant_at(self.data, self.offset_of(index)?, index)
+    }
+
+    /// Utf8 常量的内容, 只有包含 modified UTF-8 特有的编码时才需要复制
+    pub fn utf8(&self, index: u16) -> ClassFileParserResult<Cow<'a, str>> {
+        let mut buffer = self.content_of(index, 1, "Utf8")?;
+        let len = buffer.read_u16()?;
+        let bytes = buffer.read_bytes(usize::from(len))?;
+        from_java_cesu8(bytes).map_err(|_| ClassFileParserError::InvalidCesu8String)
+    }
+
+    /// 解析 Class 常量, 返回类的内部名称, 与 [ConstantPool::class_name_of] 相同
+    pub fn class_name(&self, index: u16) -> ClassFileParserResult<Cow<'a, str>> {
+        let name_index = self.content_of(index, 7, "Class")?.read_u16()?;
+        self.utf8(name_index)
+    }
+
+    /// 解码所有常量
+    pub fn to_constant_pool(&self) -> ClassFileParserResult<ConstantPool> {
+        let mut constants = ConstantPool::default();
+        for (slot, offset) in self.offsets.iter().enumerate() {
+            if let Some(offset) = offset {
+                let index = (slot + 1) as u16;
+                constants.add_entry(ClassFileReader::read_constant_at(self.data, *offset, index)?);
+            }
+        }
+        Ok(constants)
+    }
+}
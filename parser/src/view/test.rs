@@ -0,0 +1,52 @@
+use std::borrow::Cow;
+
+use crate::constant_pool::constant_pool::ConstantPoolEntry;
+use crate::error::ClassFileParserError;
+use crate::utils::buffer::{Buffer, BufferWriter};
+use crate::view::ConstantPoolView;
+
+/// 常量池: #1 Utf8 "a/B", #2 Class #1, #3 Long 7 (占据 #3 与 #4), #5 Utf8 "\0"
+fn constant_pool() -> Vec<u8> {
+    let mut writer = BufferWriter::new();
+    writer.write_u16(6);
+    writer.write_u8(1);
+    writer.write_utf8("a/B").unwrap();
+    writer.write_u8(7);
+    writer.write_u16(1);
+    writer.write_u8(5);
+    writer.write_i64(7);
+    writer.write_u8(1);
+    writer.write_utf8("\0").unwrap();
+    writer.into_bytes()
+}
+
+#[test]
+fn can_decode_constants_on_demand() {
+    let data = constant_pool();
+    let constants = ConstantPoolView::parse(&data, &mut Buffer::new(&data)).unwrap();
+
+    assert_eq!(5, constants.len());
+    assert!(matches!(constants.utf8(1), Ok(Cow::Borrowed("a/B"))));
+    assert_eq!("a/B", constants.class_name(2).unwrap());
+    assert_eq!(ConstantPoolEntry::Long(7), constants.entry(3).unwrap());
+    // modified UTF-8 中的 \0 使用两个字节编码, 只有这时才需要复制
+    assert!(matches!(constants.utf8(5), Ok(Cow::Owned(text)) if text == "\0"));
+
+    let pool = constants.to_constant_pool().unwrap();
+    assert_eq!(5, pool.len());
+    assert_eq!("a/B", pool.class_name_of(2).unwrap());
+}
+
+#[test]
+fn invalid_constant_indexes() {
+    let data = constant_pool();
+    let constants = ConstantPoolView::parse(&data, &mut Buffer::new(&data)).unwrap();
+
+    assert_eq!(Err(ClassFileParserError::WrongConstantPoolIndexError(0)), constants.utf8(0));
+    assert_eq!(Err(ClassFileParserError::WrongConstantPoolIndexError(6)), constants.entry(6));
+    assert_eq!(Err(ClassFileParserError::ConstantPoolIndexToPhantomEntryError(4)), constants.entry(4));
+    assert!(matches!(
+        constants.class_name(1),
+        Err(ClassFileParserError::InvalidClassData { is_invalidate_constant_pool_idx: true, .. })
+    ));
+}
@@ -0,0 +1,62 @@
+package rjvm;
+
+import java.lang.annotation.ElementType;
+import java.lang.annotation.Retention;
+import java.lang.annotation.RetentionPolicy;
+
+@Annotations.Entity(name = "users", tags = {"a", "b"}, kind = ElementType.TYPE, type = String.class,
+        index = @Annotations.Index(columns = {"id"}))
+@Annotations.Audited
+public class Annotations {
+    @Retention(RetentionPolicy.RUNTIME)
+    public @interface Entity {
+        String name() default "";
+
+        String[] tags() default {};
+
+        ElementType kind() default ElementType.FIELD;
+
+        Class<?> type() default void.class;
+
+        Index index() default @Index(columns = {});
+
+        int priority() default 5;
+    }
+
+    @Retention(RetentionPolicy.RUNTIME)
+    public @interface Index {
+        String[] columns();
+    }
+
+    @Retention(RetentionPolicy.CLASS)
+    public @interface Audited {
+    }
+
+    @Retention(RetentionPolicy.RUNTIME)
+    public @interface Test {
+    }
+
+    @Retention(RetentionPolicy.RUNTIME)
+    public @interface Column {
+        byte b() default 1;
+
+        char c() default 'c';
+
+        short s() default 2;
+
+        long l() default 3L;
+
+        float f() default 4.5f;
+
+        double d() default 6.5;
+
+        boolean z() default true;
+    }
+
+    @Column(b = -1, c = 'x', s = 300, l = 1L << 40, f = 1.5f, d = 2.25, z = false)
+    private String name;
+
+    @Test
+    public void shouldSave(@Column String table, int count, @Audited @Column(b = 7) long id) {
+    }
+}
@@ -0,0 +1,38 @@
+package rjvm;
+
+import java.io.IOException;
+import java.io.Serializable;
+import java.util.AbstractMap;
+import java.util.Collections;
+import java.util.Iterator;
+import java.util.List;
+import java.util.Map;
+import java.util.Set;
+
+public class Generics<K extends Comparable<K> & Serializable, V> extends AbstractMap<K, V>
+        implements Iterable<Map.Entry<K, V>> {
+    private List<? extends Number> numbers;
+    private Map<String, List<? super Integer>>[] maps;
+    private V value;
+    public Inner<String>.Nested<Integer> nested;
+    private int plain;
+
+    public class Inner<A> {
+        public class Nested<B> {
+        }
+    }
+
+    public <T extends Exception> V compute(List<? super K> keys, int[] counts, T[] errors) throws T, IOException {
+        return value;
+    }
+
+    @Override
+    public Set<Map.Entry<K, V>> entrySet() {
+        return Collections.emptySet();
+    }
+
+    @Override
+    public Iterator<Map.Entry<K, V>> iterator() {
+        return entrySet().iterator();
+    }
+}
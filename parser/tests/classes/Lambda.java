@@ -0,0 +1,24 @@
+package rjvm;
+
+import java.util.function.IntSupplier;
+import java.util.function.Supplier;
+
+public class Lambda {
+    private final int base;
+
+    public Lambda(int base) {
+        this.base = base;
+    }
+
+    public IntSupplier supplier() {
+        return () -> base + 1;
+    }
+
+    public Supplier<String> describe(String name) {
+        return () -> "Lambda " + name + " with base " + base;
+    }
+
+    public static Runnable reference() {
+        return System.out::println;
+    }
+}
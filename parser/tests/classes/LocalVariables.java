@@ -0,0 +1,28 @@
+package rjvm;
+
+import java.util.ArrayList;
+import java.util.List;
+
+public class LocalVariables {
+    public static <T> List<T> repeat(T value, int times) {
+        List<T> result = new ArrayList<>();
+        for (int i = 0; i < times; i++) {
+            result.add(value);
+        }
+        long total = times;
+        double half = total / 2.0;
+        return half > 0 ? result : null;
+    }
+
+    public int scopes(int x) {
+        {
+            int a = x + 1;
+            x = a;
+        }
+        {
+            String b = "b";
+            x += b.length();
+        }
+        return x;
+    }
+}
@@ -0,0 +1,39 @@
+package rjvm;
+
+public class Nesting {
+    private int secret = 42;
+
+    public static class StaticNested {
+    }
+
+    protected final class Inner {
+        int peek() {
+            return secret;
+        }
+    }
+
+    private interface Callback {
+        void call();
+    }
+
+    public Runnable anonymous() {
+        return new Runnable() {
+            @Override
+            public void run() {
+                secret++;
+            }
+        };
+    }
+
+    public Object local() {
+        class Local {
+        }
+        return new Local();
+    }
+
+    private final Callback field = new Callback() {
+        @Override
+        public void call() {
+        }
+    };
+}
@@ -0,0 +1,37 @@
+package rjvm;
+
+import java.lang.annotation.ElementType;
+import java.lang.annotation.Retention;
+import java.lang.annotation.RetentionPolicy;
+import java.lang.annotation.Target;
+import java.util.List;
+
+public record Records<T>(int x, @Records.Key("id") List<T> tags, @Records.Positive long weight) {
+    @Target(ElementType.RECORD_COMPONENT)
+    @Retention(RetentionPolicy.RUNTIME)
+    @interface Key {
+        String value();
+    }
+
+    @Target(ElementType.TYPE_USE)
+    @Retention(RetentionPolicy.RUNTIME)
+    @interface Positive {
+    }
+
+    public sealed interface Shape permits Circle, Square {
+    }
+
+    public static final class Circle implements Shape {
+    }
+
+    public static non-sealed class Square implements Shape {
+    }
+
+    public Records {
+        tags = List.copyOf(tags);
+    }
+
+    public Records<T> move(final int dx, String label) {
+        return new Records<>(x + dx, tags, weight);
+    }
+}
@@ -0,0 +1,46 @@
+package rjvm;
+
+public class StackMaps {
+    private double total;
+
+    public static int loop(int n) {
+        int sum = 0;
+        for (int i = 0; i < n; i++) {
+            if (i % 2 == 0) {
+                continue;
+            }
+            sum += i;
+        }
+        return sum;
+    }
+
+    public Object uninitialized(boolean flag) {
+        return new StringBuilder(flag ? "yes" : "no");
+    }
+
+    public double full(long l, Object o) {
+        if (l > 0) {
+            int a = 1;
+            float b = 2;
+            double c = 3;
+            String d = "4";
+            total += a + b + c + d.length();
+        }
+        while (o != null) {
+            o = o.toString().isEmpty() ? null : "";
+        }
+        return total;
+    }
+
+    // long branches need offset_delta > 63, i.e. the extended frames
+    public static int extended(int x) {
+        if (x > 0) {
+            x = x * 1 + x * 2 + x * 3 + x * 4 + x * 5 + x * 6 + x * 7 + x * 8
+                + x * 9 + x * 10 + x * 11 + x * 12 + x * 13 + x * 14 + x * 15 + x * 16
+                + x * 17 + x * 18 + x * 19 + x * 20 + x * 21 + x * 22 + x * 23 + x * 24;
+        }
+        return x > 1 ? 0 : x * 1 + x * 2 + x * 3 + x * 4 + x * 5 + x * 6 + x * 7 + x * 8
+                + x * 9 + x * 10 + x * 11 + x * 12 + x * 13 + x * 14 + x * 15 + x * 16
+                + x * 17 + x * 18 + x * 19 + x * 20 + x * 21 + x * 22 + x * 23 + x * 24;
+    }
+}
@@ -0,0 +1,26 @@
+package rjvm;
+
+public class Switch {
+    public static int dense(int value) {
+        switch (value) {
+            case 1: return 10;
+            case 2: return 20;
+            case 3: return 30;
+            default: return -1;
+        }
+    }
+
+    public static int sparse(int value) {
+        switch (value) {
+            case -100: return 1;
+            case 0: return 2;
+            case 100000: return 3;
+            default: return 0;
+        }
+    }
+
+    public static int bigIncrement(int value) {
+        value += 1000;
+        return value;
+    }
+}
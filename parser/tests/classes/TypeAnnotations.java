@@ -0,0 +1,44 @@
+package rjvm;
+
+import java.lang.annotation.ElementType;
+import java.lang.annotation.Retention;
+import java.lang.annotation.RetentionPolicy;
+import java.lang.annotation.Target;
+import java.util.ArrayList;
+import java.util.List;
+import java.util.Map;
+
+public class TypeAnnotations<@Nullable T> implements @Nullable Comparable<T> {
+    private List<@Nullable String> names;
+
+    private String @Nullable [] array;
+
+    private Map.@Nullable Entry<String, ? extends @Nullable Number> entry;
+
+    public @Nullable String find(@NonNull String key) throws @Nullable IllegalStateException {
+        @Nullable Object value = new @Nullable ArrayList<String>();
+        if (value instanceof @Nullable List) {
+            return (@Nullable String) key;
+        }
+        try {
+            return key.trim();
+        } catch (@Nullable RuntimeException e) {
+            return null;
+        }
+    }
+
+    @Override
+    public int compareTo(T other) {
+        return 0;
+    }
+}
+
+@Target(ElementType.TYPE_USE)
+@Retention(RetentionPolicy.RUNTIME)
+@interface Nullable {
+}
+
+@Target(ElementType.TYPE_USE)
+@Retention(RetentionPolicy.CLASS)
+@interface NonNull {
+}
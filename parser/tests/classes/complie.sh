@@ -1,2 +1,15 @@
 #!/usr/bin/env sh
-javac -source 8 -target 8 ./*.java
\ No newline at end of file
+javac -source 8 -target 8 ./Complex.java ./Constants.java ./Switch.java ./Generics.java ./StackMaps.java ./Annotations.java
+# invokedynamic 的字符串拼接需要 Java 9 以上的版本
+javac --release 11 ./Lambda.java
+# NestHost 与 NestMembers 属性需要 Java 11 以上的版本
+javac --release 11 ./Nesting.java
+# record 与 sealed 类需要 Java 17, -parameters 生成 MethodParameters 属性
+javac --release 17 -parameters ./Records.java
+# LocalVariableTable 与 LocalVariableTypeTable 需要 -g 才会生成
+javac -g -source 8 -target 8 ./LocalVariables.java ./TypeAnnotations.java
+# module-info.class 的源码位于 module 目录中, ModulePackages 与 ModuleMainClass 属性由 jar 工具添加
+(cd ./module && javac --release 17 -d ./out $(find . -name "*.java") \
+    && jar --create --file ./out/app.jar --main-class com.example.app.internal.DefaultPlugin \
+        --module-version 1.2.3 -C ./out . \
+    && unzip -o -q ./out/app.jar module-info.class && rm -r ./out)
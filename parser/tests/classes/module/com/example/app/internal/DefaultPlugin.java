@@ -0,0 +1,14 @@
+package com.example.app.internal;
+
+import com.example.app.spi.Plugin;
+
+public class DefaultPlugin implements Plugin {
+    @Override
+    public String name() {
+        return "default";
+    }
+
+    public static void main(String[] args) {
+        System.out.println(new DefaultPlugin().name());
+    }
+}
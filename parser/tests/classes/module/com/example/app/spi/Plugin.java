@@ -0,0 +1,5 @@
+package com.example.app.spi;
+
+public interface Plugin {
+    String name();
+}
@@ -0,0 +1,13 @@
+/**
+ * Example module.
+ */
+@Deprecated
+module com.example.app {
+    requires transitive java.logging;
+    requires static java.sql;
+    exports com.example.app.spi;
+    exports com.example.app.internal to java.logging, java.sql;
+    opens com.example.app.internal to java.logging;
+    uses com.example.app.spi.Plugin;
+    provides com.example.app.spi.Plugin with com.example.app.internal.DefaultPlugin;
+}
@@ -0,0 +1,66 @@
+#[cfg(test)]
+mod test {
+    use parser::annotation::annotations::{ConstValue, ElementValue};
+    use parser::log::{init_log, LogLevel};
+    use parser::read_buffer;
+
+    #[test]
+    fn test_read_file() {
+        init_log(LogLevel::INFO);
+
+        let bytes = include_bytes!("./classes/Annotations.class");
+        let class_file = read_buffer(bytes).unwrap();
+        println!("The readed class file is: \n {}", class_file);
+
+        // 不需要加载注解类型, 只根据类型名称就可以找到注解
+        let entity = class_file.annotations.visible.first().unwrap();
+        assert_eq!("rjvm/Annotations$Entity", entity.type_name);
+        assert_eq!(
+            "@rjvm.Annotations$Entity(name=\"users\", tags={\"a\", \"b\"}, kind=java.lang.annotation.ElementType.TYPE, \
+             type=java.lang.String.class, index=@rjvm.Annotations$Index(columns={\"id\"}))",
+            entity.to_string()
+        );
+        // RetentionPolicy.CLASS 的注解是运行时不可见的
+        assert_eq!(1, class_file.annotations.invisible.len());
+        assert!(class_file.annotations.contains("rjvm/Annotations$Audited"));
+
+        let field = class_file.fields.iter().find(|field| field.name == "name").unwrap();
+        let column = field.annotations.find("rjvm/Annotations$Column").unwrap();
+        assert_eq!(Some(&ElementValue::Const(ConstValue::Byte(-1))), column.element("b"));
+        assert_eq!(Some(&ElementValue::Const(ConstValue::Char('x' as u16))), column.element("c"));
+        assert_eq!(Some(&ElementValue::Const(ConstValue::Short(300))), column.element("s"));
+        assert_eq!(Some(&ElementValue::Const(ConstValue::Long(1 << 40))), column.element("l"));
+        assert_eq!(Some(&ElementValue::Const(ConstValue::Float(1.5))), column.element("f"));
+        assert_eq!(Some(&ElementValue::Const(ConstValue::Double(2.25))), column.element("d"));
+        assert_eq!(Some(&ElementValue::Const(ConstValue::Boolean(false))), column.element("z"));
+
+        let method = class_file.methods.iter().find(|method| method.name == "shouldSave").unwrap();
+        assert!(method.annotations.contains("rjvm/Annotations$Test"));
+        assert_eq!(3, method.parameter_annotations.len());
+        assert_eq!("@rjvm.Annotations$Column", method.parameter_annotations[0].visible[0].to_string());
+        assert!(method.parameter_annotations[1].is_empty());
+        assert_eq!("@rjvm.Annotations$Column(b=7)", method.parameter_annotations[2].visible[0].to_string());
+        assert!(method.parameter_annotations[2].contains("rjvm/Annotations$Audited"));
+    }
+
+    #[test]
+    fn can_read_annotation_defaults() {
+        let bytes = include_bytes!("./classes/Annotations$Entity.class");
+        let class_file = read_buffer(bytes).unwrap();
+        let default_of = |name: &str| {
+            let method = class_file.methods.iter().find(|method| method.name == name).unwrap();
+            method.annotation_default.as_ref().unwrap().to_string()
+        };
+        assert_eq!("\"\"", default_of("name"));
+        assert_eq!("{}", default_of("tags"));
+        assert_eq!("java.lang.annotation.ElementType.FIELD", default_of("kind"));
+        assert_eq!("void.class", default_of("type"));
+        assert_eq!("@rjvm.Annotations$Index(columns={})", default_of("index"));
+        assert_eq!("5", default_of("priority"));
+        // 注解类型上的 @Retention 也是一个注解
+        assert_eq!(
+            "@java.lang.annotation.Retention(value=java.lang.annotation.RetentionPolicy.RUNTIME)",
+            class_file.annotations.visible[0].to_string()
+        );
+    }
+}
@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod test {
-    use parser::{class_parser, read_buffer};
+    use parser::read_buffer;
     use parser::log::{init_log, LogLevel};
 
     #[test]
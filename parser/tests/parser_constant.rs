@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod test {
-    use parser::{class_parser, read_buffer};
+    use parser::read_buffer;
     use parser::log::{init_log, LogLevel};
 
     #[test]
@@ -12,5 +12,12 @@ mod test {
         let class_file = read_buffer(bytes).unwrap();
 
         println!("The readed class file is: \n {}",  class_file);
+
+        // 字段的原始属性也会被保留, 其中包括已经解析过的 ConstantValue
+        for field in class_file.fields.iter() {
+            assert_eq!(1, field.attributes.len());
+            assert_eq!("ConstantValue", field.attributes[0].name);
+            assert!(field.constant_value.is_some());
+        }
     }
 }
\ No newline at end of file
@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod test {
+    use parser::constant_pool::constant_pool::ConstantPool;
+    use parser::custom_attribute::{AttributeRegistry, ConstantPoolBuilder, CustomAttribute};
+    use parser::error::ClassFileParserResult;
+    use parser::log::{init_log, LogLevel};
+    use parser::utils::buffer::{Buffer, BufferWriter};
+    use parser::{read_buffer, read_buffer_with, write_buffer};
+
+    /// 内部编译器生成的属性, 记录了生成代码的模板类以及行数
+    #[derive(Debug, PartialEq)]
+    struct Generated {
+        template: String,
+        lines: u32,
+    }
+
+    impl CustomAttribute for Generated {
+        fn decode(bytes: &[u8], constants: &ConstantPool) -> ClassFileParserResult<Self> {
+            let mut buf = Buffer::new(bytes);
+            let template = constants.class_name_of(buf.read_u16()?)?;
+            let lines = buf.read_u32()?;
+            Ok(Generated { template, lines })
+        }
+
+        fn encode(&self, constants: &mut dyn ConstantPoolBuilder) -> ClassFileParserResult<Vec<u8>> {
+            let mut buffer = BufferWriter::new();
+            buffer.write_u16(constants.class_index(&self.template)?);
+            buffer.write_u32(self.lines);
+            Ok(buffer.into_bytes())
+        }
+    }
+
+    fn generated(template: &str, lines: u32) -> Generated {
+        Generated {
+            template: template.to_string(),
+            lines,
+        }
+    }
+
+    fn registry() -> AttributeRegistry {
+        let mut registry = AttributeRegistry::new();
+        registry.register::<Generated>("AcmeGenerated");
+        registry
+    }
+
+    /// javac 不会生成自定义属性, 所以先给已有的 class 文件加上自定义属性再写出
+    fn write_with_custom_attributes() -> Vec<u8> {
+        let mut class_file = read_buffer(include_bytes!("./classes/Constants.class")).unwrap();
+        class_file.custom_attributes.insert("AcmeGenerated", generated("rjvm/Template", 42));
+        class_file.fields[0]
+            .custom_attributes
+            .insert("AcmeGenerated", generated("java/lang/String", 1));
+        let method = &mut class_file.methods[0];
+        method.custom_attributes.insert("AcmeGenerated", generated("rjvm/Template", 2));
+        method
+            .code
+            .as_mut()
+            .unwrap()
+            .custom_attributes
+            .insert("AcmeGenerated", generated("rjvm/NewTemplate", 3));
+        write_buffer(&class_file).unwrap()
+    }
+
+    #[test]
+    fn test_read_file() {
+        init_log(LogLevel::INFO);
+
+        let bytes = write_with_custom_attributes();
+        let class_file = read_buffer_with(&bytes, &registry()).unwrap();
+        println!("The readed class file is: \n {}", class_file);
+
+        assert_eq!(
+            Some(&generated("rjvm/Template", 42)),
+            class_file.custom_attributes.get::<Generated>("AcmeGenerated")
+        );
+        assert_eq!(
+            Some(&generated("java/lang/String", 1)),
+            class_file.fields[0].custom_attributes.get::<Generated>("AcmeGenerated")
+        );
+        assert!(class_file.fields[1].custom_attributes.is_empty());
+        let method = &class_file.methods[0];
+        assert_eq!(
+            Some(&generated("rjvm/Template", 2)),
+            method.custom_attributes.get::<Generated>("AcmeGenerated")
+        );
+        let code = method.code.as_ref().unwrap();
+        assert_eq!(
+            Some(&generated("rjvm/NewTemplate", 3)),
+            code.custom_attributes.get::<Generated>("AcmeGenerated")
+        );
+        // 原始属性仍然保留
+        assert!(class_file.attributes.iter().any(|attr| attr.name == "AcmeGenerated"));
+
+        // 没有注册时只有原始属性
+        let class_file = read_buffer(&bytes).unwrap();
+        assert!(class_file.custom_attributes.is_empty());
+        assert!(class_file.attributes.iter().any(|attr| attr.name == "AcmeGenerated"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        init_log(LogLevel::INFO);
+
+        let bytes = write_with_custom_attributes();
+        let registry = registry();
+        let mut class_file = read_buffer_with(&bytes, &registry).unwrap();
+        assert_eq!(bytes, write_buffer(&class_file).unwrap());
+
+        // 修改之后重新编码, 替换原来的属性
+        class_file
+            .custom_attributes
+            .get_mut::<Generated>("AcmeGenerated")
+            .unwrap()
+            .lines = 43;
+        let attributes_count = class_file.attributes.len();
+        let reread = read_buffer_with(&write_buffer(&class_file).unwrap(), &registry).unwrap();
+        assert_eq!(
+            Some(&generated("rjvm/Template", 43)),
+            reread.custom_attributes.get::<Generated>("AcmeGenerated")
+        );
+        assert_eq!(attributes_count, reread.attributes.len());
+    }
+}
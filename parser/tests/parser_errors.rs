@@ -0,0 +1,75 @@
+#[cfg(test)]
+mod test {
+    use parser::error::ClassFileParserError;
+    use parser::read_buffer;
+
+    #[test]
+    fn test_invalid_magic_number() {
+        let bytes = [0xCA, 0xFE, 0xBA, 0xBF, 0x00, 0x00, 0x00, 0x3D];
+        let err = read_buffer(&bytes).err().unwrap();
+
+        assert_eq!(Some(0), err.offset());
+        assert_eq!("magic", err.path().unwrap().to_string());
+        assert_eq!(
+            "Invalidate class data=\"invalid magic number\", is invalidate constant pool idx=false \
+             at offset 0x0 (magic)",
+            err.to_string()
+        );
+        assert_eq!(
+            "00000000  ca fe ba bf 00 00 00 3d                           |.......=|\n\
+             \x20         ^^",
+            err.hex_excerpt(&bytes).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_truncated_file() {
+        let bytes = include_bytes!("./classes/Constants.class");
+        let truncated = &bytes[..bytes.len() - 1];
+        let err = read_buffer(truncated).err().unwrap();
+
+        // 唯一的类属性 SourceFile 的内容只剩下 1 个字节
+        assert_eq!(&ClassFileParserError::UnexpectedEndOfData, err.inner());
+        assert_eq!(Some(bytes.len() - 2), err.offset());
+        assert_eq!("attributes[0]", err.path().unwrap().to_string());
+    }
+
+    #[test]
+    fn test_invalid_exception_table_entry() {
+        let bytes = include_bytes!("./classes/TypeAnnotations.class");
+        let class_file = read_buffer(bytes).unwrap();
+        let (method_index, method) = class_file
+            .methods
+            .iter()
+            .enumerate()
+            .find(|(_, method)| !method.code.as_ref().unwrap().exception_table.entries().is_empty())
+            .unwrap();
+
+        // 在原始字节中找到 Code 属性的内容, exception_table 在 max_stack、max_locals、code_length 与 code 之后
+        let code_attribute = method.attributes.iter().find(|attr| attr.name == "Code").unwrap();
+        let code_offset = bytes
+            .windows(code_attribute.bytes.len())
+            .position(|window| window == code_attribute.bytes)
+            .unwrap();
+        let code_length = method.code.as_ref().unwrap().code.len();
+        let catch_type_offset = code_offset + 8 + code_length + 2 + 6;
+
+        let mut corrupted = bytes.to_vec();
+        corrupted[catch_type_offset..catch_type_offset + 2].copy_from_slice(&[0xFF, 0xFF]);
+        let err = read_buffer(&corrupted).err().unwrap();
+
+        assert_eq!(&ClassFileParserError::WrongConstantPoolIndexError(0xFFFF), err.inner());
+        assert_eq!(Some(catch_type_offset), err.offset());
+        assert_eq!(
+            format!("methods[{method_index}].attributes[Code].exception_table[0]"),
+            err.path().unwrap().to_string()
+        );
+
+        let excerpt = err.hex_excerpt(&corrupted).unwrap();
+        let caret_line = excerpt.lines().find(|line| line.trim_start().starts_with("^^")).unwrap();
+        let row_line = excerpt.lines().take_while(|line| *line != caret_line).last().unwrap();
+        let column = caret_line.find("^^").unwrap();
+        assert_eq!("ff", &row_line[column..column + 2]);
+        assert_eq!(format!("{:08x}", catch_type_offset / 16 * 16), row_line[..8]);
+    }
+}
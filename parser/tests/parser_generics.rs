@@ -0,0 +1,65 @@
+#[cfg(test)]
+mod test {
+    use parser::read_buffer;
+    use parser::log::{init_log, LogLevel};
+    use parser::utils::types::Type;
+
+    #[test]
+    fn test_read_file() {
+        init_log(LogLevel::INFO);
+
+        // 泛型信息保存在类、字段与方法的 Signature 属性中
+        let bytes = include_bytes!("./classes/Generics.class");
+        let class_file = read_buffer(bytes).unwrap();
+
+        println!("The readed class file is: \n {}", class_file);
+        let class_signature = class_file.signature.as_ref().unwrap();
+        assert_eq!(
+            "<K extends java.lang.Comparable<K> & java.io.Serializable, V> \
+             extends java.util.AbstractMap<K, V> implements java.lang.Iterable<java.util.Map$Entry<K, V>>",
+            class_signature.to_string()
+        );
+
+        let field_signature = |name: &str| {
+            class_file
+                .fields
+                .iter()
+                .find(|field| field.name == name)
+                .unwrap()
+                .signature
+                .as_ref()
+                .map(|signature| signature.to_string())
+        };
+        assert_eq!(Some("java.util.List<? extends java.lang.Number>".to_string()), field_signature("numbers"));
+        assert_eq!(
+            Some("java.util.Map<java.lang.String, java.util.List<? super java.lang.Integer>>[]".to_string()),
+            field_signature("maps")
+        );
+        assert_eq!(Some("V".to_string()), field_signature("value"));
+        assert_eq!(
+            Some("rjvm.Generics<K, V>.Inner<java.lang.String>.Nested<java.lang.Integer>".to_string()),
+            field_signature("nested")
+        );
+        assert_eq!(None, field_signature("plain"));
+
+        let value = class_file.fields.iter().find(|field| field.name == "value").unwrap();
+        assert_eq!(
+            Type::Object("java/lang/Object".to_string()),
+            value.signature.as_ref().unwrap().erasure(&class_signature.type_parameters)
+        );
+
+        let compute = class_file.methods.iter().find(|method| method.name == "compute").unwrap();
+        let method_signature = compute.signature.as_ref().unwrap();
+        assert_eq!(
+            "<T extends java.lang.Exception> V (java.util.List<? super K>, int[], T[]) throws T, java.io.IOException",
+            method_signature.to_string()
+        );
+        // 擦除之后与方法描述符一致
+        assert_eq!(
+            compute.parsed_type_descriptor,
+            method_signature.erasure(&class_signature.type_parameters)
+        );
+        let constructor = class_file.methods.iter().find(|method| method.name == "<init>").unwrap();
+        assert!(constructor.signature.is_none());
+    }
+}
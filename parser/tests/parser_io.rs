@@ -0,0 +1,92 @@
+#[cfg(test)]
+mod test {
+    use std::io::{ErrorKind, Read};
+    use std::path::PathBuf;
+
+    use parser::error::{ClassFileParserError, ClassFileReadError};
+    use parser::io::MappedClassFile;
+    use parser::{read_file, read_file_mapped, read_from};
+
+    fn class_path(name: &str) -> PathBuf {
+        [env!("CARGO_MANIFEST_DIR"), "tests", "classes", name].iter().collect()
+    }
+
+    /// 每次只返回 chunk 个字节, 读取 limit 个字节之后出错, 模拟网络或者损坏的压缩流
+    struct ChunkedReader<'a> {
+        bytes: &'a [u8],
+        chunk: usize,
+        limit: usize,
+    }
+
+    impl Read for ChunkedReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.limit == 0 {
+                return Err(std::io::Error::new(ErrorKind::InvalidData, "corrupt deflate stream"));
+            }
+            let len = self.chunk.min(buf.len()).min(self.bytes.len()).min(self.limit);
+            buf[..len].copy_from_slice(&self.bytes[..len]);
+            self.bytes = &self.bytes[len..];
+            self.limit = if self.bytes.is_empty() { usize::MAX } else { self.limit - len };
+            Ok(len)
+        }
+    }
+
+    #[test]
+    fn test_read_from_stream() {
+        let bytes = include_bytes!("./classes/Complex.class");
+        let reader = ChunkedReader {
+            bytes,
+            chunk: 7,
+            limit: usize::MAX,
+        };
+        let class_file = read_from(reader).unwrap();
+        assert_eq!("rjvm/Complex", class_file.name);
+    }
+
+    #[test]
+    fn test_io_and_format_errors_are_distinguished() {
+        let bytes = include_bytes!("./classes/Complex.class");
+        let reader = ChunkedReader {
+            bytes,
+            chunk: 64,
+            limit: 100,
+        };
+        let err = read_from(reader).err().unwrap();
+        assert!(matches!(&err, ClassFileReadError::Io(err) if err.kind() == ErrorKind::InvalidData));
+        assert_eq!("cannot read class file: corrupt deflate stream", err.to_string());
+
+        // 流正常结束但是内容不完整时是格式错误
+        let err = read_from(&bytes[..100]).err().unwrap();
+        let ClassFileReadError::Format(err) = err else {
+            panic!("expected a format error but got {err}");
+        };
+        assert_eq!(&ClassFileParserError::UnexpectedEndOfData, err.inner());
+    }
+
+    #[test]
+    fn test_read_file() {
+        let class_file = read_file(class_path("Constants.class")).unwrap();
+        assert_eq!("rjvm/Constants", class_file.name);
+
+        let err = read_file(class_path("Missing.class")).err().unwrap();
+        assert!(matches!(err, ClassFileReadError::Io(err) if err.kind() == ErrorKind::NotFound));
+        let err = read_file(class_path("Constants.java")).err().unwrap();
+        assert!(matches!(err, ClassFileReadError::Format(err) if err.path().unwrap().to_string() == "magic"));
+    }
+
+    #[test]
+    fn test_read_memory_mapped_file() {
+        // SAFETY: 测试用的 class 文件在测试期间不会被修改
+        let class_file = unsafe { read_file_mapped(class_path("Constants.class")) }.unwrap();
+        assert_eq!("rjvm/Constants", class_file.name);
+
+        let mapped = unsafe { MappedClassFile::open(class_path("Complex.class")) }.unwrap();
+        assert_eq!(&include_bytes!("./classes/Complex.class")[..], mapped.bytes());
+        assert_eq!("rjvm/Complex", mapped.view().unwrap().name().unwrap());
+
+        let err = unsafe { read_file_mapped(class_path("Missing.class")) }.err().unwrap();
+        assert!(matches!(err, ClassFileReadError::Io(err) if err.kind() == ErrorKind::NotFound));
+        let err = unsafe { read_file_mapped(class_path("Constants.java")) }.err().unwrap();
+        assert!(matches!(err, ClassFileReadError::Format(_)));
+    }
+}
@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod test {
+    use parser::class_attributes::bootstrap_methods::{BootstrapArgument, CallSite};
+    use parser::constant_pool::constant_pool::ReferenceKind;
+    use parser::read_buffer;
+    use parser::utils::instruction::Instruction;
+    use parser::log::{init_log, LogLevel};
+
+    #[test]
+    fn test_read_file() {
+        init_log(LogLevel::INFO);
+
+        // Lambda 与字符串拼接都会使用 invokedynamic 以及对应的常量
+        let bytes = include_bytes!("./classes/Lambda.class");
+        let class_file = read_buffer(bytes).unwrap();
+
+        println!("The readed class file is: \n {}",  class_file);
+        assert_eq!(
+            Ok("#0:getAsInt: (Lrjvm/Lambda;)Ljava/util/function/IntSupplier;".to_string()),
+            class_file.constants.text_of(13)
+        );
+        assert_eq!(
+            Ok("#3:makeConcatWithConstants: (Ljava/lang/String;I)Ljava/lang/String;".to_string()),
+            class_file.constants.text_of(37)
+        );
+        assert!(class_file
+            .constants
+            .text_of(59)
+            .unwrap()
+            .starts_with("REF_invokeStatic java/lang/invoke/LambdaMetafactory.metafactory: "));
+        assert_eq!(Ok("()I".to_string()), class_file.constants.text_of(66));
+
+        // 没有被解析的类属性也会被保留下来
+        let attribute_names: Vec<&str> = class_file
+            .attributes
+            .iter()
+            .map(|attribute| attribute.name.as_str())
+            .collect();
+        assert_eq!(vec!["SourceFile", "BootstrapMethods", "InnerClasses"], attribute_names);
+    }
+
+    #[test]
+    fn test_resolve_call_sites() {
+        init_log(LogLevel::INFO);
+
+        let bytes = include_bytes!("./classes/Lambda.class");
+        let class_file = read_buffer(bytes).unwrap();
+        assert_eq!(4, class_file.bootstrap_methods.len());
+
+        let call_sites: Vec<CallSite> = class_file
+            .methods
+            .iter()
+            .filter_map(|method| method.code.as_ref())
+            .flat_map(|code| Instruction::parse_instructions(&code.code).unwrap())
+            .filter(|(_, instruction)| matches!(instruction, Instruction::Invokedynamic(_)))
+            .map(|(_, instruction)| class_file.resolve_call_site(&instruction).unwrap())
+            .collect();
+        assert_eq!(4, call_sites.len());
+
+        // supplier() 中的 lambda
+        let supplier = &call_sites[0];
+        assert!(supplier.is_lambda());
+        assert_eq!("getAsInt", supplier.name);
+        assert_eq!("(Lrjvm/Lambda;)Ljava/util/function/IntSupplier;", supplier.type_descriptor);
+        assert_eq!("metafactory", supplier.bootstrap_method.name);
+        assert_eq!(BootstrapArgument::MethodType("()I".to_string()), supplier.static_arguments[0]);
+        let implementation = supplier.lambda_implementation().unwrap();
+        assert_eq!(ReferenceKind::InvokeSpecial, implementation.kind);
+        assert_eq!("lambda$supplier$0", implementation.name);
+
+        let describe = &call_sites[1];
+        assert!(describe.is_lambda());
+        assert_eq!("get", describe.name);
+        assert_eq!("lambda$describe$1", describe.lambda_implementation().unwrap().name);
+
+        // describe() 中的字符串拼接位于 lambda 的实现方法中, 因此排在最后
+        let concat = &call_sites[3];
+        assert!(concat.is_string_concat());
+        assert!(concat.lambda_implementation().is_none());
+        assert_eq!("makeConcatWithConstants", concat.name);
+        assert_eq!(
+            vec![BootstrapArgument::String("Lambda \u{1} with base \u{1}".to_string())],
+            concat.static_arguments
+        );
+
+        // 方法引用 System.out::println
+        let reference = &call_sites[2];
+        assert!(reference.is_lambda());
+        assert_eq!("println", reference.lambda_implementation().unwrap().name);
+
+        assert!(class_file.resolve_call_site(&Instruction::Nop).is_err());
+        assert!(class_file.resolve_invoke_dynamic(1).is_err());
+    }
+}
@@ -0,0 +1,322 @@
+#[cfg(test)]
+mod test {
+    use parser::constant_pool::constant_pool::ConstantPoolEntry;
+    use parser::error::diagnostic::Severity;
+    use parser::error::ClassFileParserError;
+    use parser::flags::FieldFlags;
+    use parser::{read_buffer, read_buffer_lenient, write_buffer};
+
+    /// 字段与方法在 class 文件中的位置, 用于在测试中构造损坏的输入
+    struct Layout {
+        /// 每个常量的 tag 所在的位置, 下标是常量池索引
+        constants: Vec<usize>,
+        fields: Vec<usize>,
+        methods: Vec<usize>,
+        /// 类属性 attributes_count 的位置
+        class_attributes: usize,
+    }
+
+    fn u16_at(bytes: &[u8], offset: usize) -> usize {
+        usize::from(u16::from_be_bytes([bytes[offset], bytes[offset + 1]]))
+    }
+
+    fn u32_at(bytes: &[u8], offset: usize) -> usize {
+        u32::from_be_bytes(bytes[offset..offset + 4].try_into().unwrap()) as usize
+    }
+
+    /// 按照 JVMS 4.1 的结构跳过常量池与接口, 记录每个字段与方法开始的位置
+    fn layout(bytes: &[u8]) -> Layout {
+        let constants_count = u16_at(bytes, 8);
+        let mut offset = 10;
+        let mut index = 1;
+        let mut constants = vec![0];
+        while index < constants_count {
+            let tag = bytes[offset];
+            constants.push(offset);
+            offset += 1 + match tag {
+                1 => 2 + u16_at(bytes, offset + 1),
+                7 | 8 | 16 | 19 | 20 => 2,
+                15 => 3,
+                3 | 4 | 9 | 10 | 11 | 12 | 17 | 18 => 4,
+                5 | 6 => 8,
+                _ => panic!("unknown constant tag {tag}"),
+            };
+            if matches!(tag, 5 | 6) {
+                constants.push(offset);
+                index += 2;
+            } else {
+                index += 1;
+            }
+        }
+        offset += 6;
+        offset += 2 + 2 * u16_at(bytes, offset);
+        let skip_members = |offset: &mut usize| {
+            let count = u16_at(bytes, *offset);
+            *offset += 2;
+            (0..count)
+                .map(|_| {
+                    let start = *offset;
+                    let attributes_count = u16_at(bytes, start + 6);
+                    *offset += 8;
+                    for _ in 0..attributes_count {
+                        *offset += 6 + u32_at(bytes, *offset + 2);
+                    }
+                    start
+                })
+                .collect::<Vec<usize>>()
+        };
+        let fields = skip_members(&mut offset);
+        let methods = skip_members(&mut offset);
+        Layout {
+            constants,
+            fields,
+            methods,
+            class_attributes: offset,
+        }
+    }
+
+    #[test]
+    fn test_lenient_read_of_valid_file() {
+        let bytes = include_bytes!("./classes/Constants.class");
+        let report = read_buffer_lenient(bytes);
+
+        assert!(report.diagnostics.is_empty());
+        assert_eq!("rjvm/Constants", report.class_file.name);
+        assert_eq!(5, report.class_file.fields.len());
+        assert_eq!(Some("Constants.java".to_string()), report.class_file.source_file);
+    }
+
+    #[test]
+    fn test_unknown_flag_bits() {
+        let bytes = include_bytes!("./classes/Constants.class");
+        let field = layout(bytes).fields[1];
+        let mut corrupted = bytes.to_vec();
+        corrupted[field] |= 0x80;
+
+        let err = read_buffer(&corrupted).err().unwrap();
+        assert_eq!(Some(field), err.offset());
+        assert_eq!("fields[1].access_flags", err.path().unwrap().to_string());
+
+        let report = read_buffer_lenient(&corrupted);
+        assert!(!report.has_errors());
+        let warnings: Vec<_> = report.warnings().collect();
+        assert_eq!(1, warnings.len());
+        assert_eq!(Some(field), warnings[0].error.offset());
+        assert_eq!(
+            format!(
+                "warning: Invalidate class data=\"invalid field flags: 0x801c\", is invalidate constant pool idx=false \
+                 at offset {field:#x} (fields[1].access_flags)"
+            ),
+            warnings[0].to_string()
+        );
+        // 未知的 bit 被保留下来, 已知的 flags 不受影响
+        let flags = &report.class_file.fields[1].flags;
+        assert_eq!(0x8000 | 0x001c, flags.bits());
+        assert!(flags.contains(FieldFlags::PROTECTED | FieldFlags::STATIC | FieldFlags::FINAL));
+        assert_eq!(5, report.class_file.fields.len());
+    }
+
+    /// attributes_count 位于 offset 的属性表中名为 name 的属性, 返回属性内容开始的位置
+    fn attribute_offset(bytes: &[u8], offset: usize, name: &str) -> Option<usize> {
+        let constants = read_buffer(bytes).unwrap().constants;
+        let mut offset = offset + 2;
+        for _ in 0..u16_at(bytes, offset - 2) {
+            if constants.text_of(u16_at(bytes, offset) as u16).as_deref() == Ok(name) {
+                return Some(offset + 6);
+            }
+            offset += 6 + u32_at(bytes, offset + 2);
+        }
+        None
+    }
+
+    /// 在 flags 的低字节上设置一个未定义的 bit, 严格模式下失败, 宽松模式下保留 bits 并报告一个 warning
+    fn assert_unknown_flag_bit(bytes: &[u8], flags: usize, bit: u8, path: &str, message: &str) {
+        let mut corrupted = bytes.to_vec();
+        corrupted[flags + 1] |= bit;
+
+        let err = read_buffer(&corrupted).err().unwrap();
+        assert_eq!(path, err.path().unwrap().to_string());
+        assert!(err.to_string().contains(message), "{message:?} not found in {err}");
+
+        let report = read_buffer_lenient(&corrupted);
+        assert!(!report.has_errors());
+        let warnings: Vec<_> = report.warnings().collect();
+        assert_eq!(1, warnings.len());
+        assert_eq!(path, warnings[0].error.path().unwrap().to_string());
+        assert!(warnings[0].to_string().contains(message));
+    }
+
+    #[test]
+    fn test_unknown_flag_bits_in_attributes() {
+        // InnerClasses 中第一个类的 inner_class_access_flags
+        let bytes = include_bytes!("./classes/Nesting.class");
+        let inner_classes = attribute_offset(bytes, layout(bytes).class_attributes, "InnerClasses").unwrap();
+        assert_unknown_flag_bit(bytes, inner_classes + 8, 0x80, "attributes[InnerClasses]", "invalid inner class flags");
+        let report = read_buffer_lenient(&{
+            let mut corrupted = bytes.to_vec();
+            corrupted[inner_classes + 9] |= 0x80;
+            corrupted
+        });
+        assert_eq!(0x80, report.class_file.inner_classes[0].flags.bits() & 0x80);
+
+        // Module 属性的 module_flags
+        let bytes = include_bytes!("./classes/module/module-info.class");
+        let module = attribute_offset(bytes, layout(bytes).class_attributes, "Module").unwrap();
+        assert_unknown_flag_bit(bytes, module + 2, 0x01, "attributes[Module]", "invalid module flags");
+
+        // MethodParameters 中第一个参数的 access_flags
+        let bytes = include_bytes!("./classes/Records.class");
+        let (index, parameters) = layout(bytes)
+            .methods
+            .iter()
+            .enumerate()
+            .find_map(|(index, method)| Some((index, attribute_offset(bytes, method + 6, "MethodParameters")?)))
+            .unwrap();
+        assert_unknown_flag_bit(
+            bytes,
+            parameters + 3,
+            0x01,
+            &format!("methods[{index}].attributes[MethodParameters]"),
+            "invalid method parameter flags",
+        );
+    }
+
+    #[test]
+    fn test_malformed_attribute_is_skipped() {
+        let bytes = include_bytes!("./classes/Constants.class");
+        // 唯一的类属性 SourceFile 的内容是最后两个字节
+        let mut corrupted = bytes.to_vec();
+        let len = corrupted.len();
+        corrupted[len - 2..].copy_from_slice(&[0xFF, 0xFF]);
+
+        let report = read_buffer_lenient(&corrupted);
+        assert!(!report.has_errors());
+        let warnings: Vec<_> = report.warnings().collect();
+        assert_eq!(1, warnings.len());
+        assert_eq!(&ClassFileParserError::WrongConstantPoolIndexError(0xFFFF), warnings[0].error.inner());
+        assert_eq!(Some(len - 2), warnings[0].error.offset());
+        assert_eq!("attributes[SourceFile]", warnings[0].error.path().unwrap().to_string());
+
+        // 无法解码的属性仍然作为原始属性保留
+        assert_eq!(None, report.class_file.source_file);
+        assert_eq!("SourceFile", report.class_file.attributes[0].name);
+        assert_eq!(5, report.class_file.fields.len());
+    }
+
+    #[test]
+    fn test_unresolvable_attribute_name_is_dropped() {
+        let bytes = include_bytes!("./classes/Constants.class");
+        let class_attributes = layout(bytes).class_attributes;
+        let mut corrupted = bytes.to_vec();
+        corrupted[class_attributes + 2..class_attributes + 4].copy_from_slice(&[0xFF, 0xFF]);
+
+        let report = read_buffer_lenient(&corrupted);
+        assert!(!report.has_errors());
+        let warnings: Vec<_> = report.warnings().collect();
+        assert_eq!(1, warnings.len());
+        assert_eq!(Some(class_attributes + 2), warnings[0].error.offset());
+        assert_eq!("attributes[0].attribute_name_index", warnings[0].error.path().unwrap().to_string());
+        assert!(report.class_file.attributes.is_empty());
+    }
+
+    #[test]
+    fn test_unreadable_member_is_skipped() {
+        let bytes = include_bytes!("./classes/Constants.class");
+        let field = layout(bytes).fields[2];
+        let mut corrupted = bytes.to_vec();
+        // 描述符指向不存在的常量
+        corrupted[field + 4..field + 6].copy_from_slice(&[0xFF, 0xFF]);
+
+        let report = read_buffer_lenient(&corrupted);
+        assert!(report.has_errors());
+        let errors: Vec<_> = report.errors().collect();
+        assert_eq!(1, errors.len());
+        assert_eq!(Severity::Error, errors[0].severity);
+        assert_eq!(Some(field + 4), errors[0].error.offset());
+        assert_eq!("fields[2]", errors[0].error.path().unwrap().to_string());
+
+        // 跳过的字段之后的结构仍然被读取
+        let names: Vec<&str> = report.class_file.fields.iter().map(|field| field.name.as_str()).collect();
+        assert_eq!(vec!["AN_INT", "A_FLOAT", "A_DOUBLE", "A_STRING"], names);
+        assert_eq!(1, report.class_file.methods.len());
+        assert_eq!(Some("Constants.java".to_string()), report.class_file.source_file);
+    }
+
+    #[test]
+    fn test_missing_code_attribute() {
+        let bytes = include_bytes!("./classes/Constants.class");
+        let method = layout(bytes).methods[0];
+        let mut corrupted = bytes.to_vec();
+        // Code 属性改名为方法自己的名称 `<init>`, 成为一个未知的属性
+        let name_index = [corrupted[method + 2], corrupted[method + 3]];
+        corrupted[method + 8..method + 10].copy_from_slice(&name_index);
+
+        let err = read_buffer(&corrupted).err().unwrap();
+        assert_eq!("methods[0]", err.path().unwrap().to_string());
+
+        let report = read_buffer_lenient(&corrupted);
+        assert!(!report.has_errors());
+        let warnings: Vec<_> = report.warnings().collect();
+        assert_eq!(1, warnings.len());
+        assert!(warnings[0].to_string().contains("method <init> is missing code attribute"));
+        let method = &report.class_file.methods[0];
+        assert!(method.code.is_none());
+        assert_eq!("<init>", method.attributes[0].name);
+    }
+
+    #[test]
+    fn test_truncated_file_returns_partial_class() {
+        let bytes = include_bytes!("./classes/Constants.class");
+        let layout = layout(bytes);
+        let truncated = &bytes[..layout.methods[0] + 4];
+
+        let report = read_buffer_lenient(truncated);
+        let errors: Vec<_> = report.errors().collect();
+        assert_eq!(1, errors.len());
+        assert!(errors[0].error.is_truncated());
+        assert_eq!(Some(layout.methods[0] + 4), errors[0].error.offset());
+        assert_eq!("methods[0]", errors[0].error.path().unwrap().to_string());
+
+        // 数据结束之前的内容都被保留
+        assert_eq!("rjvm/Constants", report.class_file.name);
+        assert_eq!(5, report.class_file.fields.len());
+        assert!(report.class_file.methods.is_empty());
+    }
+
+    #[test]
+    fn test_self_referencing_class_constant() {
+        let bytes = include_bytes!("./classes/Constants.class");
+        let class_file = read_buffer(bytes).unwrap();
+        let object = class_file
+            .constants
+            .iter()
+            .map(|(index, _)| index)
+            .find(|index| class_file.constants.class_name_of(*index).as_deref() == Ok("java/lang/Object"))
+            .unwrap();
+        // 让 java/lang/Object 的 CONSTANT_Class 指向它自己, 解析常量时不能无限递归
+        let mut corrupted = bytes.to_vec();
+        let offset = layout(bytes).constants[usize::from(object)];
+        corrupted[offset + 1..offset + 3].copy_from_slice(&object.to_be_bytes());
+        let expected = ClassFileParserError::InvalidClassData {
+            name: format!("expected a Utf8 constant at index {object} but found ClassReference({object})"),
+            is_invalidate_constant_pool_idx: true,
+        };
+
+        let err = read_buffer(&corrupted).err().unwrap();
+        assert_eq!(&expected, err.inner());
+        assert_eq!("super_class", err.path().unwrap().to_string());
+
+        let report = read_buffer_lenient(&corrupted);
+        let errors: Vec<_> = report.errors().collect();
+        assert_eq!(1, errors.len());
+        assert_eq!(&expected, errors[0].error.inner());
+        assert_eq!(None, report.class_file.superclass);
+        assert_eq!(1, report.class_file.methods.len());
+        assert!(report.class_file.to_string().contains("nested too deeply"));
+
+        // 写回时保留原来的常量池
+        let written = write_buffer(&report.class_file).unwrap();
+        let reread = read_buffer_lenient(&written);
+        assert_eq!(Ok(&ConstantPoolEntry::ClassReference(object)), reread.class_file.constants.get_entry(object));
+    }
+}
@@ -0,0 +1,54 @@
+#[cfg(test)]
+mod test {
+    use parser::log::{init_log, LogLevel};
+    use parser::method::local_variable_table::LocalVariableTable;
+    use parser::read_buffer;
+    use parser::utils::pc::ProgramCounter;
+    use parser::utils::types::{BaseType, Type};
+
+    fn local_variables_of<'a>(class_file: &'a parser::class::ClassFile, name: &str) -> &'a LocalVariableTable {
+        let method = class_file.methods.iter().find(|method| method.name == name).unwrap();
+        method.code.as_ref().unwrap().local_variable_table.as_ref().unwrap()
+    }
+
+    #[test]
+    fn test_read_file() {
+        init_log(LogLevel::INFO);
+
+        let bytes = include_bytes!("./classes/LocalVariables.class");
+        let class_file = read_buffer(bytes).unwrap();
+        println!("The readed class file is: \n {}", class_file);
+
+        let table = local_variables_of(&class_file, "repeat");
+        // slot 3 先被循环变量 i 使用, 循环结束后被 long 类型的 total 复用
+        let i = table.lookup(3, ProgramCounter(10)).unwrap();
+        assert_eq!("i", i.name);
+        assert_eq!(Type::Base(BaseType::Int), i.type_descriptor);
+        let total = table.lookup(3, ProgramCounter(32)).unwrap();
+        assert_eq!("total", total.name);
+        assert_eq!(Type::Base(BaseType::Long), total.type_descriptor);
+
+        // 泛型局部变量的签名来自 LocalVariableTypeTable
+        let value = table.lookup(0, ProgramCounter(0)).unwrap();
+        assert_eq!("T", value.signature.as_ref().unwrap().to_string());
+        assert_eq!(Type::Object("java/lang/Object".to_string()), value.type_descriptor);
+        let result = table.entries().iter().find(|variable| variable.name == "result").unwrap();
+        assert_eq!("java.util.List<T>", result.signature.as_ref().unwrap().to_string());
+        assert_eq!(None, table.lookup(1, ProgramCounter(0)).unwrap().signature);
+
+        let table = local_variables_of(&class_file, "scopes");
+        let names: Vec<&str> = table.variables_at(ProgramCounter(10)).iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(vec!["this", "x", "b"], names);
+    }
+
+    #[test]
+    fn no_local_variables_without_debug_info() {
+        let bytes = include_bytes!("./classes/StackMaps.class");
+        let class_file = read_buffer(bytes).unwrap();
+        assert!(class_file
+            .methods
+            .iter()
+            .filter_map(|method| method.code.as_ref())
+            .all(|code| code.local_variable_table.is_none()));
+    }
+}
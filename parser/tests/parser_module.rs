@@ -0,0 +1,73 @@
+#[cfg(test)]
+mod test {
+    use parser::class::ClassFile;
+    use parser::flags::{ModuleExportsFlags, ModuleRequiresFlags};
+    use parser::log::{init_log, LogLevel};
+    use parser::read_buffer;
+
+    fn read(bytes: &[u8]) -> ClassFile {
+        read_buffer(bytes).unwrap()
+    }
+
+    #[test]
+    fn test_read_file() {
+        init_log(LogLevel::INFO);
+
+        let class_file = read(include_bytes!("./classes/module/module-info.class"));
+        println!("The readed class file is: \n {}", class_file);
+
+        assert!(class_file.is_module());
+        assert_eq!("module-info", class_file.name);
+        assert_eq!(None, class_file.superclass);
+        assert!(class_file.annotations.contains("java/lang/Deprecated"));
+
+        let module = class_file.module.as_ref().unwrap();
+        assert_eq!("com.example.app", module.name);
+        assert!(!module.is_open());
+        assert_eq!(Some("1.2.3"), module.version.as_deref());
+
+        // java.base 是隐式的依赖
+        let requires: Vec<&str> = module.requires.iter().map(|requires| requires.module.as_str()).collect();
+        assert_eq!(vec!["java.base", "java.logging", "java.sql"], requires);
+        let java_base = module.find_requires("java.base").unwrap();
+        assert_eq!(ModuleRequiresFlags::MANDATED, java_base.flags);
+        assert!(java_base.version.is_some());
+        assert!(module.find_requires("java.logging").unwrap().is_transitive());
+        assert!(module.find_requires("java.sql").unwrap().is_static());
+        assert_eq!(None, module.find_requires("java.desktop"));
+
+        assert_eq!(2, module.exports.len());
+        assert!(!module.exports[0].is_qualified());
+        assert_eq!(ModuleExportsFlags::empty(), module.exports[0].flags);
+        assert!(module.is_exported_to("com/example/app/spi", "java.desktop"));
+        assert!(module.is_exported_to("com/example/app/internal", "java.sql"));
+        assert!(!module.is_exported_to("com/example/app/internal", "java.desktop"));
+
+        assert_eq!(1, module.opens.len());
+        assert_eq!(vec!["java.logging".to_string()], module.opens[0].to);
+        assert!(module.is_opened_to("com/example/app/internal", "java.logging"));
+        assert!(!module.is_opened_to("com/example/app/internal", "java.sql"));
+        assert!(!module.is_opened_to("com/example/app/spi", "java.logging"));
+
+        assert_eq!(vec!["com/example/app/spi/Plugin".to_string()], module.uses);
+        assert_eq!(1, module.provides.len());
+        assert_eq!("com/example/app/spi/Plugin", module.provides[0].service);
+        assert_eq!(vec!["com/example/app/internal/DefaultPlugin".to_string()], module.provides[0].with);
+
+        // 由 jar 工具添加的属性
+        assert_eq!(
+            vec!["com/example/app/internal".to_string(), "com/example/app/spi".to_string()],
+            module.packages
+        );
+        assert_eq!(Some("com/example/app/internal/DefaultPlugin"), module.main_class.as_deref());
+    }
+
+    #[test]
+    fn test_not_a_module() {
+        init_log(LogLevel::INFO);
+
+        let class_file = read(include_bytes!("./classes/Records.class"));
+        assert!(!class_file.is_module());
+        assert_eq!(None, class_file.module);
+    }
+}
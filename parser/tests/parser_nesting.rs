@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod test {
+    use parser::class::ClassFile;
+    use parser::flags::InnerClassAccessFlags;
+    use parser::log::{init_log, LogLevel};
+    use parser::read_buffer;
+
+    fn read(bytes: &[u8]) -> ClassFile {
+        read_buffer(bytes).unwrap()
+    }
+
+    #[test]
+    fn test_read_file() {
+        init_log(LogLevel::INFO);
+
+        let class_file = read(include_bytes!("./classes/Nesting.class"));
+        println!("The readed class file is: \n {}", class_file);
+
+        assert!(!class_file.is_anonymous());
+        assert_eq!(None, class_file.outer_class());
+        assert_eq!(None, class_file.nest_host);
+        assert_eq!(
+            vec![
+                "rjvm/Nesting",
+                "rjvm/Nesting$Callback",
+                "rjvm/Nesting$Inner",
+                "rjvm/Nesting$StaticNested",
+                "rjvm/Nesting$2",
+                "rjvm/Nesting$1Local",
+                "rjvm/Nesting$1",
+            ],
+            class_file.nest_mates()
+        );
+
+        let inner = class_file
+            .inner_classes
+            .iter()
+            .find(|inner_class| inner_class.inner_class == "rjvm/Nesting$Inner")
+            .unwrap();
+        assert_eq!(Some("Inner"), inner.simple_name.as_deref());
+        assert_eq!(Some("rjvm/Nesting"), inner.outer_class.as_deref());
+        assert_eq!(InnerClassAccessFlags::PROTECTED | InnerClassAccessFlags::FINAL, inner.flags);
+        // 局部类有简单名称, 但是没有外部类
+        let local = class_file
+            .inner_classes
+            .iter()
+            .find(|inner_class| inner_class.inner_class == "rjvm/Nesting$1Local")
+            .unwrap();
+        assert_eq!((Some("Local"), None), (local.simple_name.as_deref(), local.outer_class.as_deref()));
+    }
+
+    #[test]
+    fn can_query_nested_classes() {
+        let host = read(include_bytes!("./classes/Nesting.class"));
+
+        let inner = read(include_bytes!("./classes/Nesting$Inner.class"));
+        assert!(!inner.is_anonymous());
+        assert_eq!(Some("rjvm/Nesting"), inner.outer_class());
+        assert_eq!(None, inner.enclosing_method);
+        assert_eq!(vec!["rjvm/Nesting", "rjvm/Nesting$Inner"], inner.nest_mates());
+        assert!(inner.is_nest_mate_of(&host));
+
+        // 在方法中声明的匿名类
+        let anonymous = read(include_bytes!("./classes/Nesting$1.class"));
+        assert!(anonymous.is_anonymous());
+        assert_eq!(Some("rjvm/Nesting"), anonymous.outer_class());
+        let enclosing_method = anonymous.enclosing_method.as_ref().unwrap();
+        assert_eq!(
+            Some(("anonymous".to_string(), "()Ljava/lang/Runnable;".to_string())),
+            enclosing_method.method
+        );
+        // 在字段初始化中声明的匿名类没有所在的方法
+        let field_initializer = read(include_bytes!("./classes/Nesting$2.class"));
+        assert!(field_initializer.is_anonymous());
+        assert_eq!(None, field_initializer.enclosing_method.as_ref().unwrap().method);
+
+        let local = read(include_bytes!("./classes/Nesting$1Local.class"));
+        assert!(!local.is_anonymous());
+        assert_eq!(Some("rjvm/Nesting"), local.outer_class());
+        assert!(local.is_nest_mate_of(&anonymous));
+
+        // Java 11 之前编译的嵌套类没有 NestHost 属性, 自己就是一个 nest
+        let generics_inner = read(include_bytes!("./classes/Generics$Inner.class"));
+        assert_eq!(Some("rjvm/Generics"), generics_inner.outer_class());
+        assert!(!generics_inner.is_nest_mate_of(&host));
+        assert_eq!(vec!["rjvm/Generics$Inner"], generics_inner.nest_mates());
+    }
+}
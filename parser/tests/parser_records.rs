@@ -0,0 +1,70 @@
+#[cfg(test)]
+mod test {
+    use parser::flags::MethodParameterFlags;
+    use parser::log::{init_log, LogLevel};
+    use parser::read_buffer;
+    use parser::utils::types::{BaseType, Type};
+
+    #[test]
+    fn test_read_file() {
+        init_log(LogLevel::INFO);
+
+        let bytes = include_bytes!("./classes/Records.class");
+        let class_file = read_buffer(bytes).unwrap();
+        println!("The readed class file is: \n {}", class_file);
+
+        assert!(class_file.is_record());
+        let components = class_file.record_components.as_ref().unwrap();
+        let names: Vec<&str> = components.iter().map(|component| component.name.as_str()).collect();
+        assert_eq!(vec!["x", "tags", "weight"], names);
+        assert_eq!(Type::Base(BaseType::Int), components[0].type_descriptor);
+        assert_eq!(None, components[0].signature);
+        assert!(components[0].annotations.is_empty());
+
+        // 泛型组件有自己的签名, 只有 @Target(RECORD_COMPONENT) 的注解会保留在组件上
+        assert_eq!("java.util.List<T>", components[1].signature.as_ref().unwrap().to_string());
+        assert_eq!("@rjvm.Records$Key(value=\"id\")", components[1].annotations.visible[0].to_string());
+        assert_eq!(
+            "rjvm/Records$Positive",
+            components[2].type_annotations.visible[0].annotation.type_name
+        );
+    }
+
+    #[test]
+    fn can_read_parameter_names() {
+        let bytes = include_bytes!("./classes/Records.class");
+        let class_file = read_buffer(bytes).unwrap();
+
+        let constructor = class_file.methods.iter().find(|method| method.name == "<init>").unwrap();
+        assert_eq!(
+            vec![Some("x"), Some("tags"), Some("weight"), None],
+            (0..4).map(|index| constructor.parameter_name(index)).collect::<Vec<_>>()
+        );
+
+        let method = class_file.methods.iter().find(|method| method.name == "move").unwrap();
+        assert_eq!(MethodParameterFlags::FINAL, method.parameters[0].flags);
+        assert_eq!(Some("label"), method.parameter_name(1));
+
+        // 没有 -parameters 时从局部变量表中获取参数名, long 占据两个 slot
+        let bytes = include_bytes!("./classes/LocalVariables.class");
+        let class_file = read_buffer(bytes).unwrap();
+        let method = class_file.methods.iter().find(|method| method.name == "repeat").unwrap();
+        assert!(method.parameters.is_empty());
+        assert_eq!((Some("value"), Some("times")), (method.parameter_name(0), method.parameter_name(1)));
+        // 没有调试信息时无法得到参数名
+        let bytes = include_bytes!("./classes/Records$Square.class");
+        let class_file = read_buffer(bytes).unwrap();
+        assert!(class_file.methods.iter().all(|method| method.parameter_name(0).is_none()));
+    }
+
+    #[test]
+    fn can_read_permitted_subclasses() {
+        let shape = read_buffer(include_bytes!("./classes/Records$Shape.class")).unwrap();
+        assert!(shape.is_sealed());
+        assert!(!shape.is_record());
+        assert_eq!(vec!["rjvm/Records$Circle", "rjvm/Records$Square"], shape.permitted_subclasses);
+
+        let square = read_buffer(include_bytes!("./classes/Records$Square.class")).unwrap();
+        assert!(!square.is_sealed());
+    }
+}
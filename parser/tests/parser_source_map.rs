@@ -0,0 +1,105 @@
+#[cfg(test)]
+mod test {
+    use parser::class::ClassFile;
+    use parser::log::{init_log, LogLevel};
+    use parser::utils::line_number::LineNumber;
+    use parser::utils::pc::ProgramCounter;
+    use parser::{read_buffer, read_buffer_lenient, write_buffer};
+
+    /// 假设 scopes 方法的第 19 到 26 行是从 Scopes.kt 中内联进来的, 调用处在 LocalVariables.kt 的第 17 行
+    const SOURCE_MAP: &str = "SMAP
+LocalVariables.kt
+Kotlin
+*S Kotlin
+*F
++ 1 LocalVariables.kt
+rjvm/LocalVariables
++ 2 Scopes.kt
+rjvm/ScopesKt
+*L
+1#1,18:1
+40#2,8:19
+*E
+*S KotlinDebug
+*F
++ 1 LocalVariables.kt
+rjvm/LocalVariables
+*L
+17#1:19,8
+*E
+";
+
+    /// javac 不会生成 SourceDebugExtension, 所以把 SMAP 加到已有的 class 文件上
+    fn read_with_source_map() -> ClassFile {
+        let mut class_file = read_buffer(include_bytes!("./classes/LocalVariables.class")).unwrap();
+        class_file.source_debug_extension = Some(SOURCE_MAP.to_string());
+        read_buffer(&write_buffer(&class_file).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_read_file() {
+        init_log(LogLevel::INFO);
+
+        let class_file = read_with_source_map();
+        println!("The readed class file is: \n {}", class_file);
+
+        assert_eq!(Some(SOURCE_MAP), class_file.source_debug_extension.as_deref());
+        let source_map = class_file.source_map.as_ref().unwrap();
+        assert_eq!("Kotlin", source_map.default_stratum);
+        let strata: Vec<&str> = source_map.strata.iter().map(|stratum| stratum.name.as_str()).collect();
+        assert_eq!(vec!["Kotlin", "KotlinDebug"], strata);
+
+        let method = class_file.find_method("scopes", "(I)I").unwrap();
+        // pc 9 在第 24 行
+        let pc = ProgramCounter(9);
+        assert_eq!(Some(LineNumber(24)), method.code.as_ref().unwrap().line_number_of(pc));
+        let location = class_file.source_location(method, pc, None).unwrap();
+        assert_eq!("Scopes.kt:45", location.to_string());
+        assert_eq!(Some("rjvm/ScopesKt"), location.path.as_deref());
+        let location = class_file.source_location(method, pc, Some("KotlinDebug")).unwrap();
+        assert_eq!("LocalVariables.kt:17", location.to_string());
+        let location = class_file.source_location(method, pc, Some("Java")).unwrap();
+        assert_eq!("LocalVariables.kt:24", location.to_string());
+        assert_eq!(None, class_file.source_location(method, pc, Some("JSP")));
+
+        // 没有被内联的代码
+        let method = class_file.find_method("repeat", "(Ljava/lang/Object;I)Ljava/util/List;").unwrap();
+        let location = class_file.source_location(method, ProgramCounter(15), None).unwrap();
+        assert_eq!("LocalVariables.kt:10", location.to_string());
+        assert_eq!(None, class_file.source_location(method, ProgramCounter(15), Some("KotlinDebug")));
+    }
+
+    #[test]
+    fn test_without_source_map() {
+        init_log(LogLevel::INFO);
+
+        let class_file = read_buffer(include_bytes!("./classes/LocalVariables.class")).unwrap();
+        assert_eq!(None, class_file.source_map);
+        let method = class_file.find_method("scopes", "(I)I").unwrap();
+        let location = class_file.source_location(method, ProgramCounter(9), None).unwrap();
+        assert_eq!("LocalVariables.java:24", location.to_string());
+        assert_eq!(None, class_file.source_location(method, ProgramCounter(9), Some("Kotlin")));
+    }
+
+    #[test]
+    fn test_invalid_source_map() {
+        init_log(LogLevel::INFO);
+
+        // 以 SMAP 开头但是缺少默认的 stratum
+        let mut class_file = read_buffer(include_bytes!("./classes/LocalVariables.class")).unwrap();
+        class_file.source_debug_extension = Some("SMAP\nLocalVariables.kt\n".to_string());
+        let bytes = write_buffer(&class_file).unwrap();
+
+        let class_file = read_buffer(&bytes).unwrap();
+        assert_eq!(Some("SMAP\nLocalVariables.kt\n"), class_file.source_debug_extension.as_deref());
+        assert_eq!(None, class_file.source_map);
+
+        let report = read_buffer_lenient(&bytes);
+        assert!(!report.has_errors());
+        let warnings: Vec<_> = report.warnings().collect();
+        assert_eq!(1, warnings.len());
+        assert_eq!("attributes[SourceDebugExtension]", warnings[0].error.path().unwrap().to_string());
+        assert_eq!(class_file.source_debug_extension, report.class_file.source_debug_extension);
+        assert_eq!(None, report.class_file.source_map);
+    }
+}
@@ -0,0 +1,82 @@
+#[cfg(test)]
+mod test {
+    use parser::log::{init_log, LogLevel};
+    use parser::method::stack_map_table::{StackMapFrameType, VerificationType};
+    use parser::read_buffer;
+    use parser::utils::pc::ProgramCounter;
+
+    fn frames_of(class_file: &parser::class::ClassFile, name: &str) -> Vec<(u16, StackMapFrameType)> {
+        let method = class_file.methods.iter().find(|method| method.name == name).unwrap();
+        method
+            .code
+            .as_ref()
+            .unwrap()
+            .stack_map_table
+            .as_ref()
+            .unwrap()
+            .frames()
+            .iter()
+            .map(|frame| (frame.program_counter.0, frame.frame_type.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_read_file() {
+        init_log(LogLevel::INFO);
+
+        let bytes = include_bytes!("./classes/StackMaps.class");
+        let class_file = read_buffer(bytes).unwrap();
+        println!("The readed class file is: \n {}", class_file);
+
+        // 地址由 offset_delta 累加得到: 4, 4 + 13 + 1, 18 + 3 + 1, 22 + 5 + 1
+        assert_eq!(
+            vec![
+                (4, StackMapFrameType::Append(vec![VerificationType::Integer, VerificationType::Integer])),
+                (18, StackMapFrameType::Same),
+                (22, StackMapFrameType::Same),
+                (28, StackMapFrameType::Chop(1)),
+            ],
+            frames_of(&class_file, "loop")
+        );
+
+        let this = VerificationType::Object("rjvm/StackMaps".to_string());
+        let uninitialized = VerificationType::Uninitialized(ProgramCounter(0));
+        assert_eq!(
+            vec![
+                (
+                    13,
+                    StackMapFrameType::Full {
+                        locals: vec![this.clone(), VerificationType::Integer],
+                        stack: vec![uninitialized.clone(), uninitialized.clone()],
+                    }
+                ),
+                (
+                    15,
+                    StackMapFrameType::Full {
+                        locals: vec![this, VerificationType::Integer],
+                        stack: vec![
+                            uninitialized.clone(),
+                            uninitialized,
+                            VerificationType::Object("java/lang/String".to_string())
+                        ],
+                    }
+                ),
+            ],
+            frames_of(&class_file, "uninitialized")
+        );
+
+        let frames = frames_of(&class_file, "full");
+        assert_eq!(
+            StackMapFrameType::SameLocals1StackItem(VerificationType::Object("java/lang/Object".to_string())),
+            frames[2].1
+        );
+
+        let frames = frames_of(&class_file, "extended");
+        assert_eq!(StackMapFrameType::SameExtended, frames[0].1);
+        assert_eq!(StackMapFrameType::Same, frames[1].1);
+        assert_eq!(
+            StackMapFrameType::SameLocals1StackItemExtended(VerificationType::Integer),
+            frames[2].1
+        );
+    }
+}
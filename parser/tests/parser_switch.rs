@@ -0,0 +1,25 @@
+#[cfg(test)]
+mod test {
+    use parser::read_buffer;
+    use parser::log::{init_log, LogLevel};
+
+    #[test]
+    fn test_read_file() {
+        init_log(LogLevel::INFO);
+
+        // switch 语句会被编译为 tableswitch 与 lookupswitch, 较大的增量会使用 wide
+        let bytes = include_bytes!("./classes/Switch.class");
+        let class_file = read_buffer(bytes).unwrap();
+
+        let text = format!("{}", class_file);
+        println!("The readed class file is: \n {}", text);
+        assert!(!text.contains("unparseable code"));
+        assert!(text.contains(
+            "Tableswitch(TableSwitch { padding: 2, default: 37, low: 1, high: 3, offsets: [28, 31, 34] })"
+        ));
+        assert!(text.contains(
+            "Lookupswitch(LookupSwitch { padding: 2, default: 42, match_offsets: [(-100, 36), (0, 38), (100000, 40)] })"
+        ));
+        assert!(text.contains("Wide(Iinc(0, 1000))"));
+    }
+}
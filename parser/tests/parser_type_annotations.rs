@@ -0,0 +1,83 @@
+#[cfg(test)]
+mod test {
+    use parser::annotation::type_annotations::{
+        LocalVariableTarget, TypeAnnotation, TypeAnnotationTarget, TypePathEntry,
+    };
+    use parser::log::{init_log, LogLevel};
+    use parser::read_buffer;
+    use parser::utils::pc::ProgramCounter;
+
+    const NULLABLE: &str = "rjvm/Nullable";
+
+    fn targets<'a>(type_annotations: impl Iterator<Item = &'a TypeAnnotation>) -> Vec<String> {
+        type_annotations.map(|annotation| annotation.target.to_string()).collect()
+    }
+
+    #[test]
+    fn test_read_file() {
+        init_log(LogLevel::INFO);
+
+        let bytes = include_bytes!("./classes/TypeAnnotations.class");
+        let class_file = read_buffer(bytes).unwrap();
+        println!("The readed class file is: \n {}", class_file);
+
+        assert_eq!(
+            vec!["CLASS_EXTENDS, type_index=0", "CLASS_TYPE_PARAMETER, param_index=0"],
+            targets(class_file.type_annotations.of_type(NULLABLE))
+        );
+
+        let field_annotations = |name: &str| {
+            let field = class_file.fields.iter().find(|field| field.name == name).unwrap();
+            field.type_annotations.visible.clone()
+        };
+        // List<@Nullable String>
+        let names = field_annotations("names");
+        assert_eq!(vec![TypePathEntry::TypeArgument(0)], names[0].type_path);
+        // String @Nullable [] 注解的是数组类型本身
+        assert!(field_annotations("array")[0].is_on_whole_type());
+        // Map.@Nullable Entry<String, ? extends @Nullable Number>
+        let entry = field_annotations("entry");
+        assert_eq!(2, entry.len());
+        assert_eq!(vec![TypePathEntry::TypeArgument(1), TypePathEntry::WildcardBound], entry[1].type_path);
+
+        let method = class_file.methods.iter().find(|method| method.name == "find").unwrap();
+        assert!(method
+            .type_annotations
+            .of_type(NULLABLE)
+            .any(|annotation| annotation.target == TypeAnnotationTarget::MethodReturn));
+        assert!(method
+            .type_annotations
+            .of_type(NULLABLE)
+            .any(|annotation| annotation.target == TypeAnnotationTarget::Throws(0)));
+        // RetentionPolicy.CLASS 的类型注解是运行时不可见的
+        assert_eq!(TypeAnnotationTarget::FormalParameter(0), method.type_annotations.invisible[0].target);
+        assert_eq!("rjvm/NonNull", method.type_annotations.invisible[0].annotation.type_name);
+
+        // 方法体中的类型注解保存在 Code 属性中, 可以对应到指令地址、局部变量表与异常表
+        let code = method.code.as_ref().unwrap();
+        assert_eq!(
+            vec![
+                TypeAnnotationTarget::New(ProgramCounter(0)),
+                TypeAnnotationTarget::Instanceof(ProgramCounter(9)),
+                TypeAnnotationTarget::Cast {
+                    program_counter: ProgramCounter(16),
+                    type_argument_index: 0
+                },
+                TypeAnnotationTarget::LocalVariable(vec![LocalVariableTarget {
+                    range: ProgramCounter(8)..ProgramCounter(25),
+                    slot: 2
+                }]),
+                TypeAnnotationTarget::ExceptionParameter(0),
+            ],
+            code.type_annotations
+                .of_type(NULLABLE)
+                .map(|annotation| annotation.target.clone())
+                .collect::<Vec<TypeAnnotationTarget>>()
+        );
+        let local_variable_table = code.local_variable_table.as_ref().unwrap();
+        let local_variable = &code.type_annotations.visible[3];
+        assert_eq!("value", local_variable.local_variables(local_variable_table)[0].name);
+        let catch = code.type_annotations.visible[4].exception_table_entry(&code.exception_table).unwrap();
+        assert_eq!(Some("java/lang/RuntimeException".to_string()), catch.catch_class);
+    }
+}
@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    use parser::class::ClassFile;
+    use parser::constant_pool::constant_pool::{ConstantPool, ConstantPoolEntry};
+    use parser::flags::{ClassAccessFlags, FieldFlags, MethodFlags};
+    use parser::read_buffer;
+    use parser::validation::{validate, Violation};
+
+    fn read(name: &str) -> ClassFile {
+        let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "classes", name].iter().collect();
+        read_buffer(&fs::read(path).unwrap()).unwrap()
+    }
+
+    fn describe(violations: &[Violation]) -> Vec<String> {
+        violations.iter().map(|violation| violation.to_string()).collect()
+    }
+
+    fn class_files(dir: &Path) -> Vec<PathBuf> {
+        let mut paths = Vec::new();
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                paths.extend(class_files(&path));
+            } else if path.extension().is_some_and(|extension| extension == "class") {
+                paths.push(path);
+            }
+        }
+        paths
+    }
+
+    #[test]
+    fn test_fixtures_are_valid() {
+        let dir: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "classes"].iter().collect();
+        let paths = class_files(&dir);
+        assert!(paths.iter().any(|path| path.ends_with("module/module-info.class")));
+        for path in paths {
+            let class_file = read_buffer(&fs::read(&path).unwrap()).unwrap();
+            assert_eq!(Vec::<String>::new(), describe(&validate(&class_file)), "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn test_interface_without_abstract() {
+        let mut class_file = read("Nesting$Callback.class");
+        class_file.flags.remove(ClassAccessFlags::ABSTRACT);
+
+        let violations = validate(&class_file);
+        assert_eq!(
+            vec!["access_flags: interface must be ACC_ABSTRACT (JVMS §4.1)"],
+            describe(&violations)
+        );
+        assert_eq!(
+            "https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-4.html#jvms-4.1",
+            violations[0].url()
+        );
+    }
+
+    #[test]
+    fn test_conflicting_field_flags() {
+        let mut class_file = read("Constants.class");
+        class_file.fields[0].flags.insert(FieldFlags::PRIVATE | FieldFlags::VOLATILE);
+
+        assert_eq!(
+            vec![
+                "fields[0].access_flags: field has more than one access flag: \
+                 ACC_PUBLIC, ACC_PRIVATE, ACC_STATIC, ACC_FINAL, ACC_VOLATILE (JVMS §4.5)",
+                "fields[0].access_flags: field must not be both ACC_FINAL and ACC_VOLATILE (JVMS §4.5)",
+            ],
+            describe(&validate(&class_file))
+        );
+    }
+
+    #[test]
+    fn test_class_initialization_method_without_static() {
+        let mut class_file = read("Complex.class");
+        let method = class_file.methods.iter_mut().find(|method| method.name == "getReal").unwrap();
+        method.name = "<clinit>".to_string();
+
+        let violations = validate(&class_file);
+        assert_eq!(2, violations.len());
+        assert_eq!(vec!["2.9.2", "2.9.2"], violations.iter().map(|violation| violation.section).collect::<Vec<_>>());
+        assert!(violations[0].message.contains("must be ACC_STATIC"));
+        assert!(violations[1].message.contains("must be ()V but is ()D"));
+    }
+
+    #[test]
+    fn test_duplicate_members() {
+        let mut class_file = read("Complex.class");
+        let field = class_file.fields[0].name.clone();
+        class_file.fields[1].name = field.clone();
+        let method = class_file.methods.iter_mut().find(|method| method.name == "getImag").unwrap();
+        method.name = "getReal".to_string();
+
+        assert_eq!(
+            vec![
+                format!("fields[1]: duplicate field {field} D (JVMS §4.5)"),
+                "methods[3]: duplicate method getReal()D (JVMS §4.6)".to_string(),
+            ],
+            describe(&validate(&class_file))
+        );
+    }
+
+    #[test]
+    fn test_class_reference_to_integer() {
+        let mut class_file = read("Constants.class");
+        let (integer, _) = class_file
+            .constants
+            .iter()
+            .find(|(_, entry)| matches!(entry, ConstantPoolEntry::Integer(_)))
+            .unwrap();
+        let mut constants = ConstantPool::default();
+        let mut class_reference = None;
+        for (index, entry) in class_file.constants.iter() {
+            match entry {
+                ConstantPoolEntry::ClassReference(_) if class_reference.is_none() => {
+                    class_reference = Some(index);
+                    constants.add_entry(ConstantPoolEntry::ClassReference(integer));
+                }
+                entry => constants.add_entry(entry.clone()),
+            }
+        }
+        class_file.constants = constants;
+
+        let violations = validate(&class_file);
+        assert_eq!(1, violations.len());
+        assert_eq!("4.4.1", violations[0].section);
+        assert_eq!(format!("constant_pool[{}]", class_reference.unwrap()), violations[0].path.to_string());
+        assert_eq!(
+            format!("CONSTANT_Class must reference a CONSTANT_Utf8 but #{integer} is a CONSTANT_Integer"),
+            violations[0].message
+        );
+    }
+
+    #[test]
+    fn test_code_on_abstract_method() {
+        let mut class_file = read("Complex.class");
+        let method = class_file.methods.iter_mut().find(|method| method.name == "abs").unwrap();
+        method.flags.insert(MethodFlags::ABSTRACT);
+
+        assert_eq!(
+            vec!["methods[4].attributes[Code]: ACC_ABSTRACT method abs must not have a Code attribute (JVMS §4.7.3)"],
+            describe(&validate(&class_file))
+        );
+    }
+}
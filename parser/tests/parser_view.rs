@@ -0,0 +1,101 @@
+#[cfg(test)]
+mod test {
+    use parser::flags::{FieldFlags, MethodFlags};
+    use parser::read_buffer;
+    use parser::view::ClassFileView;
+
+    const CLASSES: [&[u8]; 6] = [
+        include_bytes!("./classes/Constants.class"),
+        include_bytes!("./classes/Complex.class"),
+        include_bytes!("./classes/Generics.class"),
+        include_bytes!("./classes/Lambda.class"),
+        include_bytes!("./classes/Records.class"),
+        include_bytes!("./classes/Switch.class"),
+    ];
+
+    #[test]
+    fn test_view_matches_eager_reader() {
+        for bytes in CLASSES {
+            let class_file = read_buffer(bytes).unwrap();
+            let view = ClassFileView::parse(bytes).unwrap();
+
+            assert_eq!(class_file.version.major_version(), view.version().major_version());
+            assert_eq!(class_file.flags.bits(), view.flags().bits());
+            assert_eq!(class_file.name, view.name().unwrap());
+            assert_eq!(class_file.superclass.as_deref(), view.superclass().unwrap().as_deref());
+            let interfaces: Vec<String> = view.interfaces().map(|name| name.unwrap().into_owned()).collect();
+            assert_eq!(class_file.interfaces, interfaces);
+            assert_eq!(class_file.constants.len(), view.constants().len());
+
+            assert_eq!(class_file.fields.len(), view.fields().len());
+            for (field, field_view) in class_file.fields.iter().zip(view.fields()) {
+                assert_eq!(field.name, field_view.name().unwrap());
+                assert_eq!(field.flags.bits(), field_view.flags::<FieldFlags>().bits());
+                assert!(field_view.code().unwrap().is_none());
+            }
+
+            assert_eq!(class_file.methods.len(), view.methods().len());
+            for (method, method_view) in class_file.methods.iter().zip(view.methods()) {
+                assert_eq!(method.name, method_view.name().unwrap());
+                assert_eq!(method.type_descriptor, method_view.descriptor().unwrap());
+                assert_eq!(method.flags.bits(), method_view.flags::<MethodFlags>().bits());
+                let attributes: Vec<_> =
+                    method_view.attributes().map(|attribute| attribute.to_attribute().unwrap()).collect();
+                assert_eq!(method.attributes, attributes);
+
+                let code = method_view.code().unwrap();
+                assert_eq!(method.code.is_some(), code.is_some());
+                if let (Some(code), Some(code_view)) = (&method.code, code) {
+                    assert_eq!(code.max_stack, code_view.max_stack);
+                    assert_eq!(code.max_locals, code_view.max_locals);
+                    assert_eq!(code.code, code_view.code);
+                    assert_eq!(code.exception_table.entries().len(), usize::from(code_view.exception_table_length()));
+                    let names: Vec<_> = code_view.attributes().map(|attribute| attribute.name().unwrap()).collect();
+                    let expected: Vec<_> = code.attributes.iter().map(|attribute| attribute.name.as_str()).collect();
+                    assert_eq!(expected, names);
+                }
+            }
+
+            let attributes: Vec<_> = view.attributes().map(|attribute| attribute.to_attribute().unwrap()).collect();
+            assert_eq!(class_file.attributes, attributes);
+            assert_eq!(class_file.name, view.to_class_file().unwrap().name);
+        }
+    }
+
+    #[test]
+    fn test_view_borrows_the_input() {
+        let bytes = include_bytes!("./classes/Complex.class");
+        let view = ClassFileView::parse(bytes).unwrap();
+        let input = bytes.as_ptr_range();
+
+        let method = view.methods().find(|method| method.code().unwrap().is_some()).unwrap();
+        let code = method.code().unwrap().unwrap();
+        assert!(input.contains(&code.code.as_ptr()));
+        let attribute = method.attribute("Code").unwrap().unwrap();
+        assert_eq!(&bytes[attribute.offset..attribute.offset + attribute.bytes.len()], attribute.bytes);
+        assert!(input.contains(&view.name().unwrap().as_ptr()));
+    }
+
+    #[test]
+    fn test_find_method() {
+        let bytes = include_bytes!("./classes/Constants.class");
+        let view = ClassFileView::parse(bytes).unwrap();
+
+        assert!(view.method("<init>", "()V").unwrap().is_some());
+        assert!(view.method("<init>", "(I)V").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_view_reports_structural_errors() {
+        let bytes = include_bytes!("./classes/Constants.class");
+        let err = ClassFileView::parse(&bytes[..bytes.len() - 1]).err().unwrap();
+        assert!(err.is_truncated());
+        assert_eq!("attributes", err.path().unwrap().to_string());
+
+        let mut corrupted = bytes.to_vec();
+        corrupted[3] = 0xBF;
+        let err = ClassFileView::parse(&corrupted).err().unwrap();
+        assert_eq!(Some(0), err.offset());
+        assert_eq!("magic", err.path().unwrap().to_string());
+    }
+}
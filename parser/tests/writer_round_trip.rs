@@ -0,0 +1,110 @@
+#[cfg(test)]
+mod test {
+    use parser::constant_pool::constant_pool::ConstantPoolEntry;
+    use parser::error::ClassFileParserError;
+    use parser::field::class_filed::FieldConstantValue;
+    use parser::{read_buffer, write_buffer};
+    use parser::log::{init_log, LogLevel};
+
+    /// 未经修改的 ClassFile 写回之后, 字节应该与原文件完全一致
+    fn assert_round_trip(bytes: &[u8]) {
+        let class_file = read_buffer(bytes).unwrap();
+        let written = write_buffer(&class_file).unwrap();
+        assert_eq!(bytes, written.as_slice());
+
+        let reread = read_buffer(&written).unwrap();
+        assert_eq!(format!("{}", class_file), format!("{}", reread));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        init_log(LogLevel::INFO);
+
+        assert_round_trip(include_bytes!("./classes/Complex.class"));
+        assert_round_trip(include_bytes!("./classes/Constants.class"));
+        assert_round_trip(include_bytes!("./classes/Switch.class"));
+        assert_round_trip(include_bytes!("./classes/Generics.class"));
+        assert_round_trip(include_bytes!("./classes/StackMaps.class"));
+        assert_round_trip(include_bytes!("./classes/LocalVariables.class"));
+        assert_round_trip(include_bytes!("./classes/Annotations.class"));
+        assert_round_trip(include_bytes!("./classes/TypeAnnotations.class"));
+        assert_round_trip(include_bytes!("./classes/Nesting.class"));
+        assert_round_trip(include_bytes!("./classes/Records.class"));
+        assert_round_trip(include_bytes!("./classes/module/module-info.class"));
+        // BootstrapMethods 重新编码之后与原样写出的 InnerClasses 等原始属性合并写回
+        assert_round_trip(include_bytes!("./classes/Lambda.class"));
+    }
+
+    #[test]
+    fn test_write_modified_class() {
+        init_log(LogLevel::INFO);
+
+        let bytes = include_bytes!("./classes/Constants.class");
+        let mut class_file = read_buffer(bytes).unwrap();
+        let constants_count = class_file.constants.len();
+
+        // 新的类名与常量值不在常量池中, 会被追加到常量池的末尾
+        class_file.name = "rjvm/Renamed".to_string();
+        let field = class_file
+            .fields
+            .iter_mut()
+            .find(|field| field.name == "A_STRING")
+            .unwrap();
+        field.constant_value = Some(FieldConstantValue::String("2024".to_string()));
+        let method = class_file.methods.iter_mut().find(|method| method.code.is_some()).unwrap();
+        method.code.as_mut().unwrap().max_stack += 1;
+        let max_stack = method.code.as_ref().unwrap().max_stack;
+
+        let written = write_buffer(&class_file).unwrap();
+        let reread = read_buffer(&written).unwrap();
+        assert_eq!("rjvm/Renamed", reread.name);
+        assert_eq!(constants_count + 4, reread.constants.len());
+        let field = reread.fields.iter().find(|field| field.name == "A_STRING").unwrap();
+        assert_eq!(Some(FieldConstantValue::String("2024".to_string())), field.constant_value);
+        let method = reread.methods.iter().find(|method| method.code.is_some()).unwrap();
+        assert_eq!(max_stack, method.code.as_ref().unwrap().max_stack);
+    }
+
+    fn modified_error(what: &str) -> ClassFileParserError {
+        ClassFileParserError::InvalidClassData {
+            name: format!("{what} was modified, but it can only be written from its raw attribute"),
+            is_invalidate_constant_pool_idx: false,
+        }
+    }
+
+    #[test]
+    fn test_write_modified_raw_attribute_fields() {
+        init_log(LogLevel::INFO);
+
+        // Signature、InnerClasses 与 LineNumberTable 原样写出, 修改对应的字段之后不能写回
+        let mut class_file = read_buffer(include_bytes!("./classes/Generics.class")).unwrap();
+        class_file.signature = None;
+        assert_eq!(Err(modified_error("signature of class rjvm/Generics")), write_buffer(&class_file));
+
+        let mut class_file = read_buffer(include_bytes!("./classes/Nesting.class")).unwrap();
+        class_file.inner_classes.pop();
+        assert_eq!(Err(modified_error("inner_classes of class rjvm/Nesting")), write_buffer(&class_file));
+
+        let mut class_file = read_buffer(include_bytes!("./classes/LocalVariables.class")).unwrap();
+        let method = class_file.methods.iter_mut().find(|method| method.name == "<init>").unwrap();
+        method.code.as_mut().unwrap().line_number_table = None;
+        assert_eq!(
+            Err(modified_error("line_number_table of method <init>()V")),
+            write_buffer(&class_file)
+        );
+    }
+
+    #[test]
+    fn test_write_self_referencing_class_constant() {
+        init_log(LogLevel::INFO);
+
+        // 在常量池末尾追加一个指向自己的 CONSTANT_Class, 查找已有的类常量时不能解析它
+        let mut class_file = read_buffer(include_bytes!("./classes/Constants.class")).unwrap();
+        let index = class_file.constants.len() as u16 + 1;
+        class_file.constants.add_entry(ConstantPoolEntry::ClassReference(index));
+        let written = write_buffer(&class_file).unwrap();
+        let reread = read_buffer(&written).unwrap();
+        assert_eq!(class_file.name, reread.name);
+        assert_eq!(Ok(&ConstantPoolEntry::ClassReference(index)), reread.constants.get_entry(index));
+    }
+}
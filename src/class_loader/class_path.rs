@@ -0,0 +1,143 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::fmt::{Debug, Formatter};
+use std::fs::File;
+use std::io::{BufReader, ErrorKind, Read};
+use std::path::{Path, PathBuf};
+
+use zip::result::ZipError;
+use zip::ZipArchive;
+
+use crate::class_loader::error::{ClassLoaderError, ClassLoaderResult};
+
+/// classpath 中的一项, 可以是一个目录或者一个 jar 文件
+pub trait ClassPathEntry: Debug {
+    /// 根据二进制类名 (例如 `java/lang/Object`) 查找 class 文件的内容, 找不到时返回 None
+    fn resolve(&self, class_name: &str) -> ClassLoaderResult<Option<Vec<u8>>>;
+}
+
+/// 有序的 classpath, 查找类时按照顺序依次查找每一项
+#[derive(Debug, Default)]
+pub struct ClassPath {
+    entries: Vec<Box<dyn ClassPathEntry>>,
+}
+
+impl ClassPath {
+    /// 解析形如 `classes:lib/a.jar` 的 classpath, 分隔符与平台一致 (unix 为 `:`, windows 为 `;`)。
+    /// 以 `.jar` 结尾的项被当作 jar 文件, 其余的被当作目录
+    pub fn parse(class_path: &str) -> ClassLoaderResult<Self> {
+        let mut result = Self::default();
+        for path in std::env::split_paths(class_path) {
+            if path.as_os_str().is_empty() {
+                continue;
+            }
+            result.push_path(&path)?;
+        }
+        Ok(result)
+    }
+
+    pub fn push_path(&mut self, path: &Path) -> ClassLoaderResult<()> {
+        let is_jar = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("jar"));
+        if is_jar {
+            self.push(Box::new(JarClassPathEntry::new(path)?));
+        } else {
+            self.push(Box::new(DirectoryClassPathEntry::new(path)?));
+        }
+        Ok(())
+    }
+
+    pub fn push(&mut self, entry: Box<dyn ClassPathEntry>) {
+        self.entries.push(entry);
+    }
+
+    /// 按照顺序查找类, 返回第一个找到的 class 文件内容
+    pub fn resolve(&self, class_name: &str) -> ClassLoaderResult<Option<Vec<u8>>> {
+        for entry in self.entries.iter() {
+            if let Some(bytes) = entry.resolve(class_name)? {
+                return Ok(Some(bytes));
+            }
+        }
+        Ok(None)
+    }
+}
+
+/// 目录形式的 classpath, 类 `a/b/C` 对应文件 `<dir>/a/b/C.class`
+#[derive(Debug)]
+pub struct DirectoryClassPathEntry {
+    directory: PathBuf,
+}
+
+impl DirectoryClassPathEntry {
+    pub fn new(directory: &Path) -> ClassLoaderResult<Self> {
+        if !directory.is_dir() {
+            return Err(ClassLoaderError::InvalidClassPathEntry(
+                directory.display().to_string(),
+                "not a directory".to_string(),
+            ));
+        }
+        Ok(Self {
+            directory: directory.to_path_buf(),
+        })
+    }
+}
+
+impl ClassPathEntry for DirectoryClassPathEntry {
+    fn resolve(&self, class_name: &str) -> ClassLoaderResult<Option<Vec<u8>>> {
+        let mut path = self.directory.clone();
+        path.extend(class_name.split('/'));
+        path.set_extension("class");
+        match std::fs::read(&path) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(ClassLoaderError::Io(path.display().to_string(), err.to_string())),
+        }
+    }
+}
+
+/// jar 文件形式的 classpath, 在创建时打开文件并读取目录, 之后按需解压 entry
+pub struct JarClassPathEntry {
+    path: PathBuf,
+    archive: RefCell<ZipArchive<BufReader<File>>>,
+}
+
+impl JarClassPathEntry {
+    pub fn new(path: &Path) -> ClassLoaderResult<Self> {
+        let file = File::open(path)
+            .map_err(|err| ClassLoaderError::Io(path.display().to_string(), err.to_string()))?;
+        let archive = ZipArchive::new(BufReader::new(file)).map_err(|err| {
+            ClassLoaderError::InvalidClassPathEntry(path.display().to_string(), err.to_string())
+        })?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            archive: RefCell::new(archive),
+        })
+    }
+}
+
+impl Debug for JarClassPathEntry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "JarClassPathEntry {{ path: {:?} }}", self.path)
+    }
+}
+
+impl ClassPathEntry for JarClassPathEntry {
+    fn resolve(&self, class_name: &str) -> ClassLoaderResult<Option<Vec<u8>>> {
+        let mut archive = self.archive.borrow_mut();
+        let entry_name = format!("{class_name}.class");
+        let io_error = |err: String| ClassLoaderError::Io(format!("{}!{}", self.path.display(), entry_name), err);
+        let result = match archive.by_name(&entry_name) {
+            Ok(mut entry) => {
+                let mut bytes = Vec::with_capacity(entry.size() as usize);
+                entry
+                    .read_to_end(&mut bytes)
+                    .map_err(|err| io_error(err.to_string()))?;
+                Ok(Some(bytes))
+            }
+            Err(ZipError::FileNotFound) => Ok(None),
+            Err(err) => Err(io_error(err.to_string())),
+        };
+        result
+    }
+}
@@ -0,0 +1,26 @@
+use thiserror::Error;
+use parser::error::ClassFileParserError;
+
+/// 类加载 Result
+pub type ClassLoaderResult<T> = std::result::Result<T, ClassLoaderError>;
+
+/// 类加载 error, 需要区分类不存在与类文件格式错误两种情况
+#[derive(Error, Debug, PartialEq)]
+pub enum ClassLoaderError {
+    #[error("java.lang.NoClassDefFoundError: {0}")]
+    ClassNotFound(String),
+    #[error("java.lang.ClassFormatError: {name}: {source}")]
+    MalformedClass {
+        name: String,
+        source: ClassFileParserError,
+    },
+    #[error("java.lang.NoClassDefFoundError: {expected} (wrong name: {found})")]
+    WrongClassName {
+        expected: String,
+        found: String,
+    },
+    #[error("invalid classpath entry {0}: {1}")]
+    InvalidClassPathEntry(String, String),
+    #[error("cannot read {0}: {1}")]
+    Io(String, String),
+}
@@ -0,0 +1,64 @@
+pub mod class_path;
+pub mod error;
+#[cfg(test)]
+mod test;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use log::debug;
+use parser::class::ClassFile;
+
+use crate::class_loader::class_path::ClassPath;
+use crate::class_loader::error::{ClassLoaderError, ClassLoaderResult};
+
+/// 类加载器, 根据二进制类名 (例如 `java/lang/Object`) 在 classpath 中查找并解析类。
+/// 解析过的类会被缓存, 同一个类只会被解析一次
+#[derive(Default)]
+pub struct ClassLoader {
+    class_path: ClassPath,
+    classes: RefCell<HashMap<String, Rc<ClassFile>>>,
+}
+
+impl ClassLoader {
+    pub fn new(class_path: ClassPath) -> Self {
+        Self {
+            class_path,
+            classes: Default::default(),
+        }
+    }
+
+    /// 加载一个类, 优先从缓存中获取
+    pub fn load(&self, class_name: &str) -> ClassLoaderResult<Rc<ClassFile>> {
+        if let Some(class) = self.classes.borrow().get(class_name) {
+            return Ok(Rc::clone(class));
+        }
+
+        let bytes = self
+            .class_path
+            .resolve(class_name)?
+            .ok_or_else(|| ClassLoaderError::ClassNotFound(class_name.to_string()))?;
+        let class_file = parser::read_buffer(&bytes).map_err(|err| ClassLoaderError::MalformedClass {
+            name: class_name.to_string(),
+            source: err,
+        })?;
+        if class_file.name != class_name {
+            return Err(ClassLoaderError::WrongClassName {
+                expected: class_name.to_string(),
+                found: class_file.name,
+            });
+        }
+        debug!("loaded class {}", class_name);
+        Ok(self.define(class_file))
+    }
+
+    /// 直接定义一个已经解析好的类, 例如从命令行指定的 class 文件
+    pub fn define(&self, class_file: ClassFile) -> Rc<ClassFile> {
+        let class = Rc::new(class_file);
+        self.classes
+            .borrow_mut()
+            .insert(class.name.clone(), Rc::clone(&class));
+        class
+    }
+}
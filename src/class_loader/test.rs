@@ -0,0 +1,76 @@
+use std::rc::Rc;
+
+use parser::field::class_filed::FieldConstantValue;
+
+use crate::class_loader::class_path::ClassPath;
+use crate::class_loader::error::ClassLoaderError;
+use crate::class_loader::ClassLoader;
+
+fn create_class_loader() -> ClassLoader {
+    ClassLoader::new(ClassPath::parse("tests/classes:tests/classes/math.jar").unwrap())
+}
+
+#[test]
+fn can_load_class_from_directory() {
+    let class = create_class_loader().load("rjvm/Application").unwrap();
+    assert_eq!("rjvm/Application", class.name);
+}
+
+#[test]
+fn can_load_class_from_jar() {
+    let class = create_class_loader().load("rjvm/MathUtils").unwrap();
+    assert_eq!("rjvm/MathUtils", class.name);
+}
+
+#[test]
+fn caches_loaded_classes() {
+    let class_loader = create_class_loader();
+    let first = class_loader.load("rjvm/MathUtils").unwrap();
+    let second = class_loader.load("rjvm/MathUtils").unwrap();
+    assert!(Rc::ptr_eq(&first, &second));
+}
+
+/// rjvm/Shadowed 在目录与 jar 中各有一份, ORIGIN 记录了加载的是哪一份
+fn origin_of_shadowed(class_path: &str) -> String {
+    let class_loader = ClassLoader::new(ClassPath::parse(class_path).unwrap());
+    let class = class_loader.load("rjvm/Shadowed").unwrap();
+    let field = class.fields.iter().find(|field| field.name == "ORIGIN").unwrap();
+    match &field.constant_value {
+        Some(FieldConstantValue::String(origin)) => origin.clone(),
+        constant_value => panic!("unexpected constant value {constant_value:?}"),
+    }
+}
+
+#[test]
+fn searches_entries_in_order() {
+    assert_eq!("jar", origin_of_shadowed("tests/classes/math.jar:tests/classes"));
+    assert_eq!("directory", origin_of_shadowed("tests/classes:tests/classes/math.jar"));
+}
+
+#[test]
+fn reports_missing_class() {
+    assert!(matches!(
+        create_class_loader().load("rjvm/Missing"),
+        Err(ClassLoaderError::ClassNotFound(name)) if name == "rjvm/Missing"
+    ));
+}
+
+#[test]
+fn reports_malformed_class() {
+    assert!(matches!(
+        create_class_loader().load("rjvm/Broken"),
+        Err(ClassLoaderError::MalformedClass { name, .. }) if name == "rjvm/Broken"
+    ));
+}
+
+#[test]
+fn reports_invalid_class_path_entries() {
+    assert!(matches!(
+        ClassPath::parse("tests/classes/missing"),
+        Err(ClassLoaderError::InvalidClassPathEntry(_, _))
+    ));
+    assert!(matches!(
+        ClassPath::parse("tests/classes/missing.jar"),
+        Err(ClassLoaderError::Io(_, _))
+    ));
+}
@@ -0,0 +1,704 @@
+#[cfg(test)]
+mod test;
+
+use std::fmt;
+use std::fmt::Formatter;
+
+use parser::class::ClassFile;
+use parser::constant_pool::constant_pool::ConstantPoolEntry;
+use parser::field::class_filed::{ClassFileField, FieldConstantValue};
+use parser::flags::{ClassAccessFlags, MethodFlags};
+use parser::method::class_method::{ClassFileMethod, ClassFileMethodCode};
+use parser::utils::instruction::{Instruction, NewArrayType, WideInstruction};
+use parser::utils::pc::ProgramCounter;
+use parser::signature::generic_type::TypeParameter;
+use parser::utils::types::Type;
+
+
+/// 与 `javap -c -l` 类似的反汇编器, verbose 对应 `javap -v`, 额外输出常量池、版本与 flags。
+/// 通过 Display 输出反汇编的结果
+pub struct Disassembler<'a> {
+    class_file: &'a ClassFile,
+    verbose: bool,
+}
+
+impl<'a> Disassembler<'a> {
+    pub fn new(class_file: &'a ClassFile, verbose: bool) -> Self {
+        Self { class_file, verbose }
+    }
+
+    fn fmt_class_header(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let class_file = self.class_file;
+        if let Some(source_file) = &class_file.source_file {
+            writeln!(f, "Compiled from \"{source_file}\"")?;
+        }
+        let is_interface = class_file.flags.contains(ClassAccessFlags::INTERFACE);
+        let mut header = class_file.flags.modifiers();
+        header.push(if is_interface { "interface" } else { "class" });
+        write!(f, "{} {}", header.join(" "), java_name(&class_file.name))?;
+
+        // 有泛型签名时与 javap 一样输出泛型信息
+        let (superclass, interfaces) = match &class_file.signature {
+            Some(signature) => {
+                write!(f, "{}", type_parameters_text(&signature.type_parameters))?;
+                (
+                    Some(signature.superclass.to_string()),
+                    signature.interfaces.iter().map(|interface| interface.to_string()).collect(),
+                )
+            }
+            None => (
+                class_file.superclass.as_deref().map(java_name),
+                class_file.interfaces.iter().map(|name| java_name(name)).collect::<Vec<_>>(),
+            ),
+        };
+        if is_interface {
+            if !interfaces.is_empty() {
+                write!(f, " extends {}", interfaces.join(","))?;
+            }
+        } else {
+            if let Some(superclass) = superclass.filter(|name| name != "java.lang.Object") {
+                write!(f, " extends {superclass}")?;
+            }
+            if !interfaces.is_empty() {
+                write!(f, " implements {}", interfaces.join(","))?;
+            }
+        }
+        if !self.verbose {
+            return writeln!(f, " {{");
+        }
+
+        writeln!(f)?;
+        writeln!(f, "  minor version: {}", class_file.version.minor_version())?;
+        writeln!(f, "  major version: {}", class_file.version.major_version())?;
+        writeln!(f, "  flags: {}", flag_names(class_file.flags.bits(), &class_file.flags))?;
+        self.fmt_constant_pool(f)?;
+        writeln!(f, "{{")
+    }
+
+    fn fmt_constant_pool(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Constant pool:")?;
+        // 与 javap 一样, 索引按照最大索引的宽度右对齐
+        let width = (self.class_file.constants.len() + 1).to_string().len() + 3;
+        for (index, entry) in self.class_file.constants.iter() {
+            let (kind, value) = constant_kind_and_value(entry);
+            let index = format!("#{index}");
+            match self.constant_comment(entry) {
+                Some(comment) => writeln!(f, "{index:>width$} = {kind:<18} {value:<14} // {comment}")?,
+                None => writeln!(f, "{index:>width$} = {kind:<18} {value}")?,
+            }
+        }
+        Ok(())
+    }
+
+    /// 常量池中引用其他常量的 entry 在 `//` 之后的注释
+    fn constant_comment(&self, entry: &ConstantPoolEntry) -> Option<String> {
+        let constants = &self.class_file.constants;
+        let text = |index: u16| match constants.text_of(index) {
+            Ok(text) => escape(&text),
+            Err(_) => format!("<invalid #{index}>"),
+        };
+        match entry {
+            ConstantPoolEntry::Utf8(_)
+            | ConstantPoolEntry::Integer(_)
+            | ConstantPoolEntry::Float(_)
+            | ConstantPoolEntry::Long(_)
+            | ConstantPoolEntry::Double(_) => None,
+            ConstantPoolEntry::ClassReference(i)
+            | ConstantPoolEntry::StringReference(i)
+            | ConstantPoolEntry::MethodType(i)
+            | ConstantPoolEntry::ModuleReference(i)
+            | ConstantPoolEntry::PackageReference(i) => Some(text(*i)),
+            ConstantPoolEntry::FieldReference(i, j)
+            | ConstantPoolEntry::MethodReference(i, j)
+            | ConstantPoolEntry::InterfaceMethodReference(i, j) => {
+                Some(format!("{}.{}", text(*i), self.name_and_type_text(*j)))
+            }
+            ConstantPoolEntry::NameAndTypeDescriptor(i, j) => {
+                Some(format!("{}:{}", quote_special_name(&text(*i)), text(*j)))
+            }
+            ConstantPoolEntry::MethodHandle(kind, i) => Some(format!("{} {}", kind, self.member_text(*i, true))),
+            ConstantPoolEntry::Dynamic(i, j) | ConstantPoolEntry::InvokeDynamic(i, j) => {
+                Some(format!("#{}:{}", i, self.name_and_type_text(*j)))
+            }
+        }
+    }
+
+    /// 常量在 BootstrapMethods 中的输出, 例如 `REF_invokeStatic a/B.c:()V`
+    fn constant_text(&self, index: u16) -> String {
+        match self.class_file.constants.get_entry(index) {
+            Ok(entry) => self.constant_comment(entry).unwrap_or_else(|| constant_kind_and_value(entry).1),
+            Err(_) => format!("<invalid #{index}>"),
+        }
+    }
+
+    fn fmt_bootstrap_methods(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.class_file.bootstrap_methods.is_empty() {
+            return Ok(());
+        }
+        writeln!(f, "BootstrapMethods:")?;
+        for (index, bootstrap_method) in self.class_file.bootstrap_methods.iter().enumerate() {
+            let method_handle_index = bootstrap_method.method_handle_index;
+            writeln!(f, "  {index}: #{method_handle_index} {}", self.constant_text(method_handle_index))?;
+            writeln!(f, "    Method arguments:")?;
+            for argument_index in bootstrap_method.argument_indexes.iter() {
+                writeln!(f, "      #{argument_index} {}", self.constant_text(*argument_index))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// 例如 `"<init>":()V`
+    fn name_and_type_text(&self, index: u16) -> String {
+        match self.class_file.constants.name_and_type_of(index) {
+            Ok((name, type_descriptor)) => format!("{}:{}", quote_special_name(&name), type_descriptor),
+            Err(_) => format!("<invalid #{index}>"),
+        }
+    }
+
+    /// 字段或者方法的引用, 与 javap 一样, 当前类中的成员省略类名
+    fn member_text(&self, index: u16, with_class: bool) -> String {
+        match self.class_file.constants.member_reference_of(index) {
+            Ok((class_name, name, type_descriptor)) => {
+                let name = quote_special_name(&name);
+                if with_class || class_name != self.class_file.name {
+                    format!("{class_name}.{name}:{type_descriptor}")
+                } else {
+                    format!("{name}:{type_descriptor}")
+                }
+            }
+            Err(_) => format!("<invalid #{index}>"),
+        }
+    }
+
+    /// 指令操作数所引用的常量的注释, 例如 `Method java/io/PrintStream.println:(Ljava/lang/String;)V`
+    fn operand_comment(&self, index: u16) -> String {
+        let constants = &self.class_file.constants;
+        let Ok(entry) = constants.get_entry(index) else {
+            return format!("<invalid #{index}>");
+        };
+        let text = |index: u16| match constants.text_of(index) {
+            Ok(text) => escape(&text),
+            Err(_) => format!("<invalid #{index}>"),
+        };
+        match entry {
+            ConstantPoolEntry::Integer(value) => format!("int {value}"),
+            ConstantPoolEntry::Float(value) => format!("float {value:?}f"),
+            ConstantPoolEntry::Long(value) => format!("long {value}l"),
+            ConstantPoolEntry::Double(value) => format!("double {value:?}d"),
+            ConstantPoolEntry::ClassReference(i) => format!("class {}", text(*i)),
+            ConstantPoolEntry::StringReference(i) => format!("String {}", text(*i)),
+            ConstantPoolEntry::FieldReference(_, _) => format!("Field {}", self.member_text(index, false)),
+            ConstantPoolEntry::MethodReference(_, _) => format!("Method {}", self.member_text(index, false)),
+            ConstantPoolEntry::InterfaceMethodReference(_, _) => {
+                format!("InterfaceMethod {}", self.member_text(index, false))
+            }
+            ConstantPoolEntry::MethodHandle(kind, i) => format!("MethodHandle {} {}", kind, self.member_text(*i, true)),
+            ConstantPoolEntry::MethodType(i) => format!("MethodType {}", text(*i)),
+            ConstantPoolEntry::Dynamic(i, j) => format!("Dynamic #{}:{}", i, self.name_and_type_text(*j)),
+            ConstantPoolEntry::InvokeDynamic(i, j) => format!("InvokeDynamic #{}:{}", i, self.name_and_type_text(*j)),
+            entry => format!("{entry:?}"),
+        }
+    }
+
+    fn fmt_field(&self, f: &mut Formatter<'_>, field: &ClassFileField) -> fmt::Result {
+        let mut header = field.flags.modifiers();
+        let field_type = match &field.signature {
+            Some(signature) => signature.to_string(),
+            None => java_type(&field.type_descriptor),
+        };
+        header.push(&field_type);
+        header.push(&field.name);
+        writeln!(f, "  {};", header.join(" "))?;
+        if self.verbose {
+            writeln!(f, "    descriptor: {}", field.type_descriptor.descriptor())?;
+            writeln!(f, "    flags: {}", flag_names(field.flags.bits(), &field.flags))?;
+            if let Some(constant_value) = &field.constant_value {
+                let value = match constant_value {
+                    FieldConstantValue::Int(value) => format!("int {value}"),
+                    FieldConstantValue::Float(value) => format!("float {value:?}f"),
+                    FieldConstantValue::Long(value) => format!("long {value}l"),
+                    FieldConstantValue::Double(value) => format!("double {value:?}d"),
+                    FieldConstantValue::String(value) => format!("String {}", escape(value)),
+                };
+                writeln!(f, "    ConstantValue: {value}")?;
+            }
+        }
+        Ok(())
+    }
+
+    fn fmt_method(&self, f: &mut Formatter<'_>, method: &ClassFileMethod) -> fmt::Result {
+        let mut header = method.flags.modifiers().join(" ");
+        if !header.is_empty() {
+            header.push(' ');
+        }
+        let descriptor = &method.parsed_type_descriptor;
+        let mut parameters: Vec<String> = descriptor.parameters.iter().map(java_type).collect();
+        let mut return_type = match &descriptor.return_type {
+            Some(return_type) => java_type(return_type),
+            None => "void".to_string(),
+        };
+        let mut thrown_exceptions: Vec<String> = method.thrown_exceptions.iter().map(|name| java_name(name)).collect();
+        // 编译器生成的参数(例如内部类构造器的外部类实例)不在签名中, 这时仍然使用方法描述符
+        let signature = method
+            .signature
+            .as_ref()
+            .filter(|signature| signature.parameters.len() == descriptor.parameters.len());
+        if let Some(signature) = signature {
+            header.push_str(&type_parameters_text(&signature.type_parameters));
+            if !signature.type_parameters.is_empty() {
+                header.push(' ');
+            }
+            parameters = signature.parameters.iter().map(|parameter| parameter.to_string()).collect();
+            if let Some(signature_return_type) = &signature.return_type {
+                return_type = signature_return_type.to_string();
+            }
+            if !signature.thrown_exceptions.is_empty() {
+                thrown_exceptions = signature.thrown_exceptions.iter().map(|exception| exception.to_string()).collect();
+            }
+        }
+        match method.name.as_str() {
+            "<clinit>" => header.push_str("{}"),
+            "<init>" => header.push_str(&format!("{}({})", java_name(&self.class_file.name), parameters.join(", "))),
+            name => header.push_str(&format!("{} {}({})", return_type, name, parameters.join(", "))),
+        }
+        if !thrown_exceptions.is_empty() {
+            header.push_str(&format!(" throws {}", thrown_exceptions.join(", ")));
+        }
+        writeln!(f, "  {header};")?;
+
+        if self.verbose {
+            writeln!(f, "    descriptor: {}", method.type_descriptor)?;
+            writeln!(f, "    flags: {}", flag_names(method.flags.bits(), &method.flags))?;
+        }
+        if let Some(code) = &method.code {
+            self.fmt_code(f, method, code)?;
+        }
+        if self.verbose && !method.thrown_exceptions.is_empty() {
+            writeln!(f, "    Exceptions:")?;
+            let thrown_exceptions: Vec<String> = method.thrown_exceptions.iter().map(|name| java_name(name)).collect();
+            writeln!(f, "      throws {}", thrown_exceptions.join(", "))?;
+        }
+        Ok(())
+    }
+
+    /// 与 javap 一样, verbose 时 Code 中的内容多缩进两个空格
+    fn code_indent(&self) -> &'static str {
+        if self.verbose {
+            "  "
+        } else {
+            ""
+        }
+    }
+
+    fn fmt_code(&self, f: &mut Formatter<'_>, method: &ClassFileMethod, code: &ClassFileMethodCode) -> fmt::Result {
+        writeln!(f, "    Code:")?;
+        let indent = self.code_indent();
+        if self.verbose {
+            // 与 javap 一样, args_size 是参数的个数, 实例方法还包括 this
+            let args_size = method.parsed_type_descriptor.parameters.len()
+                + usize::from(!method.flags.contains(MethodFlags::STATIC));
+            writeln!(f, "      stack={}, locals={}, args_size={}", code.max_stack, code.max_locals, args_size)?;
+        }
+
+        match Instruction::parse_instructions(&code.code) {
+            Ok(instructions) => {
+                for (i, (address, instruction)) in instructions.iter().enumerate() {
+                    let next_address = instructions.get(i + 1).map_or(code.code.len(), |(next, _)| *next);
+                    let variable = Self::variable_name(code, *address, next_address, instruction);
+                    self.fmt_instruction(f, *address, instruction, variable)?;
+                }
+            }
+            Err(err) => writeln!(f, "{indent}    unparseable code: {err}")?,
+        }
+
+        let entries = code.exception_table.entries();
+        if !entries.is_empty() {
+            writeln!(f, "{indent}    Exception table:")?;
+            writeln!(f, "{indent}       from    to  target type")?;
+            for entry in entries {
+                let catch_type = match &entry.catch_class {
+                    Some(catch_class) => format!("Class {catch_class}"),
+                    None => "any".to_string(),
+                };
+                writeln!(
+                    f,
+                    "{indent}{:>12}{:>6}{:>6}   {}",
+                    entry.range.start.0, entry.range.end.0, entry.handler_pc.0, catch_type
+                )?;
+            }
+        }
+
+        if let Some(line_number_table) = &code.line_number_table {
+            writeln!(f, "{indent}    LineNumberTable:")?;
+            for entry in line_number_table.entries() {
+                writeln!(f, "{indent}      line {}: {}", entry.line_number, entry.program_counter)?;
+            }
+        }
+
+        if let Some(local_variable_table) = &code.local_variable_table {
+            writeln!(f, "{indent}    LocalVariableTable:")?;
+            writeln!(f, "{indent}      Start  Length  Slot  Name   Signature")?;
+            for variable in local_variable_table.entries() {
+                writeln!(
+                    f,
+                    "{indent}      {:>5} {:>7} {:>5} {:>5}   {}",
+                    variable.range.start.0,
+                    variable.range.end.0 - variable.range.start.0,
+                    variable.slot,
+                    variable.name,
+                    variable.type_descriptor.descriptor()
+                )?;
+            }
+            // 与 javap 一样, 只有 verbose 时才输出泛型局部变量的签名,
+            // 不过这里输出的是 Java 源码中的形式
+            let generic_variables: Vec<_> = local_variable_table
+                .entries()
+                .iter()
+                .filter_map(|variable| variable.signature.as_ref().map(|signature| (variable, signature)))
+                .collect();
+            if self.verbose && !generic_variables.is_empty() {
+                writeln!(f, "{indent}    LocalVariableTypeTable:")?;
+                writeln!(f, "{indent}      Start  Length  Slot  Name   Signature")?;
+                for (variable, signature) in generic_variables {
+                    writeln!(
+                        f,
+                        "{indent}      {:>5} {:>7} {:>5} {:>5}   {}",
+                        variable.range.start.0,
+                        variable.range.end.0 - variable.range.start.0,
+                        variable.slot,
+                        variable.name,
+                        signature
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// 读写局部变量的指令对应的局部变量名。
+    /// store 指令执行之后变量才有值, 所以用下一条指令的地址查询
+    fn variable_name<'c>(
+        code: &'c ClassFileMethodCode,
+        address: usize,
+        next_address: usize,
+        instruction: &Instruction,
+    ) -> Option<&'c str> {
+        let (slot, is_store) = local_variable_slot(instruction)?;
+        let pc = if is_store { next_address } else { address };
+        let pc = ProgramCounter(u16::try_from(pc).ok()?);
+        code.local_variable_table
+            .as_ref()?
+            .lookup(slot, pc)
+            .map(|variable| variable.name.as_str())
+    }
+
+    fn fmt_instruction(
+        &self,
+        f: &mut Formatter<'_>,
+        address: usize,
+        instruction: &Instruction,
+        variable: Option<&str>,
+    ) -> fmt::Result {
+        let indent = self.code_indent();
+        let mut mnemonic = instruction.mnemonic().to_string();
+        let (operands, comment) = match instruction {
+            Instruction::Getfield(index)
+            | Instruction::Getstatic(index)
+            | Instruction::Putfield(index)
+            | Instruction::Putstatic(index)
+            | Instruction::Invokevirtual(index)
+            | Instruction::Invokespecial(index)
+            | Instruction::Invokestatic(index)
+            | Instruction::New(index)
+            | Instruction::Anewarray(index)
+            | Instruction::Checkcast(index)
+            | Instruction::Instanceof(index)
+            | Instruction::Ldc_w(index)
+            | Instruction::Ldc2_w(index) => (format!("#{index}"), Some(self.operand_comment(*index))),
+            Instruction::Ldc(index) => (format!("#{index}"), Some(self.operand_comment(*index as u16))),
+            Instruction::Invokeinterface(index, count) => {
+                (format!("#{index},  {count}"), Some(self.operand_comment(*index)))
+            }
+            Instruction::Invokedynamic(index) => (format!("#{index},  0"), Some(self.operand_comment(*index))),
+            Instruction::Multianewarray(index, dimensions) => {
+                (format!("#{index},  {dimensions}"), Some(self.operand_comment(*index)))
+            }
+            Instruction::Aload(index)
+            | Instruction::Astore(index)
+            | Instruction::Dload(index)
+            | Instruction::Dstore(index)
+            | Instruction::Fload(index)
+            | Instruction::Fstore(index)
+            | Instruction::Iload(index)
+            | Instruction::Istore(index)
+            | Instruction::Lload(index)
+            | Instruction::Lstore(index)
+            | Instruction::Ret(index) => (index.to_string(), None),
+            Instruction::Bipush(value) => ((*value as i8).to_string(), None),
+            Instruction::Sipush(value) => (value.to_string(), None),
+            Instruction::Iinc(index, value) => (format!("{index}, {value}"), None),
+            Instruction::Goto(target)
+            | Instruction::Goto_w(target)
+            | Instruction::Jsr(target)
+            | Instruction::Jsr_w(target)
+            | Instruction::If_acmpeq(target)
+            | Instruction::If_acmpne(target)
+            | Instruction::If_icmpeq(target)
+            | Instruction::If_icmpne(target)
+            | Instruction::If_icmplt(target)
+            | Instruction::If_icmpge(target)
+            | Instruction::If_icmpgt(target)
+            | Instruction::If_icmple(target)
+            | Instruction::Ifeq(target)
+            | Instruction::Ifne(target)
+            | Instruction::Iflt(target)
+            | Instruction::Ifge(target)
+            | Instruction::Ifgt(target)
+            | Instruction::Ifle(target)
+            | Instruction::Ifnonnull(target)
+            | Instruction::Ifnull(target) => (target.to_string(), None),
+            Instruction::Newarray(array_type) => (new_array_type_name(array_type).to_string(), None),
+            Instruction::Wide(wide) => {
+                let operands = match wide {
+                    WideInstruction::Iinc(index, value) => format!("{index}, {value}"),
+                    WideInstruction::Iload(index)
+                    | WideInstruction::Lload(index)
+                    | WideInstruction::Fload(index)
+                    | WideInstruction::Dload(index)
+                    | WideInstruction::Aload(index)
+                    | WideInstruction::Istore(index)
+                    | WideInstruction::Lstore(index)
+                    | WideInstruction::Fstore(index)
+                    | WideInstruction::Dstore(index)
+                    | WideInstruction::Astore(index)
+                    | WideInstruction::Ret(index) => index.to_string(),
+                };
+                // javap 将 wide 修饰的指令输出为 `iinc_w` 等
+                mnemonic = format!("{}_w", wide.mnemonic());
+                (operands, None)
+            }
+            Instruction::Tableswitch(table_switch) => {
+                let (low, high) = (table_switch.low, table_switch.high);
+                writeln!(f, "{indent}{address:>8}: {mnemonic:<13} {{ // {low} to {high}")?;
+                for (value, target) in (table_switch.low..=table_switch.high).zip(table_switch.offsets.iter()) {
+                    writeln!(f, "{indent}{value:>22}: {target}")?;
+                }
+                writeln!(f, "{indent}{:>22}: {}", "default", table_switch.default)?;
+                return writeln!(f, "{indent}          }}");
+            }
+            Instruction::Lookupswitch(lookup_switch) => {
+                writeln!(f, "{indent}{address:>8}: {mnemonic:<13} {{ // {}", lookup_switch.match_offsets.len())?;
+                for (value, target) in lookup_switch.match_offsets.iter() {
+                    writeln!(f, "{indent}{value:>22}: {target}")?;
+                }
+                writeln!(f, "{indent}{:>22}: {}", "default", lookup_switch.default)?;
+                return writeln!(f, "{indent}          }}");
+            }
+            _ => (String::new(), None),
+        };
+        // 有调试信息时, 读写局部变量的指令在注释中输出变量名
+        let comment = comment.or_else(|| variable.map(str::to_string));
+        match comment {
+            Some(comment) => {
+                // javap 中 invokeinterface 的注释比其他指令少缩进两个空格
+                let width = if matches!(instruction, Instruction::Invokeinterface(_, _)) { 17 } else { 19 };
+                writeln!(f, "{indent}{address:>8}: {mnemonic:<13} {operands:<width$} // {comment}")
+            }
+            None if operands.is_empty() => writeln!(f, "{indent}{address:>8}: {mnemonic}"),
+            None => writeln!(f, "{indent}{address:>8}: {mnemonic:<13} {operands}"),
+        }
+    }
+}
+
+impl fmt::Display for Disassembler<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_class_header(f)?;
+        let mut first = true;
+        for field in self.class_file.fields.iter() {
+            if !first {
+                writeln!(f)?;
+            }
+            first = false;
+            self.fmt_field(f, field)?;
+        }
+        for method in self.class_file.methods.iter() {
+            if !first {
+                writeln!(f)?;
+            }
+            first = false;
+            self.fmt_method(f, method)?;
+        }
+        writeln!(f, "}}")?;
+        if self.verbose {
+            if let Some(source_file) = &self.class_file.source_file {
+                writeln!(f, "SourceFile: \"{source_file}\"")?;
+            }
+            self.fmt_bootstrap_methods(f)?;
+        }
+        Ok(())
+    }
+}
+
+/// 常量池中的常量在 javap 中的类型名称与值
+fn constant_kind_and_value(entry: &ConstantPoolEntry) -> (&'static str, String) {
+    match entry {
+        ConstantPoolEntry::Utf8(text) => ("Utf8", escape(text)),
+        ConstantPoolEntry::Integer(value) => ("Integer", value.to_string()),
+        ConstantPoolEntry::Float(value) => ("Float", format!("{value:?}f")),
+        ConstantPoolEntry::Long(value) => ("Long", format!("{value}l")),
+        ConstantPoolEntry::Double(value) => ("Double", format!("{value:?}d")),
+        ConstantPoolEntry::ClassReference(i) => ("Class", format!("#{i}")),
+        ConstantPoolEntry::StringReference(i) => ("String", format!("#{i}")),
+        ConstantPoolEntry::FieldReference(i, j) => ("Fieldref", format!("#{i}.#{j}")),
+        ConstantPoolEntry::MethodReference(i, j) => ("Methodref", format!("#{i}.#{j}")),
+        ConstantPoolEntry::InterfaceMethodReference(i, j) => ("InterfaceMethodref", format!("#{i}.#{j}")),
+        ConstantPoolEntry::NameAndTypeDescriptor(i, j) => ("NameAndType", format!("#{i}:#{j}")),
+        ConstantPoolEntry::MethodHandle(kind, i) => ("MethodHandle", format!("{}:#{i}", *kind as u8)),
+        ConstantPoolEntry::MethodType(i) => ("MethodType", format!("#{i}")),
+        ConstantPoolEntry::Dynamic(i, j) => ("Dynamic", format!("#{i}:#{j}")),
+        ConstantPoolEntry::InvokeDynamic(i, j) => ("InvokeDynamic", format!("#{i}:#{j}")),
+        ConstantPoolEntry::ModuleReference(i) => ("Module", format!("#{i}")),
+        ConstantPoolEntry::PackageReference(i) => ("Package", format!("#{i}")),
+    }
+}
+
+/// 以 `(0x0021) ACC_PUBLIC, ACC_SUPER` 的形式输出
+fn flag_names(bits: u16, flags: &impl fmt::Display) -> String {
+    format!("({bits:#06x}) {flags:#}").trim_end().to_string()
+}
+
+/// 二进制类名转换为 Java 源码中的类名, 例如 `java/lang/String` 转换为 `java.lang.String`
+fn java_name(name: &str) -> String {
+    name.replace('/', ".")
+}
+
+/// 例如 `int`、`java.lang.String[]`
+fn java_type(field_type: &Type) -> String {
+    match field_type {
+        Type::Base(base) => base.java_name().to_string(),
+        Type::Object(name) => java_name(name),
+        Type::Array(component_type) => format!("{}[]", java_type(component_type)),
+    }
+}
+
+/// 例如 `<K extends java.lang.Comparable<K>, V>`
+fn type_parameters_text(type_parameters: &[TypeParameter]) -> String {
+    if type_parameters.is_empty() {
+        String::new()
+    } else {
+        let type_parameters: Vec<String> = type_parameters.iter().map(|parameter| parameter.to_string()).collect();
+        format!("<{}>", type_parameters.join(", "))
+    }
+}
+
+/// 与 javap 一样转义字符串常量中的控制字符, 例如字符串拼接配方中的 `\u0001`
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\u{8}' => escaped.push_str("\\b"),
+            '\u{c}' => escaped.push_str("\\f"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// 与 javap 一样, 构造器等特殊方法名带上引号
+fn quote_special_name(name: &str) -> String {
+    if name.starts_with('<') {
+        format!("\"{name}\"")
+    } else {
+        name.to_string()
+    }
+}
+
+/// 读写局部变量的指令访问的 slot, 以及是否是 store 指令
+fn local_variable_slot(instruction: &Instruction) -> Option<(u16, bool)> {
+    let slot = match instruction {
+        Instruction::Aload_0
+        | Instruction::Dload_0
+        | Instruction::Fload_0
+        | Instruction::Iload_0
+        | Instruction::Lload_0 => (0, false),
+        Instruction::Aload_1
+        | Instruction::Dload_1
+        | Instruction::Fload_1
+        | Instruction::Iload_1
+        | Instruction::Lload_1 => (1, false),
+        Instruction::Aload_2
+        | Instruction::Dload_2
+        | Instruction::Fload_2
+        | Instruction::Iload_2
+        | Instruction::Lload_2 => (2, false),
+        Instruction::Aload_3
+        | Instruction::Dload_3
+        | Instruction::Fload_3
+        | Instruction::Iload_3
+        | Instruction::Lload_3 => (3, false),
+        Instruction::Astore_0
+        | Instruction::Dstore_0
+        | Instruction::Fstore_0
+        | Instruction::Istore_0
+        | Instruction::Lstore_0 => (0, true),
+        Instruction::Astore_1
+        | Instruction::Dstore_1
+        | Instruction::Fstore_1
+        | Instruction::Istore_1
+        | Instruction::Lstore_1 => (1, true),
+        Instruction::Astore_2
+        | Instruction::Dstore_2
+        | Instruction::Fstore_2
+        | Instruction::Istore_2
+        | Instruction::Lstore_2 => (2, true),
+        Instruction::Astore_3
+        | Instruction::Dstore_3
+        | Instruction::Fstore_3
+        | Instruction::Istore_3
+        | Instruction::Lstore_3 => (3, true),
+        Instruction::Aload(index)
+        | Instruction::Dload(index)
+        | Instruction::Fload(index)
+        | Instruction::Iload(index)
+        | Instruction::Lload(index)
+        | Instruction::Iinc(index, _) => (u16::from(*index), false),
+        Instruction::Astore(index)
+        | Instruction::Dstore(index)
+        | Instruction::Fstore(index)
+        | Instruction::Istore(index)
+        | Instruction::Lstore(index) => (u16::from(*index), true),
+        Instruction::Wide(wide) => match wide {
+            WideInstruction::Aload(index)
+            | WideInstruction::Dload(index)
+            | WideInstruction::Fload(index)
+            | WideInstruction::Iload(index)
+            | WideInstruction::Lload(index)
+            | WideInstruction::Iinc(index, _) => (*index, false),
+            WideInstruction::Astore(index)
+            | WideInstruction::Dstore(index)
+            | WideInstruction::Fstore(index)
+            | WideInstruction::Istore(index)
+            | WideInstruction::Lstore(index) => (*index, true),
+            WideInstruction::Ret(_) => return None,
+        },
+        _ => return None,
+    };
+    Some(slot)
+}
+
+fn new_array_type_name(array_type: &NewArrayType) -> &'static str {
+    match array_type {
+        NewArrayType::Boolean => "boolean",
+        NewArrayType::Char => "char",
+        NewArrayType::Float => "float",
+        NewArrayType::Double => "double",
+        NewArrayType::Byte => "byte",
+        NewArrayType::Short => "short",
+        NewArrayType::Int => "int",
+        NewArrayType::Long => "long",
+    }
+}
@@ -0,0 +1,147 @@
+use parser::class::ClassFile;
+
+use crate::disassembler::Disassembler;
+
+fn read_class(path: &str) -> ClassFile {
+    parser::read_buffer(&std::fs::read(path).unwrap()).unwrap()
+}
+
+#[test]
+fn can_disassemble_class() {
+    let class_file = read_class("tests/classes/rjvm/Exceptions.class");
+    let output = Disassembler::new(&class_file, false).to_string();
+    let expected = r#"Compiled from "Exceptions.java"
+public class rjvm.Exceptions {
+  public static final int LIMIT;
+
+  private volatile long counter;
+
+  public rjvm.Exceptions();
+    Code:
+       0: aload_0                           // this
+       1: invokespecial #1                  // Method java/lang/Object."<init>":()V
+       4: return
+    LineNumberTable:
+      line 5: 0
+    LocalVariableTable:
+      Start  Length  Slot  Name   Signature
+          0       5     0  this   Lrjvm/Exceptions;
+
+  public static int parse(java.lang.String) throws java.io.IOException;
+    Code:
+       0: aload_0                           // text
+       1: invokestatic  #7                  // Method java/lang/Integer.parseInt:(Ljava/lang/String;)I
+       4: istore_1
+       5: getstatic     #13                 // Field java/lang/System.out:Ljava/io/PrintStream;
+       8: ldc           #19                 // String done
+      10: invokevirtual #21                 // Method java/io/PrintStream.println:(Ljava/lang/String;)V
+      13: iload_1
+      14: ireturn
+      15: astore_1                          // e
+      16: new           #29                 // class java/io/IOException
+      19: dup
+      20: aload_1                           // e
+      21: invokespecial #31                 // Method java/io/IOException."<init>":(Ljava/lang/Throwable;)V
+      24: athrow
+      25: astore_2
+      26: getstatic     #13                 // Field java/lang/System.out:Ljava/io/PrintStream;
+      29: ldc           #19                 // String done
+      31: invokevirtual #21                 // Method java/io/PrintStream.println:(Ljava/lang/String;)V
+      34: aload_2
+      35: athrow
+    Exception table:
+       from    to  target type
+           0     5    15   Class java/lang/NumberFormatException
+           0     5    25   any
+          15    26    25   any
+    LineNumberTable:
+      line 11: 0
+      line 15: 5
+      line 11: 13
+      line 12: 15
+      line 13: 16
+      line 15: 25
+      line 16: 34
+    LocalVariableTable:
+      Start  Length  Slot  Name   Signature
+         16       9     1     e   Ljava/lang/NumberFormatException;
+          0      36     0  text   Ljava/lang/String;
+
+  public synchronized void increment();
+    Code:
+       0: aload_0                           // this
+       1: dup
+       2: getfield      #34                 // Field counter:J
+       5: lconst_1
+       6: ladd
+       7: putfield      #34                 // Field counter:J
+      10: return
+    LineNumberTable:
+      line 20: 0
+      line 21: 10
+    LocalVariableTable:
+      Start  Length  Slot  Name   Signature
+          0      11     0  this   Lrjvm/Exceptions;
+}
+"#;
+    assert_eq!(expected, output);
+}
+
+#[test]
+fn can_disassemble_class_verbosely() {
+    let class_file = read_class("tests/classes/rjvm/Exceptions.class");
+    let output = Disassembler::new(&class_file, true).to_string();
+    for expected in [
+        "  major version: 52\n",
+        "  flags: (0x0021) ACC_PUBLIC, ACC_SUPER\n",
+        "   #1 = Methodref          #2.#3          // java/lang/Object.\"<init>\":()V\n",
+        "   #3 = NameAndType        #5:#6          // \"<init>\":()V\n",
+        "  #43 = Integer            10\n",
+        "    flags: (0x0019) ACC_PUBLIC, ACC_STATIC, ACC_FINAL\n    ConstantValue: int 10\n",
+        "    flags: (0x0042) ACC_PRIVATE, ACC_VOLATILE\n",
+        "    flags: (0x0021) ACC_PUBLIC, ACC_SYNCHRONIZED\n",
+        "      stack=3, locals=3, args_size=1\n",
+        "         5: getstatic     #13                 // Field java/lang/System.out:Ljava/io/PrintStream;\n",
+        "             0     5    15   Class java/lang/NumberFormatException\n",
+        "    Exceptions:\n      throws java.io.IOException\n",
+        "SourceFile: \"Exceptions.java\"\n",
+    ] {
+        assert!(output.contains(expected), "{expected:?} not found in:\n{output}");
+    }
+}
+
+/// 常量池中第 index 个常量的 tag 所在的位置
+fn constant_offset(bytes: &[u8], index: u16) -> usize {
+    let mut offset = 10;
+    let mut current = 1;
+    while current < index {
+        let tag = bytes[offset];
+        offset += 1 + match tag {
+            1 => 2 + usize::from(u16::from_be_bytes([bytes[offset + 1], bytes[offset + 2]])),
+            7 | 8 | 16 | 19 | 20 => 2,
+            15 => 3,
+            5 | 6 => 8,
+            _ => 4,
+        };
+        current += if matches!(tag, 5 | 6) { 2 } else { 1 };
+    }
+    offset
+}
+
+#[test]
+fn can_disassemble_self_referencing_class_constant() {
+    // #2 是 java/lang/Object 的 CONSTANT_Class, 让它指向自己
+    let mut bytes = std::fs::read("tests/classes/rjvm/Exceptions.class").unwrap();
+    let offset = constant_offset(&bytes, 2);
+    assert_eq!(7, bytes[offset]);
+    bytes[offset + 1..offset + 3].copy_from_slice(&[0, 2]);
+
+    let class_file = parser::read_buffer_lenient(&bytes).class_file;
+    let output = Disassembler::new(&class_file, true).to_string();
+    for expected in [
+        "   #2 = Class              #2             // <invalid #2>\n",
+        "         1: invokespecial #1                  // Method <invalid #1>\n",
+    ] {
+        assert!(output.contains(expected), "{expected:?} not found in:\n{output}");
+    }
+}
@@ -0,0 +1,3 @@
+pub mod class_loader;
+pub mod disassembler;
+pub mod vm;
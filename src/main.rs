@@ -1,3 +1,97 @@
-fn main() {
-    println!("Hello, world!");
+use std::process::ExitCode;
+use std::rc::Rc;
+
+use log::error;
+use parser::log::{init_log, LogLevel};
+
+use little_jvm::class_loader::class_path::ClassPath;
+use little_jvm::class_loader::error::ClassLoaderError;
+use little_jvm::class_loader::ClassLoader;
+use little_jvm::disassembler::Disassembler;
+use little_jvm::vm::error::VmError;
+use little_jvm::vm::Vm;
+
+const USAGE: &str = "usage: little_jvm [-cp <classpath>] <class file | class name>
+       little_jvm javap [-cp <classpath>] [-v] <class file | class name>";
+
+/// 用法: little_jvm [-cp <classpath>] <class 文件路径 | 类名>
+/// 读取 class 文件, 或者在 classpath 中查找类, 并执行其中的 `public static void main(String[])`
+///
+/// 用法: little_jvm javap [-cp <classpath>] [-v] <class 文件路径 | 类名>
+/// 与 `javap -c -l` 类似, 输出类的反汇编结果, `-v` 时额外输出常量池等信息
+fn main() -> ExitCode {
+    init_log(LogLevel::INFO);
+
+    let mut args = std::env::args().skip(1).peekable();
+    let javap = args.next_if(|arg| arg == "javap").is_some();
+    let mut class_path = String::new();
+    let mut verbose = false;
+    let mut target = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "-cp" | "-classpath" | "--class-path" => match args.next() {
+                Some(value) => class_path = value,
+                None => break,
+            },
+            "-v" | "-verbose" if javap => verbose = true,
+            _ => {
+                target = Some(arg);
+                break;
+            }
+        }
+    }
+    let Some(target) = target else {
+        eprintln!("{USAGE}");
+        return ExitCode::FAILURE;
+    };
+
+    let result = if javap {
+        disassemble(&class_path, &target, verbose).map_err(VmError::from)
+    } else {
+        run(&class_path, &target)
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            error!("{}", err);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// 读取 class 文件并解析
+fn read_class_file(path: &str) -> Result<parser::class::ClassFile, ClassLoaderError> {
+    let bytes = std::fs::read(path).map_err(|err| ClassLoaderError::Io(path.to_string(), err.to_string()))?;
+    parser::read_buffer(&bytes).map_err(|source| {
+        // 输出出错位置附近的字节, 方便排查损坏的 class 文件
+        if let Some(excerpt) = source.hex_excerpt(&bytes) {
+            error!("{path}:\n{excerpt}");
+        }
+        ClassLoaderError::MalformedClass {
+            name: path.to_string(),
+            source,
+        }
+    })
+}
+
+/// 以 `.class` 结尾的参数被当作文件路径, 否则被当作类名, 例如 `rjvm.Main` 或者 `rjvm/Main`
+fn run(class_path: &str, target: &str) -> Result<(), VmError> {
+    let vm = Vm::new(ClassLoader::new(ClassPath::parse(class_path)?));
+    if target.ends_with(".class") {
+        let class = vm.register_class(read_class_file(target)?);
+        vm.run_main(&class.name)
+    } else {
+        vm.run_main(&target.replace('.', "/"))
+    }
+}
+
+/// 与 [run] 一样, 参数可以是 class 文件路径或者类名
+fn disassemble(class_path: &str, target: &str, verbose: bool) -> Result<(), ClassLoaderError> {
+    let class = if target.ends_with(".class") {
+        Rc::new(read_class_file(target)?)
+    } else {
+        ClassLoader::new(ClassPath::parse(class_path)?).load(&target.replace('.', "/"))?
+    };
+    print!("{}", Disassembler::new(&class, verbose));
+    Ok(())
 }
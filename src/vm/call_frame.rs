@@ -0,0 +1,670 @@
+use std::cmp::Ordering;
+use std::rc::Rc;
+
+use log::trace;
+use parser::class::ClassFile;
+use parser::constant_pool::constant_pool::ConstantPoolEntry;
+use parser::utils::instruction::{Instruction, WideInstruction};
+use parser::utils::pc::ProgramCounter;
+
+use crate::vm::error::{VmError, VmResult};
+use crate::vm::value::{Value, ValueKind};
+use crate::vm::value_stack::ValueStack;
+use crate::vm::{ResolvedMethod, Vm};
+
+/// 一次方法调用对应的栈帧, 持有局部变量表与操作数栈
+pub struct CallFrame {
+    class: Rc<ClassFile>,
+    method_index: usize,
+    pc: ProgramCounter,
+    locals: Vec<Value>,
+    stack: ValueStack,
+}
+
+/// 单条指令执行完成之后, 方法是继续执行、调用另一个方法还是返回
+enum InstructionCompleted {
+    ContinueMethodExecution,
+    InvokeMethod(ResolvedMethod, Vec<Value>),
+    ReturnFromMethod(Option<Value>),
+}
+
+/// 栈帧暂停执行的原因, 由 [Vm] 负责压入新的栈帧或者弹出当前栈帧
+pub enum FrameCompleted {
+    InvokeMethod(ResolvedMethod, Vec<Value>),
+    ReturnFromMethod(Option<Value>),
+}
+
+impl CallFrame {
+    /// 创建栈帧, 参数会按照 JVM 规范依次放入局部变量表, long 与 double 占据两个 slot
+    pub fn new(method: ResolvedMethod, args: Vec<Value>) -> VmResult<Self> {
+        let ResolvedMethod { class, method_index } = method;
+        let method = &class.methods[method_index];
+        let code = method
+            .code
+            .as_ref()
+            .ok_or_else(|| VmError::MethodHasNoCode(class.name.clone(), method.name.clone()))?;
+        let mut locals = vec![Value::Uninitialized; code.max_locals as usize];
+        let mut index = 0;
+        for arg in args {
+            let slots = arg.slots();
+            if index + slots > locals.len() {
+                return Err(VmError::LocalVariableOutOfRange(index + slots - 1));
+            }
+            locals[index] = arg;
+            index += slots;
+        }
+        let stack = ValueStack::with_max_size(code.max_stack as usize);
+
+        Ok(Self {
+            class,
+            method_index,
+            pc: ProgramCounter(0),
+            locals,
+            stack,
+        })
+    }
+
+    /// 执行字节码, 直到需要调用另一个方法或者当前方法返回
+    pub fn execute(&mut self, vm: &Vm) -> VmResult<FrameCompleted> {
+        let class = Rc::clone(&self.class);
+        let method = &class.methods[self.method_index];
+        let code = method
+            .code
+            .as_ref()
+            .ok_or_else(|| VmError::MethodHasNoCode(class.name.clone(), method.name.clone()))?;
+
+        loop {
+            let (instruction, next_address) = Instruction::parse(&code.code, self.pc.0 as usize)?;
+            trace!("{}.{} {:3} {:?}", class.name, method.name, self.pc, instruction);
+            self.pc = ProgramCounter(u16::try_from(next_address).map_err(|_| {
+                VmError::ValidationException(format!("pc {next_address} is out of the method code"))
+            })?);
+
+            match self.execute_instruction(vm, instruction)? {
+                InstructionCompleted::ContinueMethodExecution => {}
+                InstructionCompleted::InvokeMethod(method, args) => {
+                    return Ok(FrameCompleted::InvokeMethod(method, args))
+                }
+                InstructionCompleted::ReturnFromMethod(value) => {
+                    return Ok(FrameCompleted::ReturnFromMethod(value))
+                }
+            }
+        }
+    }
+
+    fn execute_instruction(&mut self, vm: &Vm, instruction: Instruction) -> VmResult<InstructionCompleted> {
+        match instruction {
+            Instruction::Nop => {}
+
+            // 常量
+            Instruction::Aconst_null => self.push(Value::Null)?,
+            Instruction::Iconst_m1 => self.push(Value::Int(-1))?,
+            Instruction::Iconst_0 => self.push(Value::Int(0))?,
+            Instruction::Iconst_1 => self.push(Value::Int(1))?,
+            Instruction::Iconst_2 => self.push(Value::Int(2))?,
+            Instruction::Iconst_3 => self.push(Value::Int(3))?,
+            Instruction::Iconst_4 => self.push(Value::Int(4))?,
+            Instruction::Iconst_5 => self.push(Value::Int(5))?,
+            Instruction::Lconst_0 => self.push(Value::Long(0))?,
+            Instruction::Lconst_1 => self.push(Value::Long(1))?,
+            Instruction::Fconst_0 => self.push(Value::Float(0.0))?,
+            Instruction::Fconst_1 => self.push(Value::Float(1.0))?,
+            Instruction::Fconst_2 => self.push(Value::Float(2.0))?,
+            Instruction::Dconst_0 => self.push(Value::Double(0.0))?,
+            Instruction::Dconst_1 => self.push(Value::Double(1.0))?,
+            Instruction::Bipush(value) => self.push(Value::Int(value as i8 as i32))?,
+            Instruction::Sipush(value) => self.push(Value::Int(value as i32))?,
+            Instruction::Ldc(index) => self.load_constant(index as u16)?,
+            Instruction::Ldc_w(index) | Instruction::Ldc2_w(index) => self.load_constant(index)?,
+
+            // 局部变量
+            Instruction::Iload(index) => self.load_local(index as usize, ValueKind::Int)?,
+            Instruction::Iload_0 => self.load_local(0, ValueKind::Int)?,
+            Instruction::Iload_1 => self.load_local(1, ValueKind::Int)?,
+            Instruction::Iload_2 => self.load_local(2, ValueKind::Int)?,
+            Instruction::Iload_3 => self.load_local(3, ValueKind::Int)?,
+            Instruction::Lload(index) => self.load_local(index as usize, ValueKind::Long)?,
+            Instruction::Lload_0 => self.load_local(0, ValueKind::Long)?,
+            Instruction::Lload_1 => self.load_local(1, ValueKind::Long)?,
+            Instruction::Lload_2 => self.load_local(2, ValueKind::Long)?,
+            Instruction::Lload_3 => self.load_local(3, ValueKind::Long)?,
+            Instruction::Fload(index) => self.load_local(index as usize, ValueKind::Float)?,
+            Instruction::Fload_0 => self.load_local(0, ValueKind::Float)?,
+            Instruction::Fload_1 => self.load_local(1, ValueKind::Float)?,
+            Instruction::Fload_2 => self.load_local(2, ValueKind::Float)?,
+            Instruction::Fload_3 => self.load_local(3, ValueKind::Float)?,
+            Instruction::Dload(index) => self.load_local(index as usize, ValueKind::Double)?,
+            Instruction::Dload_0 => self.load_local(0, ValueKind::Double)?,
+            Instruction::Dload_1 => self.load_local(1, ValueKind::Double)?,
+            Instruction::Dload_2 => self.load_local(2, ValueKind::Double)?,
+            Instruction::Dload_3 => self.load_local(3, ValueKind::Double)?,
+            Instruction::Aload(index) => self.load_local(index as usize, ValueKind::Reference)?,
+            Instruction::Aload_0 => self.load_local(0, ValueKind::Reference)?,
+            Instruction::Aload_1 => self.load_local(1, ValueKind::Reference)?,
+            Instruction::Aload_2 => self.load_local(2, ValueKind::Reference)?,
+            Instruction::Aload_3 => self.load_local(3, ValueKind::Reference)?,
+            Instruction::Istore(index) => self.store_local(index as usize, ValueKind::Int)?,
+            Instruction::Istore_0 => self.store_local(0, ValueKind::Int)?,
+            Instruction::Istore_1 => self.store_local(1, ValueKind::Int)?,
+            Instruction::Istore_2 => self.store_local(2, ValueKind::Int)?,
+            Instruction::Istore_3 => self.store_local(3, ValueKind::Int)?,
+            Instruction::Lstore(index) => self.store_local(index as usize, ValueKind::Long)?,
+            Instruction::Lstore_0 => self.store_local(0, ValueKind::Long)?,
+            Instruction::Lstore_1 => self.store_local(1, ValueKind::Long)?,
+            Instruction::Lstore_2 => self.store_local(2, ValueKind::Long)?,
+            Instruction::Lstore_3 => self.store_local(3, ValueKind::Long)?,
+            Instruction::Fstore(index) => self.store_local(index as usize, ValueKind::Float)?,
+            Instruction::Fstore_0 => self.store_local(0, ValueKind::Float)?,
+            Instruction::Fstore_1 => self.store_local(1, ValueKind::Float)?,
+            Instruction::Fstore_2 => self.store_local(2, ValueKind::Float)?,
+            Instruction::Fstore_3 => self.store_local(3, ValueKind::Float)?,
+            Instruction::Dstore(index) => self.store_local(index as usize, ValueKind::Double)?,
+            Instruction::Dstore_0 => self.store_local(0, ValueKind::Double)?,
+            Instruction::Dstore_1 => self.store_local(1, ValueKind::Double)?,
+            Instruction::Dstore_2 => self.store_local(2, ValueKind::Double)?,
+            Instruction::Dstore_3 => self.store_local(3, ValueKind::Double)?,
+            Instruction::Astore(index) => self.store_local(index as usize, ValueKind::Reference)?,
+            Instruction::Astore_0 => self.store_local(0, ValueKind::Reference)?,
+            Instruction::Astore_1 => self.store_local(1, ValueKind::Reference)?,
+            Instruction::Astore_2 => self.store_local(2, ValueKind::Reference)?,
+            Instruction::Astore_3 => self.store_local(3, ValueKind::Reference)?,
+            Instruction::Iinc(index, constant) => self.increment_local(index as usize, constant as i32)?,
+            Instruction::Wide(wide) => match wide {
+                WideInstruction::Iload(index) => self.load_local(index as usize, ValueKind::Int)?,
+                WideInstruction::Lload(index) => self.load_local(index as usize, ValueKind::Long)?,
+                WideInstruction::Fload(index) => self.load_local(index as usize, ValueKind::Float)?,
+                WideInstruction::Dload(index) => self.load_local(index as usize, ValueKind::Double)?,
+                WideInstruction::Aload(index) => self.load_local(index as usize, ValueKind::Reference)?,
+                WideInstruction::Istore(index) => self.store_local(index as usize, ValueKind::Int)?,
+                WideInstruction::Lstore(index) => self.store_local(index as usize, ValueKind::Long)?,
+                WideInstruction::Fstore(index) => self.store_local(index as usize, ValueKind::Float)?,
+                WideInstruction::Dstore(index) => self.store_local(index as usize, ValueKind::Double)?,
+                WideInstruction::Astore(index) => self.store_local(index as usize, ValueKind::Reference)?,
+                WideInstruction::Iinc(index, constant) => self.increment_local(index as usize, constant as i32)?,
+                WideInstruction::Ret(_) => return Err(VmError::NotImplemented(format!("{instruction:?}"))),
+            },
+
+            // 操作数栈
+            Instruction::Pop => {
+                self.stack.pop_slots(1)?;
+            }
+            Instruction::Pop2 => {
+                self.stack.pop_slots(2)?;
+            }
+            Instruction::Dup => self.duplicate(1, 0)?,
+            Instruction::Dup_x1 => self.duplicate(1, 1)?,
+            Instruction::Dup_x2 => self.duplicate(1, 2)?,
+            Instruction::Dup2 => self.duplicate(2, 0)?,
+            Instruction::Dup2_x1 => self.duplicate(2, 1)?,
+            Instruction::Dup2_x2 => self.duplicate(2, 2)?,
+            Instruction::Swap => {
+                let values = self.stack.pop_slots(2)?;
+                if values.len() != 2 {
+                    return Err(VmError::ValidationException("swap needs two category 1 values".to_string()));
+                }
+                self.stack.push_all(&[values[1], values[0]])?;
+            }
+
+            // int 运算, 溢出时按照 Java 的语义回绕
+            Instruction::Iadd => self.binary_int(|a, b| Ok(a.wrapping_add(b)))?,
+            Instruction::Isub => self.binary_int(|a, b| Ok(a.wrapping_sub(b)))?,
+            Instruction::Imul => self.binary_int(|a, b| Ok(a.wrapping_mul(b)))?,
+            Instruction::Idiv => self.binary_int(|a, b| {
+                if b == 0 {
+                    Err(VmError::ArithmeticException("/ by zero".to_string()))
+                } else {
+                    Ok(a.wrapping_div(b))
+                }
+            })?,
+            Instruction::Irem => self.binary_int(|a, b| {
+                if b == 0 {
+                    Err(VmError::ArithmeticException("/ by zero".to_string()))
+                } else {
+                    Ok(a.wrapping_rem(b))
+                }
+            })?,
+            Instruction::Ineg => {
+                let value = self.pop_int()?;
+                self.push(Value::Int(value.wrapping_neg()))?
+            }
+            Instruction::Ishl => self.binary_int(|a, b| Ok(a.wrapping_shl(b as u32 & 0x1f)))?,
+            Instruction::Ishr => self.binary_int(|a, b| Ok(a.wrapping_shr(b as u32 & 0x1f)))?,
+            Instruction::Iushr => self.binary_int(|a, b| Ok(((a as u32) >> (b as u32 & 0x1f)) as i32))?,
+            Instruction::Iand => self.binary_int(|a, b| Ok(a & b))?,
+            Instruction::Ior => self.binary_int(|a, b| Ok(a | b))?,
+            Instruction::Ixor => self.binary_int(|a, b| Ok(a ^ b))?,
+
+            // long 运算, 注意移位的位数是一个 int
+            Instruction::Ladd => self.binary_long(|a, b| Ok(a.wrapping_add(b)))?,
+            Instruction::Lsub => self.binary_long(|a, b| Ok(a.wrapping_sub(b)))?,
+            Instruction::Lmul => self.binary_long(|a, b| Ok(a.wrapping_mul(b)))?,
+            Instruction::Ldiv => self.binary_long(|a, b| {
+                if b == 0 {
+                    Err(VmError::ArithmeticException("/ by zero".to_string()))
+                } else {
+                    Ok(a.wrapping_div(b))
+                }
+            })?,
+            Instruction::Lrem => self.binary_long(|a, b| {
+                if b == 0 {
+                    Err(VmError::ArithmeticException("/ by zero".to_string()))
+                } else {
+                    Ok(a.wrapping_rem(b))
+                }
+            })?,
+            Instruction::Lneg => {
+                let value = self.pop_long()?;
+                self.push(Value::Long(value.wrapping_neg()))?
+            }
+            Instruction::Lshl => self.shift_long(|a, b| a.wrapping_shl(b))?,
+            Instruction::Lshr => self.shift_long(|a, b| a.wrapping_shr(b))?,
+            Instruction::Lushr => self.shift_long(|a, b| ((a as u64) >> b) as i64)?,
+            Instruction::Land => self.binary_long(|a, b| Ok(a & b))?,
+            Instruction::Lor => self.binary_long(|a, b| Ok(a | b))?,
+            Instruction::Lxor => self.binary_long(|a, b| Ok(a ^ b))?,
+
+            // float 与 double 运算, Rust 的 % 与 Java 的 fmod 语义一致
+            Instruction::Fadd => self.binary_float(|a, b| a + b)?,
+            Instruction::Fsub => self.binary_float(|a, b| a - b)?,
+            Instruction::Fmul => self.binary_float(|a, b| a * b)?,
+            Instruction::Fdiv => self.binary_float(|a, b| a / b)?,
+            Instruction::Frem => self.binary_float(|a, b| a % b)?,
+            Instruction::Fneg => {
+                let value = self.pop_float()?;
+                self.push(Value::Float(-value))?
+            }
+            Instruction::Dadd => self.binary_double(|a, b| a + b)?,
+            Instruction::Dsub => self.binary_double(|a, b| a - b)?,
+            Instruction::Dmul => self.binary_double(|a, b| a * b)?,
+            Instruction::Ddiv => self.binary_double(|a, b| a / b)?,
+            Instruction::Drem => self.binary_double(|a, b| a % b)?,
+            Instruction::Dneg => {
+                let value = self.pop_double()?;
+                self.push(Value::Double(-value))?
+            }
+
+            // 类型转换, Rust 的 as 对 NaN 与溢出的处理与 Java 一致
+            Instruction::I2l => {
+                let value = self.pop_int()?;
+                self.push(Value::Long(value as i64))?
+            }
+            Instruction::I2f => {
+                let value = self.pop_int()?;
+                self.push(Value::Float(value as f32))?
+            }
+            Instruction::I2d => {
+                let value = self.pop_int()?;
+                self.push(Value::Double(value as f64))?
+            }
+            Instruction::I2b => {
+                let value = self.pop_int()?;
+                self.push(Value::Int(value as i8 as i32))?
+            }
+            Instruction::I2c => {
+                let value = self.pop_int()?;
+                self.push(Value::Int(value as u16 as i32))?
+            }
+            Instruction::I2s => {
+                let value = self.pop_int()?;
+                self.push(Value::Int(value as i16 as i32))?
+            }
+            Instruction::L2i => {
+                let value = self.pop_long()?;
+                self.push(Value::Int(value as i32))?
+            }
+            Instruction::L2f => {
+                let value = self.pop_long()?;
+                self.push(Value::Float(value as f32))?
+            }
+            Instruction::L2d => {
+                let value = self.pop_long()?;
+                self.push(Value::Double(value as f64))?
+            }
+            Instruction::F2i => {
+                let value = self.pop_float()?;
+                self.push(Value::Int(value as i32))?
+            }
+            Instruction::F2l => {
+                let value = self.pop_float()?;
+                self.push(Value::Long(value as i64))?
+            }
+            Instruction::F2d => {
+                let value = self.pop_float()?;
+                self.push(Value::Double(value as f64))?
+            }
+            Instruction::D2i => {
+                let value = self.pop_double()?;
+                self.push(Value::Int(value as i32))?
+            }
+            Instruction::D2l => {
+                let value = self.pop_double()?;
+                self.push(Value::Long(value as i64))?
+            }
+            Instruction::D2f => {
+                let value = self.pop_double()?;
+                self.push(Value::Float(value as f32))?
+            }
+
+            // 比较
+            Instruction::Lcmp => {
+                let b = self.pop_long()?;
+                let a = self.pop_long()?;
+                self.push(Value::Int(Self::ordering_to_int(a.cmp(&b))))?
+            }
+            Instruction::Fcmpl | Instruction::Fcmpg => {
+                let b = self.pop_float()?;
+                let a = self.pop_float()?;
+                let nan_result = if instruction == Instruction::Fcmpg { 1 } else { -1 };
+                let result = a.partial_cmp(&b).map_or(nan_result, Self::ordering_to_int);
+                self.push(Value::Int(result))?
+            }
+            Instruction::Dcmpl | Instruction::Dcmpg => {
+                let b = self.pop_double()?;
+                let a = self.pop_double()?;
+                let nan_result = if instruction == Instruction::Dcmpg { 1 } else { -1 };
+                let result = a.partial_cmp(&b).map_or(nan_result, Self::ordering_to_int);
+                self.push(Value::Int(result))?
+            }
+
+            // 控制流, 跳转地址在解析指令时已经计算为绝对地址
+            Instruction::Ifeq(address) => self.branch_if_int(address, |v| v == 0)?,
+            Instruction::Ifne(address) => self.branch_if_int(address, |v| v != 0)?,
+            Instruction::Iflt(address) => self.branch_if_int(address, |v| v < 0)?,
+            Instruction::Ifge(address) => self.branch_if_int(address, |v| v >= 0)?,
+            Instruction::Ifgt(address) => self.branch_if_int(address, |v| v > 0)?,
+            Instruction::Ifle(address) => self.branch_if_int(address, |v| v <= 0)?,
+            Instruction::If_icmpeq(address) => self.branch_if_int_cmp(address, |a, b| a == b)?,
+            Instruction::If_icmpne(address) => self.branch_if_int_cmp(address, |a, b| a != b)?,
+            Instruction::If_icmplt(address) => self.branch_if_int_cmp(address, |a, b| a < b)?,
+            Instruction::If_icmpge(address) => self.branch_if_int_cmp(address, |a, b| a >= b)?,
+            Instruction::If_icmpgt(address) => self.branch_if_int_cmp(address, |a, b| a > b)?,
+            Instruction::If_icmple(address) => self.branch_if_int_cmp(address, |a, b| a <= b)?,
+            Instruction::Ifnull(address) => {
+                if self.pop()? == Value::Null {
+                    self.goto(address);
+                }
+            }
+            Instruction::Ifnonnull(address) => {
+                if self.pop()? != Value::Null {
+                    self.goto(address);
+                }
+            }
+            Instruction::Goto(address) | Instruction::Goto_w(address) => self.goto(address),
+            Instruction::Tableswitch(ref table) => {
+                let key = self.pop_int()?;
+                let address = if key < table.low || key > table.high {
+                    table.default
+                } else {
+                    table.offsets[(key as i64 - table.low as i64) as usize]
+                };
+                self.goto(address);
+            }
+            Instruction::Lookupswitch(ref lookup) => {
+                let key = self.pop_int()?;
+                let address = lookup
+                    .match_offsets
+                    .iter()
+                    .find(|(value, _)| *value == key)
+                    .map_or(lookup.default, |(_, address)| *address);
+                self.goto(address);
+            }
+
+            // 方法调用与返回
+            Instruction::Invokestatic(index) => {
+                let (class_name, method_name, descriptor) = self.resolve_method_reference(index)?;
+                let method = vm.resolve_static_method(&class_name, &method_name, &descriptor)?;
+                let num_arguments = method.num_arguments()?;
+                let mut args = Vec::with_capacity(num_arguments);
+                for _ in 0..num_arguments {
+                    args.push(self.pop()?);
+                }
+                args.reverse();
+                return Ok(InstructionCompleted::InvokeMethod(method, args));
+            }
+            // 返回值的类型必须与指令一致
+            Instruction::Ireturn => {
+                let value = Value::Int(self.pop_int()?);
+                return Ok(InstructionCompleted::ReturnFromMethod(Some(value)));
+            }
+            Instruction::Lreturn => {
+                let value = Value::Long(self.pop_long()?);
+                return Ok(InstructionCompleted::ReturnFromMethod(Some(value)));
+            }
+            Instruction::Freturn => {
+                let value = Value::Float(self.pop_float()?);
+                return Ok(InstructionCompleted::ReturnFromMethod(Some(value)));
+            }
+            Instruction::Dreturn => {
+                let value = Value::Double(self.pop_double()?);
+                return Ok(InstructionCompleted::ReturnFromMethod(Some(value)));
+            }
+            Instruction::Areturn => {
+                let value = self.pop_reference()?;
+                return Ok(InstructionCompleted::ReturnFromMethod(Some(value)));
+            }
+            Instruction::Return => return Ok(InstructionCompleted::ReturnFromMethod(None)),
+
+            _ => return Err(VmError::NotImplemented(format!("{instruction:?}"))),
+        }
+        Ok(InstructionCompleted::ContinueMethodExecution)
+    }
+
+    pub fn push(&mut self, value: Value) -> VmResult<()> {
+        self.stack.push(value)
+    }
+
+    fn pop(&mut self) -> VmResult<Value> {
+        self.stack.pop()
+    }
+
+    fn pop_int(&mut self) -> VmResult<i32> {
+        match self.pop()? {
+            Value::Int(value) => Ok(value),
+            value => Err(VmError::ValidationException(format!("expected int but found {value}"))),
+        }
+    }
+
+    fn pop_long(&mut self) -> VmResult<i64> {
+        match self.pop()? {
+            Value::Long(value) => Ok(value),
+            value => Err(VmError::ValidationException(format!("expected long but found {value}"))),
+        }
+    }
+
+    fn pop_float(&mut self) -> VmResult<f32> {
+        match self.pop()? {
+            Value::Float(value) => Ok(value),
+            value => Err(VmError::ValidationException(format!("expected float but found {value}"))),
+        }
+    }
+
+    fn pop_double(&mut self) -> VmResult<f64> {
+        match self.pop()? {
+            Value::Double(value) => Ok(value),
+            value => Err(VmError::ValidationException(format!("expected double but found {value}"))),
+        }
+    }
+
+    /// 目前唯一的引用是 null
+    fn pop_reference(&mut self) -> VmResult<Value> {
+        match self.pop()? {
+            Value::Null => Ok(Value::Null),
+            value => Err(VmError::ValidationException(format!("expected reference but found {value}"))),
+        }
+    }
+
+    fn binary_int<F>(&mut self, op: F) -> VmResult<()>
+    where
+        F: FnOnce(i32, i32) -> VmResult<i32>,
+    {
+        let b = self.pop_int()?;
+        let a = self.pop_int()?;
+        self.push(Value::Int(op(a, b)?))
+    }
+
+    fn binary_long<F>(&mut self, op: F) -> VmResult<()>
+    where
+        F: FnOnce(i64, i64) -> VmResult<i64>,
+    {
+        let b = self.pop_long()?;
+        let a = self.pop_long()?;
+        self.push(Value::Long(op(a, b)?))
+    }
+
+    fn shift_long<F>(&mut self, op: F) -> VmResult<()>
+    where
+        F: FnOnce(i64, u32) -> i64,
+    {
+        let shift = self.pop_int()?;
+        let value = self.pop_long()?;
+        self.push(Value::Long(op(value, shift as u32 & 0x3f)))
+    }
+
+    fn binary_float<F>(&mut self, op: F) -> VmResult<()>
+    where
+        F: FnOnce(f32, f32) -> f32,
+    {
+        let b = self.pop_float()?;
+        let a = self.pop_float()?;
+        self.push(Value::Float(op(a, b)))
+    }
+
+    fn binary_double<F>(&mut self, op: F) -> VmResult<()>
+    where
+        F: FnOnce(f64, f64) -> f64,
+    {
+        let b = self.pop_double()?;
+        let a = self.pop_double()?;
+        self.push(Value::Double(op(a, b)))
+    }
+
+    fn ordering_to_int(ordering: Ordering) -> i32 {
+        match ordering {
+            Ordering::Less => -1,
+            Ordering::Equal => 0,
+            Ordering::Greater => 1,
+        }
+    }
+
+    /// 复制栈顶 `top_slots` 个 slot 的值, 并插入到其下方 `skip_slots` 个 slot 之下。
+    /// dup, dup_x1, dup_x2, dup2, dup2_x1, dup2_x2 的各种形式都可以用这种方式统一描述
+    fn duplicate(&mut self, top_slots: usize, skip_slots: usize) -> VmResult<()> {
+        let top = self.stack.pop_slots(top_slots)?;
+        let skipped = self.stack.pop_slots(skip_slots)?;
+        self.stack.push_all(&top)?;
+        self.stack.push_all(&skipped)?;
+        self.stack.push_all(&top)
+    }
+
+    fn load_local(&mut self, index: usize, kind: ValueKind) -> VmResult<()> {
+        match self.locals.get(index) {
+            Some(Value::Uninitialized) => Err(VmError::ValidationException(format!(
+                "local variable {index} is read before being initialized"
+            ))),
+            Some(value) if value.kind() == Some(kind) => {
+                let value = *value;
+                self.push(value)
+            }
+            Some(value) => Err(VmError::ValidationException(format!(
+                "expected {kind} in local variable {index} but found {value}"
+            ))),
+            None => Err(VmError::LocalVariableOutOfRange(index)),
+        }
+    }
+
+    fn store_local(&mut self, index: usize, kind: ValueKind) -> VmResult<()> {
+        let value = self.pop()?;
+        if value.kind() != Some(kind) {
+            return Err(VmError::ValidationException(format!("expected {kind} but found {value}")));
+        }
+        if index + value.slots() > self.locals.len() {
+            return Err(VmError::LocalVariableOutOfRange(index + value.slots() - 1));
+        }
+        // 覆盖了从 index - 1 开始的 long 或者 double 的第二个 slot, 这个值不再有效
+        if index > 0 && self.locals[index - 1].slots() == 2 {
+            self.locals[index - 1] = Value::Uninitialized;
+        }
+        self.locals[index] = value;
+        if value.slots() == 2 {
+            self.locals[index + 1] = Value::Uninitialized;
+        }
+        Ok(())
+    }
+
+    fn increment_local(&mut self, index: usize, constant: i32) -> VmResult<()> {
+        match self.locals.get_mut(index) {
+            Some(Value::Int(value)) => {
+                *value = value.wrapping_add(constant);
+                Ok(())
+            }
+            Some(value) => Err(VmError::ValidationException(format!(
+                "iinc expects an int local variable but found {value}"
+            ))),
+            None => Err(VmError::LocalVariableOutOfRange(index)),
+        }
+    }
+
+    fn goto(&mut self, address: u16) {
+        self.pc = ProgramCounter(address);
+    }
+
+    fn branch_if_int<F>(&mut self, address: u16, condition: F) -> VmResult<()>
+    where
+        F: FnOnce(i32) -> bool,
+    {
+        if condition(self.pop_int()?) {
+            self.goto(address);
+        }
+        Ok(())
+    }
+
+    fn branch_if_int_cmp<F>(&mut self, address: u16, condition: F) -> VmResult<()>
+    where
+        F: FnOnce(i32, i32) -> bool,
+    {
+        let b = self.pop_int()?;
+        let a = self.pop_int()?;
+        if condition(a, b) {
+            self.goto(address);
+        }
+        Ok(())
+    }
+
+    fn load_constant(&mut self, index: u16) -> VmResult<()> {
+        let value = match self.class.constants.get_entry(index)? {
+            ConstantPoolEntry::Integer(value) => Value::Int(*value),
+            ConstantPoolEntry::Float(value) => Value::Float(*value),
+            ConstantPoolEntry::Long(value) => Value::Long(*value),
+            ConstantPoolEntry::Double(value) => Value::Double(*value),
+            entry => return Err(VmError::NotImplemented(format!("ldc of {entry:?}"))),
+        };
+        self.push(value)
+    }
+
+    /// 解析常量池中的方法引用, 返回 (类名, 方法名, 描述符)
+    fn resolve_method_reference(&self, index: u16) -> VmResult<(String, String, String)> {
+        let constants = &self.class.constants;
+        let (class_index, name_and_type_index) = match constants.get_entry(index)? {
+            ConstantPoolEntry::MethodReference(i, j)
+            | ConstantPoolEntry::InterfaceMethodReference(i, j) => (*i, *j),
+            entry => {
+                return Err(VmError::ValidationException(format!(
+                    "expected a method reference at constant {index} but found {entry:?}"
+                )))
+            }
+        };
+        let (name_index, descriptor_index) = match constants.get_entry(name_and_type_index)? {
+            ConstantPoolEntry::NameAndTypeDescriptor(i, j) => (*i, *j),
+            entry => {
+                return Err(VmError::ValidationException(format!(
+                    "expected a name and type at constant {name_and_type_index} but found {entry:?}"
+                )))
+            }
+        };
+        Ok((
+            constants.text_of(class_index)?,
+            constants.text_of(name_index)?,
+            constants.text_of(descriptor_index)?,
+        ))
+    }
+}
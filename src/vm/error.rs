@@ -0,0 +1,34 @@
+use thiserror::Error;
+use parser::error::ClassFileParserError;
+
+use crate::class_loader::error::ClassLoaderError;
+
+/// 虚拟机执行 Result
+pub type VmResult<T> = std::result::Result<T, VmError>;
+
+/// 虚拟机执行 error
+#[derive(Error, Debug, PartialEq)]
+pub enum VmError {
+    #[error("error while reading class data: {0}")]
+    ClassFileParserError(#[from] ClassFileParserError),
+    #[error("{0}")]
+    ClassLoaderError(#[from] ClassLoaderError),
+    #[error("method {0}.{1}{2} not found")]
+    MethodNotFound(String, String, String),
+    #[error("method {0}.{1} has no code")]
+    MethodHasNoCode(String, String),
+    #[error("the instruction {0} is not implemented yet")]
+    NotImplemented(String),
+    #[error("validation error: {0}")]
+    ValidationException(String),
+    #[error("operand stack overflow, the max size is {0}")]
+    OperandStackOverflow(usize),
+    #[error("operand stack underflow")]
+    OperandStackUnderflow,
+    #[error("local variable index {0} is out of range")]
+    LocalVariableOutOfRange(usize),
+    #[error("java.lang.StackOverflowError")]
+    StackOverflowError,
+    #[error("java.lang.ArithmeticException: {0}")]
+    ArithmeticException(String),
+}
@@ -0,0 +1,137 @@
+pub mod call_frame;
+pub mod error;
+pub mod value;
+pub mod value_stack;
+#[cfg(test)]
+mod test;
+
+use std::rc::Rc;
+
+use log::debug;
+use parser::class::ClassFile;
+use parser::flags::MethodFlags;
+use parser::method::descriptor::MethodDescriptor;
+
+use crate::class_loader::ClassLoader;
+use crate::vm::call_frame::{CallFrame, FrameCompleted};
+use crate::vm::error::{VmError, VmResult};
+use crate::vm::value::Value;
+
+/// 最大的调用深度, 超过之后抛出 StackOverflowError
+const MAX_CALL_DEPTH: usize = 2048;
+
+/// 字节码解释器, 目前只支持基本类型的运算、控制流以及静态方法调用
+#[derive(Default)]
+pub struct Vm {
+    class_loader: ClassLoader,
+}
+
+/// 已经解析完成的方法, 由所属的类以及方法在类中的下标组成
+pub struct ResolvedMethod {
+    class: Rc<ClassFile>,
+    method_index: usize,
+}
+
+impl ResolvedMethod {
+    /// 方法参数的个数, 注意 long 与 double 也只算一个
+    pub fn num_arguments(&self) -> VmResult<usize> {
+        let method = &self.class.methods[self.method_index];
+        Ok(MethodDescriptor::parse(&method.type_descriptor)?.num_arguments())
+    }
+}
+
+impl Vm {
+    pub fn new(class_loader: ClassLoader) -> Self {
+        Self { class_loader }
+    }
+
+    /// 注册一个类, 之后可以通过类名调用它的静态方法
+    pub fn register_class(&self, class_file: ClassFile) -> Rc<ClassFile> {
+        self.class_loader.define(class_file)
+    }
+
+    /// 执行类中的 `public static void main(String[])`, 目前参数数组只能传入 null
+    pub fn run_main(&self, class_name: &str) -> VmResult<()> {
+        let method = self.resolve_static_method(class_name, "main", "([Ljava/lang/String;)V")?;
+        if !method.class.methods[method.method_index].flags.contains(MethodFlags::PUBLIC) {
+            return Err(VmError::MethodNotFound(
+                class_name.to_string(),
+                "main".to_string(),
+                "([Ljava/lang/String;)V".to_string(),
+            ));
+        }
+        self.invoke(method, vec![Value::Null]).map(|_| ())
+    }
+
+    /// 调用一个静态方法, 返回值为 None 表示方法返回 void
+    pub fn invoke_static(
+        &self,
+        class_name: &str,
+        method_name: &str,
+        type_descriptor: &str,
+        args: Vec<Value>,
+    ) -> VmResult<Option<Value>> {
+        let method = self.resolve_static_method(class_name, method_name, type_descriptor)?;
+        self.invoke(method, args)
+    }
+
+    pub(crate) fn resolve_static_method(
+        &self,
+        class_name: &str,
+        method_name: &str,
+        type_descriptor: &str,
+    ) -> VmResult<ResolvedMethod> {
+        let class = self.class_loader.load(class_name)?;
+        class
+            .methods
+            .iter()
+            .position(|method| {
+                method.is_static()
+                    && method.name == method_name
+                    && method.type_descriptor == type_descriptor
+            })
+            .map(|method_index| ResolvedMethod {
+                class: Rc::clone(&class),
+                method_index,
+            })
+            .ok_or_else(|| {
+                VmError::MethodNotFound(
+                    class_name.to_string(),
+                    method_name.to_string(),
+                    type_descriptor.to_string(),
+                )
+            })
+    }
+
+    /// 使用显式的调用栈执行方法, 而不是依赖 Rust 的递归, 避免 Java 的深度递归耗尽本地栈
+    fn invoke(&self, method: ResolvedMethod, args: Vec<Value>) -> VmResult<Option<Value>> {
+        let mut call_stack = vec![Self::new_frame(method, args)?];
+        while let Some(frame) = call_stack.last_mut() {
+            match frame.execute(self)? {
+                FrameCompleted::InvokeMethod(method, args) => {
+                    if call_stack.len() >= MAX_CALL_DEPTH {
+                        return Err(VmError::StackOverflowError);
+                    }
+                    call_stack.push(Self::new_frame(method, args)?);
+                }
+                FrameCompleted::ReturnFromMethod(value) => {
+                    call_stack.pop();
+                    match (call_stack.last_mut(), value) {
+                        (None, value) => return Ok(value),
+                        (Some(caller), Some(value)) => caller.push(value)?,
+                        (Some(_), None) => {}
+                    }
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn new_frame(method: ResolvedMethod, args: Vec<Value>) -> VmResult<CallFrame> {
+        debug!(
+            "invoking {}.{} with args {:?}",
+            method.class.name, method.class.methods[method.method_index].name, args
+        );
+        CallFrame::new(method, args)
+    }
+}
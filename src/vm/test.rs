@@ -0,0 +1,238 @@
+use parser::read_buffer;
+
+use crate::class_loader::class_path::ClassPath;
+use crate::class_loader::error::ClassLoaderError;
+use crate::class_loader::ClassLoader;
+use crate::vm::error::VmError;
+use crate::vm::value::Value;
+use crate::vm::Vm;
+
+const CALCULATOR: &str = "rjvm/Calculator";
+
+fn create_vm() -> Vm {
+    let bytes = include_bytes!("../../tests/classes/rjvm/Calculator.class");
+    let vm = Vm::new(ClassLoader::default());
+    vm.register_class(read_buffer(bytes).unwrap());
+    vm
+}
+
+fn invoke(descriptor: &str, name: &str, args: Vec<Value>) -> Result<Option<Value>, VmError> {
+    create_vm().invoke_static(CALCULATOR, name, descriptor, args)
+}
+
+/// 把 Calculator 中的方法 name 的字节码替换为 code 之后调用它
+fn invoke_code(descriptor: &str, name: &str, code: &[u8], args: Vec<Value>) -> Result<Option<Value>, VmError> {
+    let mut class_file = read_buffer(include_bytes!("../../tests/classes/rjvm/Calculator.class")).unwrap();
+    let method = class_file
+        .methods
+        .iter_mut()
+        .find(|method| method.name == name && method.type_descriptor == descriptor)
+        .unwrap();
+    method.code.as_mut().unwrap().code = code.to_vec();
+    let vm = Vm::new(ClassLoader::default());
+    vm.register_class(class_file);
+    vm.invoke_static(CALCULATOR, name, descriptor, args)
+}
+
+#[test]
+fn can_run_main() {
+    assert_eq!(Ok(()), create_vm().run_main(CALCULATOR));
+}
+
+#[test]
+fn can_invoke_recursive_static_methods() {
+    assert_eq!(Ok(Some(Value::Int(55))), invoke("(I)I", "fibonacci", vec![Value::Int(10)]));
+}
+
+#[test]
+fn can_execute_long_loops() {
+    assert_eq!(
+        Ok(Some(Value::Long(2432902008176640000))),
+        invoke("(I)J", "factorial", vec![Value::Int(20)])
+    );
+}
+
+#[test]
+fn can_execute_double_arithmetic() {
+    assert_eq!(
+        Ok(Some(Value::Double(8.0 / 3.0))),
+        invoke(
+            "(DDD)D",
+            "average",
+            vec![Value::Double(1.5), Value::Double(2.5), Value::Double(4.0)]
+        )
+    );
+}
+
+#[test]
+fn can_execute_float_arithmetic() {
+    let (a, b) = (7.5f32, 2.0f32);
+    assert_eq!(
+        Ok(Some(Value::Float(a * b - a / b + a % b))),
+        invoke("(FF)F", "floatOps", vec![Value::Float(a), Value::Float(b)])
+    );
+}
+
+#[test]
+fn can_execute_int_arithmetic() {
+    let (a, b) = (-123i32, 45i32);
+    let expected = ((a << 3) ^ ((b as u32) >> 1) as i32) - (a % b) + (-a >> 2) + (a & b | b);
+    assert_eq!(
+        Ok(Some(Value::Int(expected))),
+        invoke("(II)I", "intOps", vec![Value::Int(a), Value::Int(b)])
+    );
+}
+
+#[test]
+fn can_execute_long_arithmetic() {
+    let (a, shift) = (-987654321i64, 67i32);
+    let expected = a.wrapping_shl(shift as u32)
+        .wrapping_add(((a as u64) >> (shift & 0x3f)) as i64)
+        .wrapping_sub((a >> 1).wrapping_mul(a));
+    assert_eq!(
+        Ok(Some(Value::Long(expected))),
+        invoke("(JI)J", "longOps", vec![Value::Long(a), Value::Int(shift)])
+    );
+}
+
+#[test]
+fn can_execute_conversions() {
+    let value = 70000.75f64;
+    let expected = (value as i64 as i8 as i32)
+        + (value as i32 as u16 as i32)
+        + (value as i32 as i16 as i32)
+        + (value as i64 as f32 as i32);
+    assert_eq!(
+        Ok(Some(Value::Int(expected))),
+        invoke("(D)I", "conversions", vec![Value::Double(value)])
+    );
+}
+
+#[test]
+fn can_execute_comparisons() {
+    assert_eq!(
+        Ok(Some(Value::Int(101))),
+        invoke(
+            "(DDJJ)I",
+            "compare",
+            vec![Value::Double(1.0), Value::Double(2.0), Value::Long(3), Value::Long(3)]
+        )
+    );
+    assert_eq!(
+        Ok(Some(Value::Int(0))),
+        invoke(
+            "(DDJJ)I",
+            "compare",
+            vec![Value::Double(f64::NAN), Value::Double(2.0), Value::Long(3), Value::Long(4)]
+        )
+    );
+}
+
+#[test]
+fn can_execute_switches() {
+    assert_eq!(Ok(Some(Value::Int(0))), invoke("(I)I", "season", vec![Value::Int(1)]));
+    assert_eq!(Ok(Some(Value::Int(2))), invoke("(I)I", "season", vec![Value::Int(7)]));
+    assert_eq!(Ok(Some(Value::Int(-1))), invoke("(I)I", "season", vec![Value::Int(13)]));
+    assert_eq!(Ok(Some(Value::Int(1))), invoke("(I)I", "sparse", vec![Value::Int(-1000)]));
+    assert_eq!(Ok(Some(Value::Int(3))), invoke("(I)I", "sparse", vec![Value::Int(1000000)]));
+    assert_eq!(Ok(Some(Value::Int(0))), invoke("(I)I", "sparse", vec![Value::Int(8)]));
+}
+
+#[test]
+fn reports_division_by_zero() {
+    assert_eq!(
+        Err(VmError::ArithmeticException("/ by zero".to_string())),
+        invoke("(II)I", "divide", vec![Value::Int(1), Value::Int(0)])
+    );
+    assert_eq!(
+        Ok(Some(Value::Int(i32::MIN))),
+        invoke("(II)I", "divide", vec![Value::Int(i32::MIN), Value::Int(-1)])
+    );
+}
+
+#[test]
+fn reports_stack_overflow() {
+    assert_eq!(
+        Err(VmError::StackOverflowError),
+        invoke("(I)I", "infinite", vec![Value::Int(0)])
+    );
+}
+
+#[test]
+fn reports_missing_methods() {
+    assert_eq!(
+        Err(VmError::MethodNotFound(
+            CALCULATOR.to_string(),
+            "missing".to_string(),
+            "()V".to_string()
+        )),
+        invoke("()V", "missing", vec![])
+    );
+    assert_eq!(
+        Err(VmError::ClassLoaderError(ClassLoaderError::ClassNotFound("rjvm/Missing".to_string()))),
+        create_vm().invoke_static("rjvm/Missing", "main", "()V", vec![])
+    );
+}
+
+#[test]
+fn can_invoke_methods_of_classes_in_the_class_path() {
+    let class_path = ClassPath::parse("tests/classes:tests/classes/math.jar").unwrap();
+    let vm = Vm::new(ClassLoader::new(class_path));
+    assert_eq!(
+        Ok(Some(Value::Int(152))),
+        vm.invoke_static("rjvm/Application", "compute", "(I)I", vec![Value::Int(12)])
+    );
+    assert_eq!(Ok(()), vm.run_main("rjvm/Application"));
+}
+
+#[test]
+fn reports_returned_values_of_wrong_type() {
+    // iload_0; lreturn
+    assert_eq!(
+        Err(VmError::ValidationException("expected long but found int 10".to_string())),
+        invoke_code("(I)I", "fibonacci", &[0x1a, 0xad], vec![Value::Int(10)])
+    );
+    // iload_0; areturn
+    assert_eq!(
+        Err(VmError::ValidationException("expected reference but found int 10".to_string())),
+        invoke_code("(I)I", "fibonacci", &[0x1a, 0xb0], vec![Value::Int(10)])
+    );
+    // aconst_null; ireturn
+    assert_eq!(
+        Err(VmError::ValidationException("expected int but found null".to_string())),
+        invoke_code("(I)I", "fibonacci", &[0x01, 0xac], vec![Value::Int(10)])
+    );
+}
+
+#[test]
+fn overwriting_the_second_slot_invalidates_a_long() {
+    // iconst_0; istore_1; lload_0; lreturn
+    assert_eq!(
+        Err(VmError::ValidationException("local variable 0 is read before being initialized".to_string())),
+        invoke_code("(JI)J", "longOps", &[0x03, 0x3c, 0x1e, 0xad], vec![Value::Long(1), Value::Int(2)])
+    );
+    // 覆盖 long 之后的 slot 不影响 long 本身: iconst_0; istore_2; lload_0; lreturn
+    assert_eq!(
+        Ok(Some(Value::Long(1))),
+        invoke_code("(JI)J", "longOps", &[0x03, 0x3d, 0x1e, 0xad], vec![Value::Long(1), Value::Int(2)])
+    );
+}
+
+#[test]
+fn reports_locals_of_wrong_type() {
+    // fload_0; freturn
+    assert_eq!(
+        Err(VmError::ValidationException("expected float in local variable 0 but found int 10".to_string())),
+        invoke_code("(I)I", "fibonacci", &[0x22, 0xae], vec![Value::Int(10)])
+    );
+    // aconst_null; istore_0
+    assert_eq!(
+        Err(VmError::ValidationException("expected int but found null".to_string())),
+        invoke_code("(I)I", "fibonacci", &[0x01, 0x3b], vec![Value::Int(10)])
+    );
+    // wide dload 0
+    assert_eq!(
+        Err(VmError::ValidationException("expected double in local variable 0 but found long 1".to_string())),
+        invoke_code("(JI)J", "longOps", &[0xc4, 0x18, 0x00, 0x00], vec![Value::Long(1), Value::Int(2)])
+    );
+}
@@ -0,0 +1,73 @@
+use std::fmt;
+use std::fmt::Formatter;
+
+/// 虚拟机中的值, 可以存放在局部变量表或者操作数栈上。
+/// long 与 double 在 JVM 规范中占据两个 slot, 在这里仍然是一个值, 但是通过 [Value::slots] 计算占用空间
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value {
+    /// 尚未赋值的局部变量, 或者 long/double 占据的第二个局部变量 slot
+    Uninitialized,
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    /// 目前还没有对象模型, 唯一支持的引用是 null
+    Null,
+}
+
+/// 值的类型, 用于校验 xload、xstore 等带有类型前缀的指令
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    Int,
+    Long,
+    Float,
+    Double,
+    Reference,
+}
+
+impl Value {
+    /// 尚未赋值的局部变量没有类型
+    pub fn kind(&self) -> Option<ValueKind> {
+        match self {
+            Value::Uninitialized => None,
+            Value::Int(_) => Some(ValueKind::Int),
+            Value::Long(_) => Some(ValueKind::Long),
+            Value::Float(_) => Some(ValueKind::Float),
+            Value::Double(_) => Some(ValueKind::Double),
+            Value::Null => Some(ValueKind::Reference),
+        }
+    }
+
+    /// 值在局部变量表与操作数栈中占据的 slot 数量
+    pub fn slots(&self) -> usize {
+        match self {
+            Value::Long(_) | Value::Double(_) => 2,
+            _ => 1,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Uninitialized => f.write_str("<uninitialized>"),
+            Value::Int(v) => write!(f, "int {v}"),
+            Value::Long(v) => write!(f, "long {v}"),
+            Value::Float(v) => write!(f, "float {v}"),
+            Value::Double(v) => write!(f, "double {v}"),
+            Value::Null => f.write_str("null"),
+        }
+    }
+}
+
+impl fmt::Display for ValueKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ValueKind::Int => "int",
+            ValueKind::Long => "long",
+            ValueKind::Float => "float",
+            ValueKind::Double => "double",
+            ValueKind::Reference => "reference",
+        })
+    }
+}
@@ -0,0 +1,59 @@
+use crate::vm::error::{VmError, VmResult};
+use crate::vm::value::Value;
+
+/// 栈帧中的操作数栈, 大小由 `max_stack` 决定。
+/// 注意 `max_stack` 的单位是 slot, long 和 double 占据两个 slot
+#[derive(Debug)]
+pub struct ValueStack {
+    values: Vec<Value>,
+    max_slots: usize,
+    used_slots: usize,
+}
+
+impl ValueStack {
+    pub fn with_max_size(max_slots: usize) -> Self {
+        Self {
+            values: Vec::with_capacity(max_slots),
+            max_slots,
+            used_slots: 0,
+        }
+    }
+
+    pub fn push(&mut self, value: Value) -> VmResult<()> {
+        if self.used_slots + value.slots() > self.max_slots {
+            return Err(VmError::OperandStackOverflow(self.max_slots));
+        }
+        self.used_slots += value.slots();
+        self.values.push(value);
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> VmResult<Value> {
+        let value = self.values.pop().ok_or(VmError::OperandStackUnderflow)?;
+        self.used_slots -= value.slots();
+        Ok(value)
+    }
+
+    /// 按照 slot 数量弹出若干个值, 返回的值保持在栈中的顺序 (栈底在前)。
+    /// dup 与 pop2 系列指令都是按照 slot 而不是按照值来定义的, 不能把一个 long 或 double 拆开
+    pub fn pop_slots(&mut self, slots: usize) -> VmResult<Vec<Value>> {
+        let mut popped = Vec::new();
+        let mut popped_slots = 0;
+        while popped_slots < slots {
+            let value = self.pop()?;
+            popped_slots += value.slots();
+            popped.push(value);
+        }
+        if popped_slots != slots {
+            return Err(VmError::ValidationException(format!(
+                "cannot split a category 2 value while taking {slots} slots from the stack"
+            )));
+        }
+        popped.reverse();
+        Ok(popped)
+    }
+
+    pub fn push_all(&mut self, values: &[Value]) -> VmResult<()> {
+        values.iter().try_for_each(|value| self.push(*value))
+    }
+}
@@ -0,0 +1,11 @@
+package rjvm;
+
+public class Application {
+    public static void main(String[] args) {
+        int result = compute(12);
+    }
+
+    public static int compute(int value) {
+        return MathUtils.square(value) + MathUtils.cube(2);
+    }
+}
@@ -0,0 +1,91 @@
+package rjvm;
+
+public class Calculator {
+    public static void main(String[] args) {
+        int fib = fibonacci(10);
+        long factorial = factorial(20);
+        double average = average(1.5, 2.5, 4.0);
+        int sum = fib + (int) factorial + (int) average;
+    }
+
+    public static int fibonacci(int n) {
+        if (n < 2) {
+            return n;
+        }
+        return fibonacci(n - 1) + fibonacci(n - 2);
+    }
+
+    public static long factorial(int n) {
+        long result = 1;
+        for (int i = 2; i <= n; i++) {
+            result *= i;
+        }
+        return result;
+    }
+
+    public static double average(double a, double b, double c) {
+        return (a + b + c) / 3;
+    }
+
+    public static float floatOps(float a, float b) {
+        return a * b - a / b + a % b;
+    }
+
+    public static int intOps(int a, int b) {
+        return ((a << 3) ^ (b >>> 1)) - (a % b) + (-a >> 2) + (a & b | b);
+    }
+
+    public static long longOps(long a, int shift) {
+        return (a << shift) + (a >>> shift) - (a >> 1) * a;
+    }
+
+    public static int conversions(double value) {
+        byte b = (byte) value;
+        char c = (char) value;
+        short s = (short) value;
+        long l = (long) value;
+        float f = (float) l;
+        return b + c + s + (int) f;
+    }
+
+    public static int compare(double a, double b, long c, long d) {
+        int result = 0;
+        if (a < b) {
+            result += 1;
+        }
+        if (a > b) {
+            result += 10;
+        }
+        if (c == d) {
+            result += 100;
+        }
+        return result;
+    }
+
+    public static int season(int month) {
+        switch (month) {
+            case 12: case 1: case 2: return 0;
+            case 3: case 4: case 5: return 1;
+            case 6: case 7: case 8: return 2;
+            case 9: case 10: case 11: return 3;
+            default: return -1;
+        }
+    }
+
+    public static int sparse(int value) {
+        switch (value) {
+            case -1000: return 1;
+            case 7: return 2;
+            case 1000000: return 3;
+            default: return 0;
+        }
+    }
+
+    public static int divide(int a, int b) {
+        return a / b;
+    }
+
+    public static int infinite(int n) {
+        return infinite(n + 1);
+    }
+}
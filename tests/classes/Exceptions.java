@@ -0,0 +1,22 @@
+package rjvm;
+
+import java.io.IOException;
+
+public class Exceptions {
+    public static final int LIMIT = 10;
+    private volatile long counter;
+
+    public static int parse(String text) throws IOException {
+        try {
+            return Integer.parseInt(text);
+        } catch (NumberFormatException e) {
+            throw new IOException(e);
+        } finally {
+            System.out.println("done");
+        }
+    }
+
+    public synchronized void increment() {
+        counter++;
+    }
+}
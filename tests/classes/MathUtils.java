@@ -0,0 +1,11 @@
+package rjvm;
+
+public class MathUtils {
+    public static int square(int value) {
+        return value * value;
+    }
+
+    public static int cube(int value) {
+        return square(value) * value;
+    }
+}
@@ -0,0 +1,5 @@
+package rjvm;
+
+public class Shadowed {
+    public static final String ORIGIN = "directory";
+}
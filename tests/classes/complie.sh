@@ -0,0 +1,9 @@
+#!/usr/bin/env sh
+javac -source 8 -target 8 -d . ./*.java
+# Exceptions 带有调试信息, 用于测试反汇编时输出局部变量名
+javac -g -source 8 -target 8 -d . ./Exceptions.java
+# MathUtils 只放在 jar 中, 用于测试从 jar 文件中加载类
+# Shadowed 在目录与 jar 中各有一份, 用于测试按照 classpath 的顺序查找
+javac -source 8 -target 8 -d jar ./jar/Shadowed.java
+jar cf math.jar rjvm/MathUtils.class -C jar rjvm/Shadowed.class
+rm -r rjvm/MathUtils.class jar/rjvm